@@ -7,11 +7,13 @@
 //! complexity of inserting, deleting or replacing a piece of text is always
 //! logarithmic in the size of the `Rope`.
 //!
-//! The crate has a relatively straightforward API. There are 3 structs to be
+//! The crate has a relatively straightforward API. There are 4 structs to be
 //! aware of:
 //!
 //! - [`Rope`]: the star of the crate;
 //! - [`RopeSlice`]: an immutable slice of a `Rope`;
+//! - [`RopeSpan`]: an owned version of a `RopeSlice` that doesn't borrow the
+//! `Rope` it was created from;
 //! - [`RopeBuilder`]: an incremental `Rope` builder.
 //!
 //! plus the [`iter`] module which contains iterators over `Rope`s and
@@ -130,24 +132,109 @@
 //!
 //! - `simd` (enabled by default): enables SIMD on supported platforms;
 //!
+//! - `std` (enabled by default): enables the integrations with the standard
+//! library, like the `std::error::Error` implementation for [`RopeError`].
+//! crop is `no_std` and only depends on `alloc` when this is disabled;
+//!
+//! - `bracket-depth` (disabled by default): caches the nesting depth of the
+//! brackets in every internal node of the `Rope`, enabling the
+//! `matching_bracket()` and `enclosing_brackets()` methods on `Rope`s which
+//! run in logarithmic time once the depths are cached;
+//!
+//! - `content-hash` (disabled by default): caches a hash of the text in every
+//! internal node of the `Rope`, enabling the `content_hash()` methods on
+//! `Rope`s and `RopeSlice`s which run in logarithmic time once the hashes
+//! are cached;
+//!
+//! - `crdt` (disabled by default): enables the [`crdt`] module, which
+//! contains a text CRDT whose visible text is stored in a `Rope`;
+//!
+//! - `display-width` (disabled by default): enables the `Cells` variant of
+//! [`ColumnUnit`], which measures the columns of block selections in the
+//! number of cells each character takes up when displayed;
+//!
+//! - `encoding` (disabled by default): enables the [`encoding`] module and
+//! the `Rope::from_reader_with_encoding()` and `Rope::write_encoded()`
+//! methods, which load and save `Rope`s in any of the encodings supported
+//! by [`encoding_rs`](https://docs.rs/encoding_rs), detecting and
+//! preserving byte order marks. This also enables the `std` feature;
+//!
 //! - `graphemes` (disabled by default): enables a few grapheme-oriented APIs
 //! on `Rope`s and `RopeSlice`s such as the
-//! [`Graphemes`](crate::iter::Graphemes) iterator and others;
+//! [`Graphemes`](crate::iter::Graphemes) iterator, the word and sentence
+//! iterators, `Rope::map_range()` and others;
+//!
+//! - `normalization` (disabled by default): enables `eq_normalized()` on
+//! `Rope`s and `RopeSlice`s, which compares their text with a string up to
+//! Unicode canonical equivalence, and the NFC and NFD transforms in the
+//! `transforms` module;
+//!
+//! - `rayon` (disabled by default): enables `Rope::from_str_parallel()`,
+//! which builds a `Rope` from a large string using the
+//! [`rayon`](https://docs.rs/rayon) thread pool;
+//!
+//! - `text-stats` (disabled by default): makes the `Rope` track the number
+//! of `char`s it contains and caches the number of words and paragraphs in
+//! every internal node, enabling the `char_count()`, `word_count()` and
+//! `paragraph_count()` methods on `Rope`s and `RopeSlice`s which run in
+//! logarithmic time once the counts are cached;
 //!
 //! - `utf16-metric` (disabled by default): makes the `Rope` and `RopeSlice`
 //! track the UTF-16 code units they'd have if their content was stored as
 //! UTF-16 instead of UTF-8, allowing them to efficiently convert UTF-16
 //! code unit offsets to and from byte offsets in logarithmic time.
 
+#![allow(clippy::doc_lazy_continuation)]
 #![allow(clippy::explicit_auto_deref)]
 #![allow(clippy::module_inception)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![deny(missing_docs)]
 #![deny(rustdoc::broken_intra_doc_links)]
 #![deny(rustdoc::private_intra_doc_links)]
+#![no_std]
 
 extern crate alloc;
 
+#[cfg(any(test, feature = "std"))]
+extern crate std;
+
+pub mod byte_rope {
+    //! A rope over arbitrary bytes.
+    //!
+    //! A [`ByteRope`] shares the B-tree of [`Rope`](crate::Rope)s but has no
+    //! UTF-8 invariants, which makes it suitable for editing binary data,
+    //! e.g. in a hex editor.
+
+    pub use crate::rope::byte_rope::{ByteRope, ByteSlice, Bytes, Chunks};
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "crdt")))]
+#[cfg(feature = "crdt")]
+pub mod crdt {
+    //! A text CRDT for peer-to-peer collaborative editing.
+    //!
+    //! A [`Replica`] keeps the visible text in a [`Rope`](crate::Rope) and
+    //! exchanges [`Operation`]s with the other replicas, which converge to
+    //! the same text once they've all applied the same operations, no matter
+    //! in which order.
+
+    pub use crate::rope::crdt::*;
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "encoding")))]
+#[cfg(feature = "encoding")]
+pub mod encoding {
+    //! Loading and saving [`Rope`](crate::Rope)s in encodings other than
+    //! UTF-8.
+    //!
+    //! The encodings themselves are provided by the re-exported
+    //! [`encoding_rs`] crate, see
+    //! [`Rope::from_reader_with_encoding()`](crate::Rope::from_reader_with_encoding())
+    //! and [`Rope::write_encoded()`](crate::Rope::write_encoded()).
+
+    pub use crate::rope::encoding::*;
+}
+
 pub mod iter {
     //! Iterators over [`Rope`](crate::Rope)s and
     //! [`RopeSlice`](crate::RopeSlice)s.
@@ -157,6 +244,31 @@ pub mod iter {
 
 mod rope;
 
+#[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+#[cfg(feature = "graphemes")]
+pub mod transforms {
+    //! Built-in text transforms to be used with
+    //! [`Rope::map_range()`](crate::Rope::map_range()).
+    //!
+    //! All of them return a borrowed `Cow` when they don't change their
+    //! input, which lets `map_range()` leave the `Rope` untouched if none of
+    //! its text changed.
+
+    pub use crate::rope::transforms::*;
+}
+
+pub mod ot {
+    //! Operational transform primitives for collaborative editing.
+    //!
+    //! A [`Delta`] describes an edit of a whole text as a sequence of
+    //! retains, insertions and deletions. Concurrent deltas can be combined
+    //! with [`Delta::compose()`] and [`Delta::transform()`], undone with
+    //! [`Delta::invert()`] and applied with
+    //! [`Rope::apply_delta()`](crate::Rope::apply_delta()).
+
+    pub use crate::rope::ot::*;
+}
+
 #[doc(hidden)]
 pub mod tree;
 
@@ -168,7 +280,20 @@ pub use rope::{
     gap_slice::GapSlice,
     metrics::ChunkSummary,
 };
-pub use rope::{Rope, RopeBuilder, RopeSlice};
+pub use rope::{
+    ColumnUnit,
+    Indent,
+    InvalidSequence,
+    InvalidUtf8,
+    Rope,
+    RopeBuilder,
+    RopeError,
+    RopeSlice,
+    RopeSpan,
+    RopeStats,
+    Selection,
+    SelectionSet,
+};
 
 #[inline]
 pub(crate) fn range_bounds_to_start_end<T, B>(
@@ -178,18 +303,20 @@ pub(crate) fn range_bounds_to_start_end<T, B>(
 ) -> (usize, usize)
 where
     B: core::ops::RangeBounds<T>,
-    T: core::ops::Add<usize, Output = usize> + Into<usize> + Copy,
+    T: Into<usize> + Copy,
 {
     use core::ops::Bound;
 
+    // An inclusive `usize::MAX` is out of bounds for every range, so we
+    // saturate instead of overflowing and let the caller reject it.
     let start = match range.start_bound() {
         Bound::Included(&n) => n.into(),
-        Bound::Excluded(&n) => n + 1,
+        Bound::Excluded(&n) => n.into().saturating_add(1),
         Bound::Unbounded => lo,
     };
 
     let end = match range.end_bound() {
-        Bound::Included(&n) => n + 1,
+        Bound::Included(&n) => n.into().saturating_add(1),
         Bound::Excluded(&n) => n.into(),
         Bound::Unbounded => hi,
     };
@@ -0,0 +1,144 @@
+use super::RopeSlice;
+
+/// The unit used to measure the columns of a block selection, passed to
+/// [`Rope::block_slice()`](crate::Rope::block_slice) and
+/// [`Rope::replace_block()`](crate::Rope::replace_block).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColumnUnit {
+    /// Every byte is one column wide.
+    Bytes,
+
+    /// Every `char` is one column wide.
+    Chars,
+
+    /// Every `char` is as wide as the number of cells it takes up when
+    /// displayed in a terminal, e.g. two for most CJK characters and zero for
+    /// combining marks. Control characters, tabs included, are one cell wide.
+    #[cfg_attr(docsrs, doc(cfg(feature = "display-width")))]
+    #[cfg(feature = "display-width")]
+    Cells,
+}
+
+impl ColumnUnit {
+    /// Returns the number of columns taken up by the given character.
+    #[inline]
+    fn width_of(self, ch: char) -> usize {
+        match self {
+            Self::Bytes => ch.len_utf8(),
+            Self::Chars => 1,
+            #[cfg(feature = "display-width")]
+            Self::Cells => {
+                unicode_width::UnicodeWidthChar::width(ch).unwrap_or(1)
+            },
+        }
+    }
+}
+
+/// The part of a line covered by a column range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct BlockSpan {
+    /// The byte offset of the first non-zero-width character starting at or
+    /// after the start of the column range, or the length of the line if
+    /// there's none.
+    pub(super) start: usize,
+
+    /// The byte offset of the first non-zero-width character starting at or
+    /// after the end of the column range, or the length of the line if there's
+    /// none.
+    pub(super) end: usize,
+
+    /// How many columns the line falls short of the start of the column
+    /// range. It's only non-zero if `start` is the length of the line.
+    pub(super) missing: usize,
+}
+
+/// Returns the span of the line (not including its line terminator) covered
+/// by the `start..end` column range, where a character is part of the span
+/// if the column it starts at is inside the range. Zero-width characters (e.g.
+/// combining marks) always stay with the character they follow.
+#[inline]
+pub(super) fn span_of_columns(
+    line: RopeSlice<'_>,
+    start: usize,
+    end: usize,
+    unit: ColumnUnit,
+) -> BlockSpan {
+    debug_assert!(start <= end);
+
+    let byte_len = line.byte_len();
+
+    // Byte columns can be converted in constant time.
+    if unit == ColumnUnit::Bytes {
+        return BlockSpan {
+            start: line.ceil_char_boundary(start.min(byte_len)),
+            end: line.ceil_char_boundary(end.min(byte_len)),
+            missing: start.saturating_sub(byte_len),
+        };
+    }
+
+    let mut span = BlockSpan { start: byte_len, end: byte_len, missing: 0 };
+
+    let mut column = 0;
+
+    let mut byte_offset = 0;
+
+    let mut chars = line.chars().peekable();
+
+    while column < start {
+        let Some(ch) = chars.next() else {
+            span.missing = start - column;
+            return span;
+        };
+        column += unit.width_of(ch);
+        byte_offset += ch.len_utf8();
+    }
+
+    // Zero-width characters start at the same column as the one after them,
+    // but they belong to the one before them.
+    if start > 0 {
+        while let Some(ch) = chars.next_if(|&ch| unit.width_of(ch) == 0) {
+            byte_offset += ch.len_utf8();
+        }
+    }
+
+    span.start = byte_offset;
+
+    while column < end {
+        let Some(ch) = chars.next() else {
+            return span;
+        };
+        column += unit.width_of(ch);
+        byte_offset += ch.len_utf8();
+    }
+
+    if end > 0 {
+        while let Some(ch) = chars.next_if(|&ch| unit.width_of(ch) == 0) {
+            byte_offset += ch.len_utf8();
+        }
+    }
+
+    span.end = byte_offset;
+
+    span
+}
+
+/// Splits a raw line into its contents and its line terminator (`\n`, `\r\n`
+/// or nothing if it's the last line).
+#[inline]
+pub(super) fn split_terminator(
+    raw_line: RopeSlice<'_>,
+) -> (RopeSlice<'_>, RopeSlice<'_>) {
+    let byte_len = raw_line.byte_len();
+
+    let terminator_len = match byte_len {
+        0 => 0,
+        _ if raw_line.byte(byte_len - 1) != b'\n' => 0,
+        1 => 1,
+        _ if raw_line.byte(byte_len - 2) == b'\r' => 2,
+        _ => 1,
+    };
+
+    let split = byte_len - terminator_len;
+
+    (raw_line.byte_slice(..split), raw_line.byte_slice(split..))
+}
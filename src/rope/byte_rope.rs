@@ -0,0 +1,700 @@
+//! This module contains the byte-oriented rope exported in the
+//! [`byte_rope`](crate::byte_rope) module.
+
+use alloc::vec::Vec;
+use core::ops::RangeBounds;
+
+use super::gap_buffer::GapBuffer;
+use super::metrics::{ByteMetric, ChunkSummary, RawLineMetric};
+use super::utils::{self, panic_messages as panic};
+use super::Rope;
+use crate::range_bounds_to_start_end;
+use crate::tree::{Leaves, Tree, TreeSlice};
+
+#[cfg(any(test, feature = "small_chunks"))]
+const CHUNK_MAX_BYTES: usize = 4;
+
+#[cfg(not(any(test, feature = "small_chunks")))]
+const CHUNK_MAX_BYTES: usize = 2048;
+
+/// A rope over arbitrary bytes, for when the contents aren't text, e.g. in a
+/// hex editor.
+///
+/// A `ByteRope` is stored in the same B-tree of gap buffers used by [`Rope`],
+/// so edits and slicing run in logarithmic time, consecutive edits at the same
+/// place are cheap and clones are cheap, but none of its methods care about
+/// UTF-8: any byte offset is a valid place to insert, delete or slice at.
+///
+/// The `\n` bytes are counted as line breaks, which lets a `ByteRope` convert
+/// between byte and line offsets in logarithmic time for inputs that do have
+/// lines, like text in an unknown encoding.
+#[derive(Clone, Default)]
+pub struct ByteRope {
+    tree: Tree<{ Rope::arity() }, ByteChunk>,
+}
+
+impl ByteRope {
+    #[doc(hidden)]
+    pub fn assert_invariants(&self) {
+        self.tree.assert_invariants();
+
+        let leaves = self.tree.leaves();
+
+        if leaves.len() == 1 {
+            return;
+        }
+
+        for chunk in leaves {
+            assert!(
+                chunk.len() >= ByteChunk::chunk_min(),
+                "The chunk {:?} was supposed to contain at least {} bytes \
+                 but actually contains {}",
+                chunk,
+                ByteChunk::chunk_min(),
+                chunk.len()
+            );
+
+            chunk.assert_invariants();
+        }
+    }
+
+    /// Returns the byte at `byte_index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte index is out of bounds (i.e. greater than or equal
+    /// to [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::byte_rope::ByteRope;
+    /// #
+    /// let r = ByteRope::from(&b"\x00\xFF\x80"[..]);
+    ///
+    /// assert_eq!(r.byte(1), 0xFF);
+    /// assert_eq!(r.byte(2), 0x80);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn byte(&self, byte_index: usize) -> u8 {
+        if byte_index >= self.byte_len() {
+            panic::byte_index_out_of_bounds(byte_index, self.byte_len());
+        }
+
+        let (chunk, ByteMetric(chunk_byte_offset)) =
+            self.tree.leaf_at_measure(ByteMetric(byte_index + 1));
+
+        chunk.byte(byte_index - chunk_byte_offset)
+    }
+
+    /// Returns the length of the `ByteRope` in bytes.
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        self.tree.summary().bytes()
+    }
+
+    /// Returns the byte offset of the start of the given line, where lines
+    /// are separated by `\n` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line offset is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::byte_rope::ByteRope;
+    /// #
+    /// let r = ByteRope::from(&b"foo\n\xFF\xFE\nbar"[..]);
+    ///
+    /// assert_eq!(r.byte_of_line(1), 4);
+    /// assert_eq!(r.byte_of_line(2), 7);
+    /// assert_eq!(r.byte_of_line(r.line_len()), r.byte_len());
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_line(&self, line_offset: usize) -> usize {
+        if line_offset > self.line_len() {
+            panic::line_offset_out_of_bounds(line_offset, self.line_len());
+        }
+
+        if line_offset > self.tree.summary().line_breaks() {
+            return self.byte_len();
+        }
+
+        let ByteMetric(byte_offset) =
+            self.tree.convert_measure(RawLineMetric(line_offset));
+
+        byte_offset
+    }
+
+    /// Returns an immutable slice of the `ByteRope` in the specified byte
+    /// range, where the start and end of the range are interpreted as
+    /// offsets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::byte_rope::ByteRope;
+    /// #
+    /// let r = ByteRope::from(&b"\xCA\xFE\xBA\xBE"[..]);
+    ///
+    /// assert_eq!(r.byte_slice(1..3), &b"\xFE\xBA"[..]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn byte_slice<R>(&self, byte_range: R) -> ByteSlice<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.byte_len());
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        if end > self.byte_len() {
+            panic::byte_offset_out_of_bounds(end, self.byte_len());
+        }
+
+        ByteSlice {
+            tree_slice: self.tree.slice(ByteMetric(start)..ByteMetric(end)),
+        }
+    }
+
+    /// Returns an iterator over the bytes of this `ByteRope`.
+    #[inline]
+    pub fn bytes(&self) -> Bytes<'_> {
+        Bytes::new(self.chunks())
+    }
+
+    /// Returns an iterator over the chunks of this `ByteRope`.
+    #[inline]
+    pub fn chunks(&self) -> Chunks<'_> {
+        Chunks::new(self.tree.leaves())
+    }
+
+    /// Deletes the contents of the `ByteRope` within the specified byte
+    /// range, where the start and end of the range are interpreted as
+    /// offsets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::byte_rope::ByteRope;
+    /// #
+    /// let mut r = ByteRope::from(&b"\x00\x01\x02\x03"[..]);
+    ///
+    /// r.delete(1..3);
+    /// assert_eq!(r, &b"\x00\x03"[..]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn delete<R>(&mut self, byte_range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        self.replace(byte_range, []);
+    }
+
+    /// Inserts `bytes` in the `ByteRope` at the given byte offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::byte_rope::ByteRope;
+    /// #
+    /// let mut r = ByteRope::from(&b"\xDE\xEF"[..]);
+    ///
+    /// r.insert(1, b"\xAD\xBE");
+    /// assert_eq!(r, &b"\xDE\xAD\xBE\xEF"[..]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn insert<T>(&mut self, byte_offset: usize, bytes: T)
+    where
+        T: AsRef<[u8]>,
+    {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let bytes = bytes.as_ref();
+
+        if !bytes.is_empty() {
+            self.tree.replace(
+                ByteMetric(byte_offset)..ByteMetric(byte_offset),
+                bytes,
+            );
+        }
+    }
+
+    /// Returns `true` if the `ByteRope`'s byte length is zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.byte_len() == 0
+    }
+
+    /// Returns the number of lines in the `ByteRope`, where lines are
+    /// separated by `\n` bytes.
+    ///
+    /// Like for [`Rope::line_len()`], the final line break is optional and
+    /// doesn't count as a separate empty line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::byte_rope::ByteRope;
+    /// #
+    /// assert_eq!(ByteRope::new().line_len(), 0);
+    /// assert_eq!(ByteRope::from(&b"\xFF\n\xFF"[..]).line_len(), 2);
+    /// assert_eq!(ByteRope::from(&b"\xFF\n\xFF\n"[..]).line_len(), 2);
+    /// ```
+    #[inline]
+    pub fn line_len(&self) -> usize {
+        line_len(self.tree.summary(), self.chunks().next_back())
+    }
+
+    /// Returns the line offset of the given byte, where lines are separated
+    /// by `\n` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::byte_rope::ByteRope;
+    /// #
+    /// let r = ByteRope::from(&b"foo\n\xFF\xFE\nbar"[..]);
+    ///
+    /// assert_eq!(r.line_of_byte(3), 0);
+    /// assert_eq!(r.line_of_byte(4), 1);
+    /// assert_eq!(r.line_of_byte(r.byte_len()), 2);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn line_of_byte(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let RawLineMetric(line_offset) =
+            self.tree.convert_measure(ByteMetric(byte_offset));
+
+        line_offset
+    }
+
+    /// Returns an immutable slice of the `ByteRope` in the specified line
+    /// range, where the start and end of the range are interpreted as
+    /// offsets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::byte_rope::ByteRope;
+    /// #
+    /// let r = ByteRope::from(&b"foo\n\xFF\xFE\nbar"[..]);
+    ///
+    /// assert_eq!(r.line_slice(1..2), &b"\xFF\xFE\n"[..]);
+    /// assert_eq!(r.line_slice(2..), &b"bar"[..]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn line_slice<R>(&self, line_range: R) -> ByteSlice<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(line_range, 0, self.line_len());
+
+        if start > end {
+            panic::line_start_after_end(start, end);
+        }
+
+        if end > self.line_len() {
+            panic::line_offset_out_of_bounds(end, self.line_len());
+        }
+
+        self.byte_slice(self.byte_of_line(start)..self.byte_of_line(end))
+    }
+
+    /// Creates a new, empty `ByteRope`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the contents of the `ByteRope` within the specified byte
+    /// range with `bytes`, where the start and end of the range are
+    /// interpreted as offsets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::byte_rope::ByteRope;
+    /// #
+    /// let mut r = ByteRope::from(&b"\x7FELF\x01"[..]);
+    ///
+    /// r.replace(4.., b"\x02\x01");
+    /// assert_eq!(r, &b"\x7FELF\x02\x01"[..]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn replace<R, T>(&mut self, byte_range: R, bytes: T)
+    where
+        R: RangeBounds<usize>,
+        T: AsRef<[u8]>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.byte_len());
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        if end > self.byte_len() {
+            panic::byte_offset_out_of_bounds(end, self.byte_len());
+        }
+
+        let bytes = bytes.as_ref();
+
+        if start < end || !bytes.is_empty() {
+            self.tree.replace(ByteMetric(start)..ByteMetric(end), bytes);
+        }
+    }
+}
+
+impl From<&[u8]> for ByteRope {
+    #[inline]
+    fn from(bytes: &[u8]) -> Self {
+        Self {
+            tree: Tree::from_leaves(
+                ByteChunk::segmenter(bytes).map(ByteChunk::from),
+            ),
+        }
+    }
+}
+
+impl From<Vec<u8>> for ByteRope {
+    #[inline]
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::from(&*bytes)
+    }
+}
+
+impl From<ByteSlice<'_>> for ByteRope {
+    #[inline]
+    fn from(slice: ByteSlice<'_>) -> Self {
+        Self { tree: Tree::from(slice.tree_slice) }
+    }
+}
+
+impl From<ByteRope> for Vec<u8> {
+    #[inline]
+    fn from(rope: ByteRope) -> Self {
+        rope.chunks().flatten().copied().collect()
+    }
+}
+
+impl core::fmt::Debug for ByteRope {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("ByteRope(")?;
+        f.debug_list().entries(self.bytes()).finish()?;
+        f.write_str(")")
+    }
+}
+
+/// An immutable slice of a [`ByteRope`].
+#[derive(Copy, Clone)]
+pub struct ByteSlice<'a> {
+    tree_slice: TreeSlice<'a, { Rope::arity() }, ByteChunk>,
+}
+
+impl<'a> ByteSlice<'a> {
+    /// Returns the byte at `byte_index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte index is out of bounds (i.e. greater than or equal
+    /// to [`byte_len()`](Self::byte_len())).
+    #[track_caller]
+    #[inline]
+    pub fn byte(&self, byte_index: usize) -> u8 {
+        if byte_index >= self.byte_len() {
+            panic::byte_index_out_of_bounds(byte_index, self.byte_len());
+        }
+
+        let (chunk, ByteMetric(chunk_byte_offset)) =
+            self.tree_slice.leaf_at_measure(ByteMetric(byte_index + 1));
+
+        chunk.byte(byte_index - chunk_byte_offset)
+    }
+
+    /// Returns the length of the `ByteSlice` in bytes.
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        self.tree_slice.summary().bytes()
+    }
+
+    /// Returns an immutable slice of the `ByteSlice` in the specified byte
+    /// range, where the start and end of the range are interpreted as
+    /// offsets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    #[track_caller]
+    #[inline]
+    pub fn byte_slice<R>(&self, byte_range: R) -> ByteSlice<'a>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.byte_len());
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        if end > self.byte_len() {
+            panic::byte_offset_out_of_bounds(end, self.byte_len());
+        }
+
+        ByteSlice {
+            tree_slice: self
+                .tree_slice
+                .slice(ByteMetric(start)..ByteMetric(end)),
+        }
+    }
+
+    /// Returns an iterator over the bytes of this `ByteSlice`.
+    #[inline]
+    pub fn bytes(&self) -> Bytes<'a> {
+        Bytes::new(self.chunks())
+    }
+
+    /// Returns an iterator over the chunks of this `ByteSlice`.
+    #[inline]
+    pub fn chunks(&self) -> Chunks<'a> {
+        Chunks::new(self.tree_slice.leaves())
+    }
+
+    /// Returns `true` if the `ByteSlice`'s byte length is zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.byte_len() == 0
+    }
+
+    /// Returns the number of lines in the `ByteSlice`, where lines are
+    /// separated by `\n` bytes.
+    #[inline]
+    pub fn line_len(&self) -> usize {
+        line_len(self.tree_slice.summary(), self.chunks().next_back())
+    }
+}
+
+impl From<ByteSlice<'_>> for Vec<u8> {
+    #[inline]
+    fn from(slice: ByteSlice<'_>) -> Self {
+        slice.chunks().flatten().copied().collect()
+    }
+}
+
+impl core::fmt::Debug for ByteSlice<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("ByteSlice(")?;
+        f.debug_list().entries(self.bytes()).finish()?;
+        f.write_str(")")
+    }
+}
+
+/// Returns the number of lines given the summary of a `ByteRope` or
+/// `ByteSlice` and its last chunk.
+#[inline]
+fn line_len(summary: &ChunkSummary, last_chunk: Option<&[u8]>) -> usize {
+    let has_trailing_newline =
+        last_chunk.and_then(|chunk| chunk.last()) == Some(&b'\n');
+
+    summary.line_breaks() + 1
+        - (has_trailing_newline as usize)
+        - ((summary.bytes() == 0) as usize)
+}
+
+macro_rules! impl_eq {
+    ($lhs:ty, $rhs:ty) => {
+        impl core::cmp::PartialEq<$rhs> for $lhs {
+            #[inline]
+            fn eq(&self, rhs: &$rhs) -> bool {
+                self.byte_len() == rhs.byte_len()
+                    && utils::chunks_eq_chunks(self.chunks(), rhs.chunks())
+            }
+        }
+    };
+
+    ($lhs:ty, $rhs:ty, slice) => {
+        impl core::cmp::PartialEq<$rhs> for $lhs {
+            #[inline]
+            fn eq(&self, rhs: &$rhs) -> bool {
+                let rhs: &[u8] = rhs.as_ref();
+                self.byte_len() == rhs.len()
+                    && utils::chunks_eq_chunks(
+                        self.chunks(),
+                        core::iter::once(rhs),
+                    )
+            }
+        }
+    };
+}
+
+impl_eq!(ByteRope, ByteRope);
+impl_eq!(ByteRope, ByteSlice<'_>);
+impl_eq!(ByteSlice<'_>, ByteRope);
+impl_eq!(ByteSlice<'_>, ByteSlice<'_>);
+impl_eq!(ByteRope, [u8], slice);
+impl_eq!(ByteRope, &[u8], slice);
+impl_eq!(ByteRope, Vec<u8>, slice);
+impl_eq!(ByteSlice<'_>, [u8], slice);
+impl_eq!(ByteSlice<'_>, &[u8], slice);
+impl_eq!(ByteSlice<'_>, Vec<u8>, slice);
+
+impl core::cmp::Eq for ByteRope {}
+
+impl core::cmp::Eq for ByteSlice<'_> {}
+
+/// An iterator over the `&[u8]` chunks of [`ByteRope`]s and [`ByteSlice`]s.
+///
+/// This struct is created by the `chunks` method on
+/// [`ByteRope`](ByteRope::chunks()) and [`ByteSlice`](ByteSlice::chunks()).
+#[derive(Clone)]
+pub struct Chunks<'a> {
+    leaves: Leaves<'a, { Rope::arity() }, ByteChunk>,
+    forward_extra_right: Option<&'a [u8]>,
+    backward_extra_left: Option<&'a [u8]>,
+}
+
+impl<'a> Chunks<'a> {
+    #[inline]
+    fn new(leaves: Leaves<'a, { Rope::arity() }, ByteChunk>) -> Self {
+        Self { leaves, forward_extra_right: None, backward_extra_left: None }
+    }
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = &'a [u8];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(extra) = self.forward_extra_right.take() {
+            return Some(extra);
+        }
+
+        // The only empty leaf is the one of an empty `ByteRope`.
+        let Some(chunk) = self.leaves.find(|chunk| chunk.len() > 0) else {
+            return self.backward_extra_left.take();
+        };
+
+        if chunk.left_chunk().is_empty() {
+            Some(chunk.right_chunk())
+        } else {
+            if !chunk.right_chunk().is_empty() {
+                self.forward_extra_right = Some(chunk.right_chunk());
+            }
+            Some(chunk.left_chunk())
+        }
+    }
+}
+
+impl DoubleEndedIterator for Chunks<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if let Some(extra) = self.backward_extra_left.take() {
+            return Some(extra);
+        }
+
+        let Some(chunk) = self.leaves.rfind(|chunk| chunk.len() > 0) else {
+            return self.forward_extra_right.take();
+        };
+
+        if chunk.right_chunk().is_empty() {
+            Some(chunk.left_chunk())
+        } else {
+            if !chunk.left_chunk().is_empty() {
+                self.backward_extra_left = Some(chunk.left_chunk());
+            }
+            Some(chunk.right_chunk())
+        }
+    }
+}
+
+impl core::iter::FusedIterator for Chunks<'_> {}
+
+/// An iterator over the bytes of [`ByteRope`]s and [`ByteSlice`]s.
+///
+/// This struct is created by the `bytes` method on
+/// [`ByteRope`](ByteRope::bytes()) and [`ByteSlice`](ByteSlice::bytes()).
+#[derive(Clone)]
+pub struct Bytes<'a> {
+    bytes: core::iter::Copied<core::iter::Flatten<Chunks<'a>>>,
+}
+
+impl<'a> Bytes<'a> {
+    #[inline]
+    fn new(chunks: Chunks<'a>) -> Self {
+        Self { bytes: chunks.flatten().copied() }
+    }
+}
+
+impl Iterator for Bytes<'_> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        self.bytes.next()
+    }
+}
+
+impl DoubleEndedIterator for Bytes<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<u8> {
+        self.bytes.next_back()
+    }
+}
+
+impl core::iter::FusedIterator for Bytes<'_> {}
+
+/// The leaves of the tree storing the bytes of a [`ByteRope`].
+type ByteChunk = GapBuffer<CHUNK_MAX_BYTES, [u8]>;
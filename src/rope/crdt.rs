@@ -0,0 +1,1065 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::{Add, AddAssign, Range, RangeBounds, Sub, SubAssign};
+
+use super::utils::panic_messages as panic;
+use super::vec_chunk::{impl_metric_ops, VecChunk};
+use super::Rope;
+use crate::range_bounds_to_start_end;
+use crate::tree::{
+    AsSlice,
+    BaseMeasured,
+    Metric,
+    SlicingMetric,
+    Summarize,
+    Tree,
+};
+
+#[cfg(any(test, feature = "small_chunks"))]
+const CHUNK_MAX_ITEMS: usize = 4;
+
+#[cfg(not(any(test, feature = "small_chunks")))]
+const CHUNK_MAX_ITEMS: usize = 64;
+
+/// The label increment given to new characters when there's room for it.
+/// It's small with `small_chunks` so that the tests have to relabel often.
+#[cfg(any(test, feature = "small_chunks"))]
+const LABEL_SPACING: u64 = 4;
+
+#[cfg(not(any(test, feature = "small_chunks")))]
+const LABEL_SPACING: u64 = 1 << 16;
+
+/// The unique identifier of a character inserted by a [`Replica`].
+///
+/// Ids are ordered by their clock first and by the id of the replica that
+/// created them second, which is the order used to break ties between
+/// concurrent insertions at the same position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ItemId {
+    /// The Lamport timestamp of the insertion.
+    pub clock: u64,
+
+    /// The id of the replica that inserted the character.
+    pub replica: u64,
+}
+
+/// An edit made by a [`Replica`], to be sent to the other replicas of the
+/// text and applied with [`Replica::apply()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    /// Inserts some text right after the `origin` character, or at the start
+    /// of the text if there's no origin.
+    ///
+    /// The `n`-th character of the text has the same id as `id` except for
+    /// its clock, which is `id.clock + n`.
+    Insert {
+        /// The id of the first inserted character.
+        id: ItemId,
+
+        /// The id of the character the text was inserted after.
+        origin: Option<ItemId>,
+
+        /// The inserted text.
+        text: String,
+    },
+
+    /// Deletes the characters with the given ids.
+    Delete {
+        /// The ids of the deleted characters.
+        ids: Vec<ItemId>,
+    },
+}
+
+/// An error returned when applying an [`Operation`] to a [`Replica`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CrdtError {
+    /// The operation refers to a character that the replica hasn't seen yet,
+    /// which means the operations weren't delivered in causal order.
+    UnknownItem {
+        /// The id of the unknown character.
+        id: ItemId,
+    },
+}
+
+impl core::fmt::Display for CrdtError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::UnknownItem { id } => write!(
+                f,
+                "unknown item: no character with clock {} inserted by \
+                 replica {} has been integrated yet",
+                id.clock, id.replica
+            ),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+impl std::error::Error for CrdtError {}
+
+/// A replica of a text edited collaboratively by several peers, whose visible
+/// text is stored in a [`Rope`].
+///
+/// Every character ever inserted in the text is kept in a sequence ordered
+/// like in an RGA (Replicated Growable Array), and deleted characters are
+/// kept as tombstones. The sequence is stored in a B-tree whose internal
+/// nodes track how many characters and how many visible bytes their subtree
+/// contains, so converting between a position in the sequence and a byte
+/// offset in the `Rope` takes logarithmic time.
+///
+/// Local edits return an [`Operation`] to be sent to the other replicas,
+/// which can apply them in any order as long as every operation is applied
+/// after the ones it depends on, i.e. the operations that inserted the
+/// characters it refers to. Replicas that applied the same set of operations
+/// always end up with the same text.
+///
+/// Every character is also given a label that grows along the sequence and
+/// that doesn't change when other characters are inserted around it. The
+/// replica maps the id of every character to its label, and the internal
+/// nodes of the B-tree track the labels too, so looking up the characters an
+/// operation refers to also takes logarithmic time. Once in a while an
+/// insertion runs out of labels between its neighbours, in which case the
+/// labels of the characters around it are spread out again.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::crdt::Replica;
+/// #
+/// let mut alice = Replica::new(1);
+/// let mut bob = Replica::new(2);
+///
+/// let hello = alice.insert(0, "Hello").unwrap();
+/// bob.apply(&hello).unwrap();
+///
+/// // Alice and Bob edit the text concurrently.
+/// let world = alice.insert(5, " world").unwrap();
+/// let exclamation = bob.insert(5, "!").unwrap();
+/// let lowercase = bob.delete(0..1).unwrap();
+///
+/// alice.apply(&exclamation).unwrap();
+/// alice.apply(&lowercase).unwrap();
+/// bob.apply(&world).unwrap();
+///
+/// assert_eq!(alice.rope(), bob.rope());
+/// assert_eq!(alice.rope(), "ello! world");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Replica {
+    rope: Rope,
+    items: Tree<{ Rope::arity() }, ItemChunk>,
+    labels: BTreeMap<ItemId, u64>,
+    replica_id: u64,
+    clock: u64,
+}
+
+impl Replica {
+    /// Applies an operation created by another replica, editing the
+    /// [`Rope`] in place.
+    ///
+    /// Operations that were already applied are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CrdtError::UnknownItem`] if the operation refers to a
+    /// character this replica hasn't seen yet, in which case the replica is
+    /// left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::crdt::{CrdtError, Replica};
+    /// #
+    /// let mut alice = Replica::new(1);
+    /// let mut bob = Replica::new(2);
+    ///
+    /// let foo = alice.insert(0, "foo").unwrap();
+    /// let bar = alice.insert(3, "bar").unwrap();
+    ///
+    /// // "bar" was inserted after "foo", which Bob hasn't seen yet.
+    /// assert!(matches!(bob.apply(&bar), Err(CrdtError::UnknownItem { .. })));
+    ///
+    /// bob.apply(&foo).unwrap();
+    /// bob.apply(&bar).unwrap();
+    /// bob.apply(&bar).unwrap();
+    ///
+    /// assert_eq!(bob.rope(), "foobar");
+    /// ```
+    #[inline]
+    pub fn apply(&mut self, operation: &Operation) -> Result<(), CrdtError> {
+        match operation {
+            Operation::Insert { id, origin, text } => {
+                self.apply_insert(*id, *origin, text)
+            },
+            Operation::Delete { ids } => self.apply_delete(ids),
+        }
+    }
+
+    #[inline]
+    fn apply_delete(&mut self, ids: &[ItemId]) -> Result<(), CrdtError> {
+        let mut positions = ids
+            .iter()
+            .map(|&id| {
+                self.position_of_id(id).ok_or(CrdtError::UnknownItem { id })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        positions.sort_unstable();
+        positions.dedup();
+
+        // Deleted characters are kept as tombstones, so the positions don't
+        // shift and every run of contiguous positions can be deleted from the
+        // `Rope` with a single edit.
+        let mut end = positions.len();
+
+        while end > 0 {
+            let mut start = end - 1;
+
+            while start > 0 && positions[start - 1] + 1 == positions[start] {
+                start -= 1;
+            }
+
+            let (_, byte_range) =
+                self.tombstone(positions[start]..positions[end - 1] + 1);
+
+            self.rope.delete(byte_range);
+
+            end = start;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn apply_insert(
+        &mut self,
+        id: ItemId,
+        origin: Option<ItemId>,
+        text: &str,
+    ) -> Result<(), CrdtError> {
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        if self.labels.contains_key(&id) {
+            return Ok(());
+        }
+
+        let mut position = match origin {
+            Some(id) => {
+                self.position_of_id(id).ok_or(CrdtError::UnknownItem { id })?
+                    + 1
+            },
+            None => 0,
+        };
+
+        // Concurrent insertions after the same origin are ordered by
+        // decreasing id, and every character inserted after one of them has
+        // a greater id than it, so we can skip all the characters with a
+        // greater id than ours.
+        position += self
+            .items
+            .slice(ItemMetric(position)..ItemMetric(self.item_len()))
+            .leaves()
+            .flat_map(<[Item]>::iter)
+            .take_while(|item| item.id > id)
+            .count();
+
+        let byte_offset = self.byte_of_position(position);
+
+        self.rope.insert(byte_offset, text);
+
+        let len = self.integrate(position, id, text);
+
+        self.clock = self.clock.max(id.clock + len as u64 - 1);
+
+        Ok(())
+    }
+
+    #[doc(hidden)]
+    pub fn assert_invariants(&self) {
+        self.rope.assert_invariants();
+        self.items.assert_invariants();
+
+        let leaves = self.items.leaves();
+
+        if leaves.len() > 1 {
+            for items in leaves {
+                assert!(items.len() >= ItemChunk::min_len());
+            }
+        }
+
+        assert_eq!(self.labels.len(), self.item_len());
+
+        let mut label = 0;
+
+        for item in self.items() {
+            assert!(item.label_increment > 0);
+            label += item.label_increment;
+            assert_eq!(self.labels.get(&item.id), Some(&label));
+        }
+
+        let visible = self
+            .items()
+            .filter(|item| !item.deleted)
+            .map(|item| item.ch)
+            .collect::<String>();
+
+        assert_eq!(self.rope, visible);
+    }
+
+    /// Returns the byte offset in the [`Rope`] corresponding to the given
+    /// position in the sequence of characters, including the deleted ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is out of bounds (i.e. greater than
+    /// [`item_len()`](Self::item_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::crdt::Replica;
+    /// #
+    /// let mut replica = Replica::new(0);
+    ///
+    /// replica.insert(0, "aéb");
+    /// replica.delete(0..1);
+    ///
+    /// assert_eq!(replica.byte_of_position(1), 0);
+    /// assert_eq!(replica.byte_of_position(2), 2);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_position(&self, position: usize) -> usize {
+        if position > self.item_len() {
+            panic::position_out_of_bounds(position, self.item_len());
+        }
+
+        self.items
+            .convert_measure::<_, VisibleByteMetric>(ItemMetric(position))
+            .0
+    }
+
+    /// Deletes the text in the given byte range, returning the operation to
+    /// send to the other replicas, or `None` if the range was empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than the length of the [`Rope`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::crdt::Replica;
+    /// #
+    /// let mut replica = Replica::new(0);
+    ///
+    /// replica.insert(0, "foo bar");
+    ///
+    /// assert!(replica.delete(3..7).is_some());
+    /// assert!(replica.delete(3..3).is_none());
+    /// assert_eq!(replica.rope(), "foo");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn delete<R>(&mut self, byte_range: R) -> Option<Operation>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.rope.byte_len());
+
+        self.rope.delete(start..end);
+
+        let (ids, _) = self.tombstone(
+            self.position_after_byte(start)..self.position_after_byte(end),
+        );
+
+        (!ids.is_empty()).then_some(Operation::Delete { ids })
+    }
+
+    /// Inserts the items of the text inserted with the given id at the given
+    /// position in the sequence, returning how many there are.
+    #[inline]
+    fn integrate(&mut self, position: usize, id: ItemId, text: &str) -> usize {
+        let mut items = items_of(id, text);
+
+        let len = items.len();
+
+        let mut replaced = position..position;
+
+        let increment = if position == self.item_len() {
+            // Nothing comes after the new characters, so they can take as
+            // much room as they want.
+            LABEL_SPACING
+        } else {
+            // The new characters take their labels from the room left before
+            // the next character, so that its label and the ones of all the
+            // characters after it don't change.
+            if self.item_at(position).label_increment <= len as u64 {
+                self.relabel(position, len);
+            }
+
+            let mut next = self.item_at(position);
+
+            let increment =
+                (next.label_increment / (len as u64 + 1)).min(LABEL_SPACING);
+
+            next.label_increment -= increment * len as u64;
+
+            items.push(next);
+
+            replaced.end += 1;
+
+            increment
+        };
+
+        let mut label = self.label_of_position(position);
+
+        for item in &mut items[..len] {
+            item.label_increment = increment;
+            label += increment;
+            self.labels.insert(item.id, label);
+        }
+
+        self.items.replace(
+            ItemMetric(replaced.start)..ItemMetric(replaced.end),
+            &items,
+        );
+
+        len
+    }
+
+    /// Inserts the text at the given byte offset, returning the operation to
+    /// send to the other replicas, or `None` if the text was empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than the
+    /// length of the [`Rope`]) or if it doesn't lie on a code point boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::crdt::{Operation, Replica};
+    /// #
+    /// let mut replica = Replica::new(0);
+    ///
+    /// let Some(Operation::Insert { id, origin, .. }) = replica.insert(0, "ab")
+    /// else {
+    ///     unreachable!();
+    /// };
+    ///
+    /// assert_eq!(origin, None);
+    ///
+    /// let Some(Operation::Insert { origin, .. }) = replica.insert(1, "c") else {
+    ///     unreachable!();
+    /// };
+    ///
+    /// assert_eq!(origin, Some(id));
+    /// assert_eq!(replica.rope(), "acb");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn insert<T>(
+        &mut self,
+        byte_offset: usize,
+        text: T,
+    ) -> Option<Operation>
+    where
+        T: AsRef<str>,
+    {
+        let text = text.as_ref();
+
+        self.rope.insert(byte_offset, text);
+
+        if text.is_empty() {
+            return None;
+        }
+
+        let position = self.position_after_byte(byte_offset);
+
+        let origin = position.checked_sub(1).map(|pos| self.item_at(pos).id);
+
+        let id = ItemId { clock: self.clock + 1, replica: self.replica_id };
+
+        self.clock += self.integrate(position, id, text) as u64;
+
+        Some(Operation::Insert { id, origin, text: text.into() })
+    }
+
+    /// Returns the number of characters in the sequence, including the
+    /// deleted ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::crdt::Replica;
+    /// #
+    /// let mut replica = Replica::new(0);
+    ///
+    /// replica.insert(0, "foo");
+    /// replica.delete(..);
+    ///
+    /// assert_eq!(replica.item_len(), 3);
+    /// ```
+    #[inline]
+    pub fn item_len(&self) -> usize {
+        self.items.measure::<ItemMetric>().0
+    }
+
+    /// Returns the character at the given position.
+    #[inline]
+    fn item_at(&self, position: usize) -> Item {
+        debug_assert!(position < self.item_len());
+
+        let (items, ItemMetric(offset)) =
+            self.items.leaf_at_measure(ItemMetric(position + 1));
+
+        items[position - offset]
+    }
+
+    /// Returns an iterator over all the characters in the sequence.
+    #[inline]
+    fn items(&self) -> impl Iterator<Item = &Item> + '_ {
+        self.items.leaves().flat_map(<[Item]>::iter)
+    }
+
+    /// Returns the label of the character right before the given position, or
+    /// 0 if the position is 0.
+    #[inline]
+    fn label_of_position(&self, position: usize) -> u64 {
+        self.items.convert_measure::<_, LabelMetric>(ItemMetric(position)).0
+    }
+
+    /// Creates a new, empty replica with the given id.
+    ///
+    /// Every replica editing the same text must have a different id.
+    #[inline]
+    pub fn new(replica_id: u64) -> Self {
+        Self {
+            rope: Rope::new(),
+            items: Tree::default(),
+            labels: BTreeMap::new(),
+            replica_id,
+            clock: 0,
+        }
+    }
+
+    /// Returns the position in the sequence of characters right after the
+    /// visible character ending at the given byte offset.
+    #[inline]
+    fn position_after_byte(&self, byte_offset: usize) -> usize {
+        self.items
+            .convert_measure::<_, ItemMetric>(VisibleByteMetric(byte_offset))
+            .0
+    }
+
+    /// Returns the position in the sequence of characters right after the
+    /// visible character ending at the given byte offset in the [`Rope`], or
+    /// 0 if the byte offset is 0.
+    ///
+    /// Text inserted at the byte offset is placed at this position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than the
+    /// length of the [`Rope`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::crdt::Replica;
+    /// #
+    /// let mut replica = Replica::new(0);
+    ///
+    /// replica.insert(0, "abc");
+    /// replica.delete(1..2);
+    ///
+    /// // The deleted "b" is still at position 1.
+    /// assert_eq!(replica.position_of_byte(1), 1);
+    /// assert_eq!(replica.position_of_byte(2), 3);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn position_of_byte(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.rope.byte_len() {
+            panic::byte_offset_out_of_bounds(
+                byte_offset,
+                self.rope.byte_len(),
+            );
+        }
+
+        self.position_after_byte(byte_offset)
+    }
+
+    /// Returns the position of the character with the given id, or `None` if
+    /// this replica hasn't seen it yet.
+    #[inline]
+    fn position_of_id(&self, id: ItemId) -> Option<usize> {
+        let &label = self.labels.get(&id)?;
+
+        let ItemMetric(up_to) =
+            self.items.convert_measure::<_, ItemMetric>(LabelMetric(label));
+
+        Some(up_to - 1)
+    }
+
+    /// Spreads out the labels of the characters around the given position
+    /// until the character at the position has room for `len` characters
+    /// before it.
+    ///
+    /// The window of characters to relabel keeps doubling until they have
+    /// room for twice as many labels as they need, so that the same window
+    /// doesn't have to be relabeled again right away.
+    #[inline]
+    fn relabel(&mut self, position: usize, len: usize) {
+        debug_assert!(position < self.item_len());
+
+        let mut radius = len.max(1);
+
+        loop {
+            let start = position.saturating_sub(radius);
+
+            let end = (position + radius).clamp(position + 1, self.item_len());
+
+            let start_label = self.label_of_position(start);
+
+            let mut room = self.label_of_position(end) - start_label;
+
+            let needed = (end - start + len) as u64;
+
+            // The characters at the end of the sequence can always be given
+            // more room.
+            if end == self.item_len() {
+                room = room.max(needed * LABEL_SPACING);
+            }
+
+            if room < 2 * needed {
+                radius *= 2;
+                continue;
+            }
+
+            let increment = room / needed;
+
+            let mut label = start_label;
+
+            let mut items = self
+                .items
+                .slice(ItemMetric(start)..ItemMetric(end))
+                .leaves()
+                .flat_map(<[Item]>::iter)
+                .copied()
+                .collect::<Vec<_>>();
+
+            let last = items.len() - 1;
+
+            for (idx, item) in items.iter_mut().enumerate() {
+                item.label_increment = increment;
+
+                if start + idx == position {
+                    item.label_increment += increment * len as u64;
+                }
+
+                if idx == last {
+                    item.label_increment += room - increment * needed;
+                }
+
+                label += item.label_increment;
+
+                self.labels.insert(item.id, label);
+            }
+
+            self.items.replace(ItemMetric(start)..ItemMetric(end), &items);
+
+            return;
+        }
+    }
+
+    /// Returns the id of this replica.
+    #[inline]
+    pub fn replica_id(&self) -> u64 {
+        self.replica_id
+    }
+
+    /// Returns the visible text of this replica.
+    #[inline]
+    pub fn rope(&self) -> &Rope {
+        &self.rope
+    }
+
+    /// Marks the characters in the given range of positions as deleted,
+    /// returning the ids of the ones that weren't already deleted and the
+    /// byte range they covered in the [`Rope`].
+    #[inline]
+    fn tombstone(
+        &mut self,
+        range: Range<usize>,
+    ) -> (Vec<ItemId>, Range<usize>) {
+        let byte_start = self.byte_of_position(range.start);
+
+        let mut byte_end = byte_start;
+
+        let mut ids = Vec::new();
+
+        let tombstones = self
+            .items
+            .slice(ItemMetric(range.start)..ItemMetric(range.end))
+            .leaves()
+            .flat_map(<[Item]>::iter)
+            .map(|item| {
+                if !item.deleted {
+                    byte_end += item.ch.len_utf8();
+                    ids.push(item.id);
+                }
+                Item { deleted: true, ..*item }
+            })
+            .collect::<Vec<_>>();
+
+        if !ids.is_empty() {
+            self.items.replace(
+                ItemMetric(range.start)..ItemMetric(range.end),
+                &tombstones,
+            );
+        }
+
+        (ids, byte_start..byte_end)
+    }
+}
+
+/// A character in the sequence of a [`Replica`].
+#[derive(Debug, Clone, Copy)]
+struct Item {
+    id: ItemId,
+    ch: char,
+    deleted: bool,
+
+    /// The difference between the label of this character and the one of the
+    /// character before it, which is always positive.
+    label_increment: u64,
+}
+
+impl Item {
+    /// Returns the number of bytes this item takes up in the [`Rope`].
+    #[inline]
+    fn visible_bytes(&self) -> usize {
+        if self.deleted {
+            0
+        } else {
+            self.ch.len_utf8()
+        }
+    }
+}
+
+/// Returns the items of the given text inserted with the given id.
+#[inline]
+fn items_of(id: ItemId, text: &str) -> Vec<Item> {
+    text.chars()
+        .zip(id.clock..)
+        .map(|(ch, clock)| Item {
+            id: ItemId { clock, replica: id.replica },
+            ch,
+            deleted: false,
+            label_increment: 0,
+        })
+        .collect()
+}
+
+/// The leaves of the tree storing the sequence of a [`Replica`].
+type ItemChunk = VecChunk<Item, CHUNK_MAX_ITEMS>;
+
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+struct ItemSummary {
+    items: usize,
+    visible_bytes: usize,
+    labels: u64,
+}
+
+impl Add<&Self> for ItemSummary {
+    type Output = Self;
+
+    #[inline]
+    fn add(mut self, other: &Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl Sub<&Self> for ItemSummary {
+    type Output = Self;
+
+    #[inline]
+    fn sub(mut self, other: &Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+impl AddAssign<&Self> for ItemSummary {
+    #[inline]
+    fn add_assign(&mut self, other: &Self) {
+        self.items += other.items;
+        self.visible_bytes += other.visible_bytes;
+        self.labels += other.labels;
+    }
+}
+
+impl SubAssign<&Self> for ItemSummary {
+    #[inline]
+    fn sub_assign(&mut self, other: &Self) {
+        self.items -= other.items;
+        self.visible_bytes -= other.visible_bytes;
+        self.labels -= other.labels;
+    }
+}
+
+impl Summarize for &[Item] {
+    type Summary = ItemSummary;
+
+    #[inline]
+    fn summarize(&self) -> ItemSummary {
+        ItemSummary {
+            items: self.len(),
+            visible_bytes: self.iter().map(Item::visible_bytes).sum(),
+            labels: self.iter().map(|item| item.label_increment).sum(),
+        }
+    }
+}
+
+impl Summarize for ItemChunk {
+    type Summary = ItemSummary;
+
+    #[inline]
+    fn summarize(&self) -> ItemSummary {
+        self.as_slice().summarize()
+    }
+}
+
+impl BaseMeasured for ItemChunk {
+    type BaseMetric = ItemMetric;
+}
+
+impl AsSlice for ItemChunk {
+    type Slice<'a> = &'a [Item];
+
+    #[inline]
+    fn as_slice(&self) -> &[Item] {
+        self.elems()
+    }
+}
+
+/// A metric measuring the number of characters in the sequence, including
+/// the deleted ones.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct ItemMetric(usize);
+
+/// A metric measuring the number of bytes of the characters that haven't
+/// been deleted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct VisibleByteMetric(usize);
+
+/// A metric measuring the sum of the label increments of the characters, so
+/// that the measure up to and including a character is its label.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct LabelMetric(u64);
+
+impl_metric_ops!(ItemMetric);
+impl_metric_ops!(VisibleByteMetric);
+impl_metric_ops!(LabelMetric);
+
+impl Add<usize> for ItemMetric {
+    type Output = usize;
+
+    #[inline]
+    fn add(self, other: usize) -> usize {
+        self.0 + other
+    }
+}
+
+impl From<ItemMetric> for usize {
+    #[inline]
+    fn from(ItemMetric(value): ItemMetric) -> usize {
+        value
+    }
+}
+
+impl Metric<ItemSummary> for ItemMetric {
+    #[inline]
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    #[inline]
+    fn one() -> Self {
+        Self(1)
+    }
+
+    #[inline]
+    fn measure(summary: &ItemSummary) -> Self {
+        Self(summary.items)
+    }
+}
+
+impl SlicingMetric<ItemChunk> for ItemMetric {
+    #[inline]
+    fn slice_up_to<'a>(
+        items: &'a [Item],
+        ItemMetric(up_to): Self,
+        _: &ItemSummary,
+    ) -> (&'a [Item], ItemSummary)
+    where
+        'a: 'a,
+    {
+        let left = &items[..up_to];
+        (left, left.summarize())
+    }
+
+    #[inline]
+    fn slice_from<'a>(
+        items: &'a [Item],
+        ItemMetric(from): Self,
+        _: &ItemSummary,
+    ) -> (&'a [Item], ItemSummary)
+    where
+        'a: 'a,
+    {
+        let right = &items[from..];
+        (right, right.summarize())
+    }
+}
+
+impl Metric<ItemSummary> for VisibleByteMetric {
+    #[inline]
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    #[inline]
+    fn one() -> Self {
+        Self(1)
+    }
+
+    #[inline]
+    fn measure(summary: &ItemSummary) -> Self {
+        Self(summary.visible_bytes)
+    }
+}
+
+impl VisibleByteMetric {
+    /// Returns the number of items up to and including the visible item
+    /// ending at the given byte offset, or 0 if the byte offset is 0.
+    #[inline]
+    fn items_up_to(items: &[Item], byte_offset: usize) -> usize {
+        let mut bytes = 0;
+
+        let mut idx = 0;
+
+        while bytes < byte_offset {
+            bytes += items[idx].visible_bytes();
+            idx += 1;
+        }
+
+        debug_assert_eq!(bytes, byte_offset);
+
+        idx
+    }
+}
+
+impl SlicingMetric<ItemChunk> for VisibleByteMetric {
+    #[inline]
+    fn slice_up_to<'a>(
+        items: &'a [Item],
+        VisibleByteMetric(up_to): Self,
+        _: &ItemSummary,
+    ) -> (&'a [Item], ItemSummary)
+    where
+        'a: 'a,
+    {
+        let left = &items[..Self::items_up_to(items, up_to)];
+        (left, left.summarize())
+    }
+
+    #[inline]
+    fn slice_from<'a>(
+        items: &'a [Item],
+        VisibleByteMetric(from): Self,
+        _: &ItemSummary,
+    ) -> (&'a [Item], ItemSummary)
+    where
+        'a: 'a,
+    {
+        let right = &items[Self::items_up_to(items, from)..];
+        (right, right.summarize())
+    }
+}
+
+impl Metric<ItemSummary> for LabelMetric {
+    #[inline]
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    #[inline]
+    fn one() -> Self {
+        Self(1)
+    }
+
+    #[inline]
+    fn measure(summary: &ItemSummary) -> Self {
+        Self(summary.labels)
+    }
+}
+
+impl LabelMetric {
+    /// Returns the number of items up to and including the item with the
+    /// given label, or 0 if the label is 0.
+    #[inline]
+    fn items_up_to(items: &[Item], label: u64) -> usize {
+        let mut labels = 0;
+
+        let mut idx = 0;
+
+        while labels < label {
+            labels += items[idx].label_increment;
+            idx += 1;
+        }
+
+        debug_assert_eq!(labels, label);
+
+        idx
+    }
+}
+
+impl SlicingMetric<ItemChunk> for LabelMetric {
+    #[inline]
+    fn slice_up_to<'a>(
+        items: &'a [Item],
+        LabelMetric(up_to): Self,
+        _: &ItemSummary,
+    ) -> (&'a [Item], ItemSummary)
+    where
+        'a: 'a,
+    {
+        let left = &items[..Self::items_up_to(items, up_to)];
+        (left, left.summarize())
+    }
+
+    #[inline]
+    fn slice_from<'a>(
+        items: &'a [Item],
+        LabelMetric(from): Self,
+        _: &ItemSummary,
+    ) -> (&'a [Item], ItemSummary)
+    where
+        'a: 'a,
+    {
+        let right = &items[Self::items_up_to(items, from)..];
+        (right, right.summarize())
+    }
+}
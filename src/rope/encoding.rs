@@ -0,0 +1,330 @@
+//! This module contains the types used to load and save `Rope`s in encodings
+//! other than UTF-8, exported in the [`encoding`](crate::encoding) module.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::io::{self, Read, Write};
+
+pub use encoding_rs::{self, Encoding};
+use encoding_rs::{DecoderResult, EncoderResult, UTF_16BE, UTF_16LE, UTF_8};
+
+use super::{Rope, RopeBuilder};
+
+/// The size of the buffers used to read and write encoded text.
+#[cfg(not(any(test, feature = "small_chunks")))]
+const BUFFER_SIZE: usize = 8192;
+
+#[cfg(any(test, feature = "small_chunks"))]
+const BUFFER_SIZE: usize = 16;
+
+/// An [`Encoding`] together with whether the text starts with a byte order
+/// mark.
+///
+/// This is returned by
+/// [`Rope::from_reader_with_encoding()`](crate::Rope::from_reader_with_encoding())
+/// and can be passed back to
+/// [`Rope::write_encoded()`](crate::Rope::write_encoded()) to save a `Rope`
+/// in the same encoding it was loaded from, BOM included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextEncoding {
+    /// The encoding of the text.
+    pub encoding: &'static Encoding,
+
+    /// Whether the text starts with a byte order mark. This is only ever
+    /// `true` for UTF-8, UTF-16LE and UTF-16BE, the only encodings that have
+    /// one.
+    pub has_bom: bool,
+}
+
+impl From<&'static Encoding> for TextEncoding {
+    #[inline]
+    fn from(encoding: &'static Encoding) -> Self {
+        Self { encoding, has_bom: false }
+    }
+}
+
+impl TextEncoding {
+    /// Returns the byte order mark of the encoding if `has_bom` is `true`,
+    /// or an empty slice otherwise.
+    #[inline]
+    fn bom(&self) -> &'static [u8] {
+        match self.encoding {
+            _ if !self.has_bom => &[],
+            enc if enc == UTF_8 => b"\xEF\xBB\xBF",
+            enc if enc == UTF_16LE => b"\xFF\xFE",
+            enc if enc == UTF_16BE => b"\xFE\xFF",
+            _ => &[],
+        }
+    }
+}
+
+/// The error type returned when loading or saving a `Rope` in a given
+/// [`Encoding`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EncodingError {
+    /// Reading from the reader or writing to the writer failed.
+    Io(io::Error),
+
+    /// The input contained a byte sequence that's not valid in the encoding
+    /// it was decoded with.
+    Malformed {
+        /// The encoding the input was decoded with.
+        encoding: &'static Encoding,
+
+        /// The offset of the start of the malformed sequence in the input.
+        byte_offset: usize,
+    },
+
+    /// The `Rope` contained a character that can't be represented in the
+    /// encoding it was being encoded to.
+    Unmappable {
+        /// The encoding the `Rope` was being encoded to.
+        encoding: &'static Encoding,
+
+        /// The offset of the character in the `Rope`.
+        byte_offset: usize,
+
+        /// The character that can't be encoded.
+        ch: char,
+    },
+}
+
+impl core::fmt::Display for EncodingError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Io(err) => err.fmt(f),
+
+            Self::Malformed { encoding, byte_offset } => write!(
+                f,
+                "malformed {} byte sequence at byte offset {byte_offset}",
+                encoding.name()
+            ),
+
+            Self::Unmappable { encoding, byte_offset, ch } => write!(
+                f,
+                "character {ch:?} at byte offset {byte_offset} can't be \
+                 encoded in {}",
+                encoding.name()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EncodingError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for EncodingError {
+    #[inline]
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Decodes the contents of the reader in the given encoding, or in the one
+/// given by its BOM if it starts with one.
+#[inline]
+pub(super) fn decode<R: Read>(
+    mut reader: R,
+    encoding: &'static Encoding,
+) -> Result<(Rope, TextEncoding), EncodingError> {
+    let mut input = alloc::vec![0; BUFFER_SIZE];
+
+    // Make sure we've read enough bytes to sniff the BOM, unless the input is
+    // shorter than that.
+    let mut filled = 0;
+
+    while filled < 3 {
+        match read(&mut reader, &mut input[filled..])? {
+            0 => break,
+            read => filled += read,
+        }
+    }
+
+    let (encoding, bom_len) = match Encoding::for_bom(&input[..filled]) {
+        Some((encoding, bom_len)) => (encoding, bom_len),
+        None => (encoding, 0),
+    };
+
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+
+    let mut builder = RopeBuilder::new();
+
+    let mut output = String::with_capacity(
+        decoder
+            .max_utf8_buffer_length_without_replacement(BUFFER_SIZE)
+            .unwrap_or(BUFFER_SIZE * 3),
+    );
+
+    let mut byte_offset = bom_len;
+
+    let mut start = bom_len;
+
+    loop {
+        let is_last = filled == 0;
+
+        let mut src = &input[start..filled];
+
+        loop {
+            let (result, read) = decoder.decode_to_string_without_replacement(
+                src,
+                &mut output,
+                is_last,
+            );
+
+            byte_offset += read;
+            src = &src[read..];
+
+            match result {
+                DecoderResult::InputEmpty => break,
+
+                DecoderResult::OutputFull => {
+                    builder.append(&output);
+                    output.clear();
+                },
+
+                DecoderResult::Malformed(len, consumed_after) => {
+                    // The malformed sequence could've started in a previous
+                    // buffer.
+                    let byte_offset = byte_offset.saturating_sub(
+                        len as usize + consumed_after as usize,
+                    );
+
+                    return Err(EncodingError::Malformed {
+                        encoding,
+                        byte_offset,
+                    });
+                },
+            }
+        }
+
+        builder.append(&output);
+        output.clear();
+
+        if is_last {
+            break;
+        }
+
+        filled = read(&mut reader, &mut input)?;
+        start = 0;
+    }
+
+    Ok((builder.build(), TextEncoding { encoding, has_bom: bom_len > 0 }))
+}
+
+/// Encodes the contents of the `Rope` in the given encoding, writing them to
+/// the writer.
+#[inline]
+pub(super) fn encode<W: Write>(
+    rope: &Rope,
+    mut writer: W,
+    encoding: TextEncoding,
+) -> Result<(), EncodingError> {
+    writer.write_all(encoding.bom())?;
+
+    match encoding.encoding {
+        enc if enc == UTF_8 => {
+            for chunk in rope.chunks() {
+                writer.write_all(chunk.as_bytes())?;
+            }
+        },
+
+        // `encoding_rs` follows the Encoding Standard, which only allows
+        // decoding UTF-16, so we encode it ourselves.
+        enc if enc == UTF_16LE || enc == UTF_16BE => {
+            let to_bytes = if enc == UTF_16LE {
+                u16::to_le_bytes
+            } else {
+                u16::to_be_bytes
+            };
+
+            let mut output = Vec::with_capacity(BUFFER_SIZE);
+
+            for chunk in rope.chunks() {
+                for code_unit in chunk.encode_utf16() {
+                    if output.len() + 2 > BUFFER_SIZE {
+                        writer.write_all(&output)?;
+                        output.clear();
+                    }
+                    output.extend_from_slice(&to_bytes(code_unit));
+                }
+            }
+
+            writer.write_all(&output)?;
+        },
+
+        enc => {
+            let mut encoder = enc.output_encoding().new_encoder();
+
+            let mut output = [0; BUFFER_SIZE];
+
+            let mut chunk_offset = 0;
+
+            // The encoder has to be told when the input is over to let
+            // stateful encodings like ISO-2022-JP return to their initial
+            // state.
+            let chunks = rope
+                .chunks()
+                .map(|chunk| (chunk, false))
+                .chain(core::iter::once(("", true)));
+
+            for (chunk, is_last) in chunks {
+                let mut src = chunk;
+
+                loop {
+                    let (result, read, written) = encoder
+                        .encode_from_utf8_without_replacement(
+                            src,
+                            &mut output,
+                            is_last,
+                        );
+
+                    writer.write_all(&output[..written])?;
+
+                    src = &src[read..];
+
+                    match result {
+                        EncoderResult::InputEmpty => break,
+
+                        EncoderResult::OutputFull => {},
+
+                        EncoderResult::Unmappable(ch) => {
+                            let byte_offset = chunk_offset
+                                + (chunk.len() - src.len())
+                                - ch.len_utf8();
+
+                            return Err(EncodingError::Unmappable {
+                                encoding: enc,
+                                byte_offset,
+                                ch,
+                            });
+                        },
+                    }
+                }
+
+                chunk_offset += chunk.len();
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// Like [`Read::read()`], but retries on [`io::ErrorKind::Interrupted`].
+#[inline]
+fn read<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    loop {
+        match reader.read(buf) {
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            res => return res,
+        }
+    }
+}
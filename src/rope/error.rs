@@ -0,0 +1,374 @@
+use core::ops::{Range, RangeBounds};
+
+use crate::range_bounds_to_start_end;
+
+/// The error type returned by the `try_*` methods of [`Rope`](crate::Rope)
+/// and [`RopeSlice`](crate::RopeSlice), i.e. the non-panicking versions of
+/// the methods taking byte offsets, line offsets or ranges.
+///
+/// Each variant carries the same information that would've been included in
+/// the panic message of the corresponding panicking method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RopeError {
+    /// A byte index was greater than or equal to the byte length.
+    ByteIndexOutOfBounds {
+        /// The byte index that was out of bounds.
+        byte_index: usize,
+
+        /// The byte length of the `Rope` or `RopeSlice`.
+        byte_len: usize,
+    },
+
+    /// A byte offset didn't lie on a char boundary.
+    ByteOffsetNotCharBoundary {
+        /// The byte offset that wasn't on a char boundary.
+        byte_offset: usize,
+
+        /// The character containing the byte offset.
+        splitting_char: char,
+
+        /// The byte range of `splitting_char`.
+        char_range: Range<usize>,
+    },
+
+    /// A byte offset was greater than the byte length.
+    ByteOffsetOutOfBounds {
+        /// The byte offset that was out of bounds.
+        byte_offset: usize,
+
+        /// The byte length of the `Rope` or `RopeSlice`.
+        byte_len: usize,
+    },
+
+    /// The start of a byte range was greater than its end.
+    ByteStartAfterEnd {
+        /// The start of the byte range.
+        byte_start: usize,
+
+        /// The end of the byte range.
+        byte_end: usize,
+    },
+
+    /// A line index was greater than or equal to the number of lines.
+    LineIndexOutOfBounds {
+        /// The line index that was out of bounds.
+        line_index: usize,
+
+        /// The number of lines in the `Rope` or `RopeSlice`.
+        line_len: usize,
+    },
+
+    /// A line offset was greater than the number of lines.
+    LineOffsetOutOfBounds {
+        /// The line offset that was out of bounds.
+        line_offset: usize,
+
+        /// The number of lines in the `Rope` or `RopeSlice`.
+        line_len: usize,
+    },
+
+    /// The start of a line range was greater than its end.
+    LineStartAfterEnd {
+        /// The start of the line range.
+        line_start: usize,
+
+        /// The end of the line range.
+        line_end: usize,
+    },
+
+    /// A UTF-16 code unit offset was greater than the UTF-16 length.
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    Utf16OffsetOutOfBounds {
+        /// The UTF-16 code unit offset that was out of bounds.
+        utf16_offset: usize,
+
+        /// The UTF-16 length of the `Rope` or `RopeSlice`.
+        utf16_len: usize,
+    },
+
+    /// The start of a UTF-16 code unit range was greater than its end.
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    Utf16StartAfterEnd {
+        /// The start of the UTF-16 code unit range.
+        utf16_start: usize,
+
+        /// The end of the UTF-16 code unit range.
+        utf16_end: usize,
+    },
+}
+
+impl core::fmt::Display for RopeError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::ByteIndexOutOfBounds { byte_index, byte_len } => write!(
+                f,
+                "byte index out of bounds: the index is {byte_index} but the \
+                 length is {byte_len}"
+            ),
+
+            Self::ByteOffsetNotCharBoundary {
+                byte_offset,
+                splitting_char,
+                char_range,
+            } => write!(
+                f,
+                "byte offset {byte_offset} is not a char boundary: it is \
+                 inside {splitting_char:?} (bytes {char_range:?})"
+            ),
+
+            Self::ByteOffsetOutOfBounds { byte_offset, byte_len } => write!(
+                f,
+                "byte offset out of bounds: the offset is {byte_offset} but \
+                 the length is {byte_len}"
+            ),
+
+            Self::ByteStartAfterEnd { byte_start, byte_end } => write!(
+                f,
+                "byte start after end: the start is {byte_start} but the end \
+                 is {byte_end}"
+            ),
+
+            Self::LineIndexOutOfBounds { line_index, line_len } => write!(
+                f,
+                "line index out of bounds: the index is {line_index} but the \
+                 length is {line_len}"
+            ),
+
+            Self::LineOffsetOutOfBounds { line_offset, line_len } => write!(
+                f,
+                "line offset out of bounds: the offset is {line_offset} but \
+                 the length is {line_len}"
+            ),
+
+            Self::LineStartAfterEnd { line_start, line_end } => write!(
+                f,
+                "line start after end: the start is {line_start} but the end \
+                 is {line_end}"
+            ),
+
+            #[cfg(feature = "utf16-metric")]
+            Self::Utf16OffsetOutOfBounds { utf16_offset, utf16_len } => {
+                write!(
+                    f,
+                    "UTF-16 offset out of bounds: the offset is \
+                     {utf16_offset} but the length is {utf16_len}"
+                )
+            },
+
+            #[cfg(feature = "utf16-metric")]
+            Self::Utf16StartAfterEnd { utf16_start, utf16_end } => write!(
+                f,
+                "UTF-16 offset start after end: the start is {utf16_start} \
+                 but the end is {utf16_end}"
+            ),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+impl std::error::Error for RopeError {}
+
+/// Checks that the byte index is in bounds.
+#[inline]
+pub(super) fn check_byte_index(
+    byte_index: usize,
+    byte_len: usize,
+) -> Result<(), RopeError> {
+    if byte_index >= byte_len {
+        Err(RopeError::ByteIndexOutOfBounds { byte_index, byte_len })
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that the byte offset is in bounds.
+#[inline]
+pub(super) fn check_byte_offset(
+    byte_offset: usize,
+    byte_len: usize,
+) -> Result<(), RopeError> {
+    if byte_offset > byte_len {
+        Err(RopeError::ByteOffsetOutOfBounds { byte_offset, byte_len })
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that the byte offset is in bounds and that it lies on a char
+/// boundary of the text whose bytes are returned by `byte_at`.
+#[inline]
+pub(super) fn check_char_boundary<B>(
+    byte_offset: usize,
+    byte_len: usize,
+    byte_at: B,
+) -> Result<(), RopeError>
+where
+    B: Fn(usize) -> u8,
+{
+    check_byte_offset(byte_offset, byte_len)?;
+
+    let is_char_boundary = |offset: usize| {
+        offset == byte_len || !is_continuation(byte_at(offset))
+    };
+
+    if is_char_boundary(byte_offset) {
+        return Ok(());
+    }
+
+    let mut start = byte_offset;
+    while !is_char_boundary(start) {
+        start -= 1;
+    }
+
+    let mut end = byte_offset;
+    while !is_char_boundary(end) {
+        end += 1;
+    }
+
+    let mut buf = [0; 4];
+
+    for (idx, byte) in (start..end).map(&byte_at).enumerate() {
+        buf[idx] = byte;
+    }
+
+    let splitting_char = core::str::from_utf8(&buf[..end - start])
+        .expect("the bytes are between two char boundaries")
+        .chars()
+        .next()
+        .expect("the range is not empty");
+
+    Err(RopeError::ByteOffsetNotCharBoundary {
+        byte_offset,
+        splitting_char,
+        char_range: start..end,
+    })
+}
+
+/// Converts the byte range into a `(start, end)` pair, checking that the
+/// start is not after the end and that both lie on char boundaries.
+#[inline]
+pub(super) fn check_byte_range<R, B>(
+    byte_range: R,
+    byte_len: usize,
+    byte_at: B,
+) -> Result<(usize, usize), RopeError>
+where
+    R: RangeBounds<usize>,
+    B: Fn(usize) -> u8,
+{
+    let (start, end) = range_bounds_to_start_end(byte_range, 0, byte_len);
+
+    if start > end {
+        return Err(RopeError::ByteStartAfterEnd {
+            byte_start: start,
+            byte_end: end,
+        });
+    }
+
+    check_byte_offset(end, byte_len)?;
+    check_char_boundary(start, byte_len, &byte_at)?;
+    check_char_boundary(end, byte_len, &byte_at)?;
+
+    Ok((start, end))
+}
+
+/// Checks that the line index is in bounds.
+#[inline]
+pub(super) fn check_line_index(
+    line_index: usize,
+    line_len: usize,
+) -> Result<(), RopeError> {
+    if line_index >= line_len {
+        Err(RopeError::LineIndexOutOfBounds { line_index, line_len })
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that the line offset is in bounds.
+#[inline]
+pub(super) fn check_line_offset(
+    line_offset: usize,
+    line_len: usize,
+) -> Result<(), RopeError> {
+    if line_offset > line_len {
+        Err(RopeError::LineOffsetOutOfBounds { line_offset, line_len })
+    } else {
+        Ok(())
+    }
+}
+
+/// Converts the line range into a `(start, end)` pair, checking that the
+/// start is not after the end and that the end is in bounds.
+#[inline]
+pub(super) fn check_line_range<R>(
+    line_range: R,
+    line_len: usize,
+) -> Result<(usize, usize), RopeError>
+where
+    R: RangeBounds<usize>,
+{
+    let (start, end) = range_bounds_to_start_end(line_range, 0, line_len);
+
+    if start > end {
+        return Err(RopeError::LineStartAfterEnd {
+            line_start: start,
+            line_end: end,
+        });
+    }
+
+    check_line_offset(end, line_len)?;
+
+    Ok((start, end))
+}
+
+/// Checks that the UTF-16 code unit offset is in bounds.
+#[cfg(feature = "utf16-metric")]
+#[inline]
+pub(super) fn check_utf16_offset(
+    utf16_offset: usize,
+    utf16_len: usize,
+) -> Result<(), RopeError> {
+    if utf16_offset > utf16_len {
+        Err(RopeError::Utf16OffsetOutOfBounds { utf16_offset, utf16_len })
+    } else {
+        Ok(())
+    }
+}
+
+/// Converts the UTF-16 code unit range into a `(start, end)` pair, checking
+/// that the start is not after the end and that the end is in bounds.
+#[cfg(feature = "utf16-metric")]
+#[inline]
+pub(super) fn check_utf16_range<R>(
+    utf16_range: R,
+    utf16_len: usize,
+) -> Result<(usize, usize), RopeError>
+where
+    R: RangeBounds<usize>,
+{
+    let (start, end) = range_bounds_to_start_end(utf16_range, 0, utf16_len);
+
+    if start > end {
+        return Err(RopeError::Utf16StartAfterEnd {
+            utf16_start: start,
+            utf16_end: end,
+        });
+    }
+
+    check_utf16_offset(end, utf16_len)?;
+
+    Ok((start, end))
+}
+
+/// Returns whether the byte is a UTF-8 continuation byte, i.e. one that's not
+/// the first byte of a code point.
+#[inline]
+fn is_continuation(byte: u8) -> bool {
+    (byte as i8) < -0x40
+}
@@ -0,0 +1,778 @@
+//! This module contains the non-panicking versions of the `Rope`,
+//! `RopeSlice` and `RopeSpan` methods taking offsets, indices or ranges that
+//! could be out of bounds or not lie on a char boundary.
+
+use core::ops::RangeBounds;
+
+use super::error::*;
+use super::{Rope, RopeSlice, RopeSpan};
+
+impl Rope {
+    /// Non-panicking version of [`byte()`](Self::byte()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteIndexOutOfBounds`] if the byte index is out of
+    /// bounds (i.e. greater than or equal to
+    /// [`byte_len()`](Self::byte_len())).
+    #[inline]
+    pub fn try_byte(&self, byte_index: usize) -> Result<u8, RopeError> {
+        check_byte_index(byte_index, self.byte_len())?;
+        Ok(self.byte(byte_index))
+    }
+
+    /// Non-panicking version of [`byte_of_line()`](Self::byte_of_line()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::LineOffsetOutOfBounds`] if the line offset is out
+    /// of bounds (i.e. greater than [`line_len()`](Self::line_len())).
+    #[inline]
+    pub fn try_byte_of_line(
+        &self,
+        line_offset: usize,
+    ) -> Result<usize, RopeError> {
+        check_line_offset(line_offset, self.line_len())?;
+        Ok(self.byte_of_line(line_offset))
+    }
+
+    /// Non-panicking version of
+    /// [`byte_of_utf16_code_unit()`](Self::byte_of_utf16_code_unit()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::Utf16OffsetOutOfBounds`] if the UTF-16 code unit
+    /// offset is out of bounds (i.e. greater than
+    /// [`utf16_len()`](Self::utf16_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[inline]
+    pub fn try_byte_of_utf16_code_unit(
+        &self,
+        utf16_offset: usize,
+    ) -> Result<usize, RopeError> {
+        check_utf16_offset(utf16_offset, self.utf16_len())?;
+        Ok(self.byte_of_utf16_code_unit(utf16_offset))
+    }
+
+    /// Non-panicking version of [`byte_slice()`](Self::byte_slice()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteStartAfterEnd`] if the start of the range is
+    /// greater than the end, [`RopeError::ByteOffsetOutOfBounds`] if the end
+    /// is out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())),
+    /// or [`RopeError::ByteOffsetNotCharBoundary`] if the start or the end
+    /// don't lie on a code point boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeError};
+    /// #
+    /// let r = Rope::from("🗻∈🌏");
+    ///
+    /// assert_eq!(r.try_byte_slice(4..7).unwrap(), "∈");
+    ///
+    /// assert_eq!(
+    ///     r.try_byte_slice(5..),
+    ///     Err(RopeError::ByteOffsetNotCharBoundary {
+    ///         byte_offset: 5,
+    ///         splitting_char: '∈',
+    ///         char_range: 4..7,
+    ///     })
+    /// );
+    ///
+    /// assert_eq!(
+    ///     r.try_byte_slice(..20),
+    ///     Err(RopeError::ByteOffsetOutOfBounds {
+    ///         byte_offset: 20,
+    ///         byte_len: 11
+    ///     })
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_byte_slice<R>(
+        &self,
+        byte_range: R,
+    ) -> Result<RopeSlice<'_>, RopeError>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            check_byte_range(byte_range, self.byte_len(), |i| self.byte(i))?;
+        Ok(self.byte_slice(start..end))
+    }
+
+    /// Non-panicking version of [`delete()`](Self::delete()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteStartAfterEnd`] if the start of the range is
+    /// greater than the end, [`RopeError::ByteOffsetOutOfBounds`] if the end
+    /// is out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())),
+    /// or [`RopeError::ByteOffsetNotCharBoundary`] if the start or the end
+    /// don't lie on a code point boundary.
+    #[inline]
+    pub fn try_delete<R>(&mut self, byte_range: R) -> Result<(), RopeError>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.try_replace(byte_range, "")
+    }
+
+    /// Non-panicking version of [`insert()`](Self::insert()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteOffsetOutOfBounds`] if the byte offset is out
+    /// of bounds (i.e. greater than [`byte_len()`](Self::byte_len())), or
+    /// [`RopeError::ByteOffsetNotCharBoundary`] if it doesn't lie on a code
+    /// point boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeError};
+    /// #
+    /// let mut r = Rope::from("Hello Earth!");
+    ///
+    /// assert!(r.try_insert(11, " 🌎").is_ok());
+    /// assert_eq!(r, "Hello Earth 🌎!");
+    ///
+    /// assert_eq!(
+    ///     r.try_insert(100, "!"),
+    ///     Err(RopeError::ByteOffsetOutOfBounds {
+    ///         byte_offset: 100,
+    ///         byte_len: 17
+    ///     })
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_insert<T>(
+        &mut self,
+        byte_offset: usize,
+        text: T,
+    ) -> Result<(), RopeError>
+    where
+        T: AsRef<str>,
+    {
+        self.try_replace(byte_offset..byte_offset, text)
+    }
+
+    /// Non-panicking version of [`insert_rope()`](Self::insert_rope()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteOffsetOutOfBounds`] if the byte offset is out
+    /// of bounds (i.e. greater than [`byte_len()`](Self::byte_len())), or
+    /// [`RopeError::ByteOffsetNotCharBoundary`] if it doesn't lie on a code
+    /// point boundary.
+    #[inline]
+    pub fn try_insert_rope(
+        &mut self,
+        byte_offset: usize,
+        slice: RopeSlice<'_>,
+    ) -> Result<(), RopeError> {
+        self.try_replace_with_rope(byte_offset..byte_offset, slice)
+    }
+
+    /// Non-panicking version of
+    /// [`is_char_boundary()`](Self::is_char_boundary()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteOffsetOutOfBounds`] if the byte offset is out
+    /// of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    #[inline]
+    pub fn try_is_char_boundary(
+        &self,
+        byte_offset: usize,
+    ) -> Result<bool, RopeError> {
+        check_byte_offset(byte_offset, self.byte_len())?;
+        Ok(self.is_char_boundary(byte_offset))
+    }
+
+    /// Non-panicking version of
+    /// [`is_grapheme_boundary()`](Self::is_grapheme_boundary()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteOffsetOutOfBounds`] if the byte offset is out
+    /// of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn try_is_grapheme_boundary(
+        &self,
+        byte_offset: usize,
+    ) -> Result<bool, RopeError> {
+        check_byte_offset(byte_offset, self.byte_len())?;
+        Ok(self.is_grapheme_boundary(byte_offset))
+    }
+
+    /// Non-panicking version of [`line()`](Self::line()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::LineIndexOutOfBounds`] if the line index is out of
+    /// bounds (i.e. greater than or equal to
+    /// [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeError};
+    /// #
+    /// let r = Rope::from("foo\nbar");
+    ///
+    /// assert_eq!(r.try_line(1).unwrap(), "bar");
+    ///
+    /// assert_eq!(
+    ///     r.try_line(2),
+    ///     Err(RopeError::LineIndexOutOfBounds { line_index: 2, line_len: 2 })
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_line(
+        &self,
+        line_index: usize,
+    ) -> Result<RopeSlice<'_>, RopeError> {
+        check_line_index(line_index, self.line_len())?;
+        Ok(self.line(line_index))
+    }
+
+    /// Non-panicking version of [`line_of_byte()`](Self::line_of_byte()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteOffsetOutOfBounds`] if the byte offset is out
+    /// of bounds (i.e. greater than [`byte_len()`](Self::byte_len())), or
+    /// [`RopeError::ByteOffsetNotCharBoundary`] if it doesn't lie on a code
+    /// point boundary.
+    #[inline]
+    pub fn try_line_of_byte(
+        &self,
+        byte_offset: usize,
+    ) -> Result<usize, RopeError> {
+        check_char_boundary(byte_offset, self.byte_len(), |i| self.byte(i))?;
+        Ok(self.line_of_byte(byte_offset))
+    }
+
+    /// Non-panicking version of [`line_slice()`](Self::line_slice()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::LineStartAfterEnd`] if the start of the range is
+    /// greater than the end, or [`RopeError::LineOffsetOutOfBounds`] if the
+    /// end is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    #[inline]
+    pub fn try_line_slice<R>(
+        &self,
+        line_range: R,
+    ) -> Result<RopeSlice<'_>, RopeError>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = check_line_range(line_range, self.line_len())?;
+        Ok(self.line_slice(start..end))
+    }
+
+    /// Non-panicking version of [`replace()`](Self::replace()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteStartAfterEnd`] if the start of the range is
+    /// greater than the end, [`RopeError::ByteOffsetOutOfBounds`] if the end
+    /// is out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())),
+    /// or [`RopeError::ByteOffsetNotCharBoundary`] if the start or the end
+    /// don't lie on a code point boundary.
+    #[inline]
+    pub fn try_replace<R, T>(
+        &mut self,
+        byte_range: R,
+        text: T,
+    ) -> Result<(), RopeError>
+    where
+        R: RangeBounds<usize>,
+        T: AsRef<str>,
+    {
+        let (start, end) =
+            check_byte_range(byte_range, self.byte_len(), |i| self.byte(i))?;
+        self.replace(start..end, text);
+        Ok(())
+    }
+
+    /// Non-panicking version of
+    /// [`replace_with_rope()`](Self::replace_with_rope()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteStartAfterEnd`] if the start of the range is
+    /// greater than the end, [`RopeError::ByteOffsetOutOfBounds`] if the end
+    /// is out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())),
+    /// or [`RopeError::ByteOffsetNotCharBoundary`] if the start or the end
+    /// don't lie on a code point boundary.
+    #[inline]
+    pub fn try_replace_with_rope<R>(
+        &mut self,
+        byte_range: R,
+        slice: RopeSlice<'_>,
+    ) -> Result<(), RopeError>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            check_byte_range(byte_range, self.byte_len(), |i| self.byte(i))?;
+        self.replace_with_rope(start..end, slice);
+        Ok(())
+    }
+
+    /// Non-panicking version of [`split_off()`](Self::split_off()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteOffsetOutOfBounds`] if the byte offset is out
+    /// of bounds (i.e. greater than [`byte_len()`](Self::byte_len())), or
+    /// [`RopeError::ByteOffsetNotCharBoundary`] if it doesn't lie on a code
+    /// point boundary.
+    #[inline]
+    pub fn try_split_off(
+        &mut self,
+        byte_offset: usize,
+    ) -> Result<Self, RopeError> {
+        check_char_boundary(byte_offset, self.byte_len(), |i| self.byte(i))?;
+        Ok(self.split_off(byte_offset))
+    }
+
+    /// Non-panicking version of
+    /// [`utf16_code_unit_of_byte()`](Self::utf16_code_unit_of_byte()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteOffsetOutOfBounds`] if the byte offset is out
+    /// of bounds (i.e. greater than [`byte_len()`](Self::byte_len())), or
+    /// [`RopeError::ByteOffsetNotCharBoundary`] if it doesn't lie on a code
+    /// point boundary.
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[inline]
+    pub fn try_utf16_code_unit_of_byte(
+        &self,
+        byte_offset: usize,
+    ) -> Result<usize, RopeError> {
+        check_char_boundary(byte_offset, self.byte_len(), |i| self.byte(i))?;
+        Ok(self.utf16_code_unit_of_byte(byte_offset))
+    }
+
+    /// Non-panicking version of [`utf16_slice()`](Self::utf16_slice()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::Utf16StartAfterEnd`] if the start of the range is
+    /// greater than the end, or [`RopeError::Utf16OffsetOutOfBounds`] if the
+    /// end is out of bounds (i.e. greater than
+    /// [`utf16_len()`](Self::utf16_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[inline]
+    pub fn try_utf16_slice<R>(
+        &self,
+        utf16_range: R,
+    ) -> Result<RopeSlice<'_>, RopeError>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = check_utf16_range(utf16_range, self.utf16_len())?;
+        Ok(self.utf16_slice(start..end))
+    }
+}
+
+impl<'a> RopeSlice<'a> {
+    /// Non-panicking version of [`byte()`](Self::byte()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteIndexOutOfBounds`] if the byte index is out of
+    /// bounds (i.e. greater than or equal to
+    /// [`byte_len()`](Self::byte_len())).
+    #[inline]
+    pub fn try_byte(&self, byte_index: usize) -> Result<u8, RopeError> {
+        check_byte_index(byte_index, self.byte_len())?;
+        Ok(self.byte(byte_index))
+    }
+
+    /// Non-panicking version of [`byte_of_line()`](Self::byte_of_line()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::LineOffsetOutOfBounds`] if the line offset is out
+    /// of bounds (i.e. greater than [`line_len()`](Self::line_len())).
+    #[inline]
+    pub fn try_byte_of_line(
+        &self,
+        line_offset: usize,
+    ) -> Result<usize, RopeError> {
+        check_line_offset(line_offset, self.line_len())?;
+        Ok(self.byte_of_line(line_offset))
+    }
+
+    /// Non-panicking version of
+    /// [`byte_of_utf16_code_unit()`](Self::byte_of_utf16_code_unit()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::Utf16OffsetOutOfBounds`] if the UTF-16 code unit
+    /// offset is out of bounds (i.e. greater than
+    /// [`utf16_len()`](Self::utf16_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[inline]
+    pub fn try_byte_of_utf16_code_unit(
+        &self,
+        utf16_offset: usize,
+    ) -> Result<usize, RopeError> {
+        check_utf16_offset(utf16_offset, self.utf16_len())?;
+        Ok(self.byte_of_utf16_code_unit(utf16_offset))
+    }
+
+    /// Non-panicking version of [`byte_slice()`](Self::byte_slice()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteStartAfterEnd`] if the start of the range is
+    /// greater than the end, [`RopeError::ByteOffsetOutOfBounds`] if the end
+    /// is out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())),
+    /// or [`RopeError::ByteOffsetNotCharBoundary`] if the start or the end
+    /// don't lie on a code point boundary.
+    #[inline]
+    pub fn try_byte_slice<R>(
+        self,
+        byte_range: R,
+    ) -> Result<RopeSlice<'a>, RopeError>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            check_byte_range(byte_range, self.byte_len(), |i| self.byte(i))?;
+        Ok(self.byte_slice(start..end))
+    }
+
+    /// Non-panicking version of
+    /// [`is_char_boundary()`](Self::is_char_boundary()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteOffsetOutOfBounds`] if the byte offset is out
+    /// of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    #[inline]
+    pub fn try_is_char_boundary(
+        &self,
+        byte_offset: usize,
+    ) -> Result<bool, RopeError> {
+        check_byte_offset(byte_offset, self.byte_len())?;
+        Ok(self.is_char_boundary(byte_offset))
+    }
+
+    /// Non-panicking version of
+    /// [`is_grapheme_boundary()`](Self::is_grapheme_boundary()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteOffsetOutOfBounds`] if the byte offset is out
+    /// of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn try_is_grapheme_boundary(
+        &self,
+        byte_offset: usize,
+    ) -> Result<bool, RopeError> {
+        check_byte_offset(byte_offset, self.byte_len())?;
+        Ok(self.is_grapheme_boundary(byte_offset))
+    }
+
+    /// Non-panicking version of [`line()`](Self::line()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::LineIndexOutOfBounds`] if the line index is out of
+    /// bounds (i.e. greater than or equal to
+    /// [`line_len()`](Self::line_len())).
+    #[inline]
+    pub fn try_line(
+        self,
+        line_index: usize,
+    ) -> Result<RopeSlice<'a>, RopeError> {
+        check_line_index(line_index, self.line_len())?;
+        Ok(self.line(line_index))
+    }
+
+    /// Non-panicking version of [`line_of_byte()`](Self::line_of_byte()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteOffsetOutOfBounds`] if the byte offset is out
+    /// of bounds (i.e. greater than [`byte_len()`](Self::byte_len())), or
+    /// [`RopeError::ByteOffsetNotCharBoundary`] if it doesn't lie on a code
+    /// point boundary.
+    #[inline]
+    pub fn try_line_of_byte(
+        &self,
+        byte_offset: usize,
+    ) -> Result<usize, RopeError> {
+        check_char_boundary(byte_offset, self.byte_len(), |i| self.byte(i))?;
+        Ok(self.line_of_byte(byte_offset))
+    }
+
+    /// Non-panicking version of [`line_slice()`](Self::line_slice()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::LineStartAfterEnd`] if the start of the range is
+    /// greater than the end, or [`RopeError::LineOffsetOutOfBounds`] if the
+    /// end is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    #[inline]
+    pub fn try_line_slice<R>(
+        self,
+        line_range: R,
+    ) -> Result<RopeSlice<'a>, RopeError>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = check_line_range(line_range, self.line_len())?;
+        Ok(self.line_slice(start..end))
+    }
+
+    /// Non-panicking version of
+    /// [`utf16_code_unit_of_byte()`](Self::utf16_code_unit_of_byte()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteOffsetOutOfBounds`] if the byte offset is out
+    /// of bounds (i.e. greater than [`byte_len()`](Self::byte_len())), or
+    /// [`RopeError::ByteOffsetNotCharBoundary`] if it doesn't lie on a code
+    /// point boundary.
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[inline]
+    pub fn try_utf16_code_unit_of_byte(
+        &self,
+        byte_offset: usize,
+    ) -> Result<usize, RopeError> {
+        check_char_boundary(byte_offset, self.byte_len(), |i| self.byte(i))?;
+        Ok(self.utf16_code_unit_of_byte(byte_offset))
+    }
+
+    /// Non-panicking version of [`utf16_slice()`](Self::utf16_slice()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::Utf16StartAfterEnd`] if the start of the range is
+    /// greater than the end, or [`RopeError::Utf16OffsetOutOfBounds`] if the
+    /// end is out of bounds (i.e. greater than
+    /// [`utf16_len()`](Self::utf16_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[inline]
+    pub fn try_utf16_slice<R>(
+        self,
+        utf16_range: R,
+    ) -> Result<RopeSlice<'a>, RopeError>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = check_utf16_range(utf16_range, self.utf16_len())?;
+        Ok(self.utf16_slice(start..end))
+    }
+}
+
+impl RopeSpan {
+    /// Non-panicking version of [`byte()`](Self::byte()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteIndexOutOfBounds`] if the byte index is out of
+    /// bounds (i.e. greater than or equal to
+    /// [`byte_len()`](Self::byte_len())).
+    #[inline]
+    pub fn try_byte(&self, byte_index: usize) -> Result<u8, RopeError> {
+        self.as_slice().try_byte(byte_index)
+    }
+
+    /// Non-panicking version of [`byte_of_line()`](Self::byte_of_line()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::LineOffsetOutOfBounds`] if the line offset is out
+    /// of bounds (i.e. greater than [`line_len()`](Self::line_len())).
+    #[inline]
+    pub fn try_byte_of_line(
+        &self,
+        line_offset: usize,
+    ) -> Result<usize, RopeError> {
+        self.as_slice().try_byte_of_line(line_offset)
+    }
+
+    /// Non-panicking version of
+    /// [`byte_of_utf16_code_unit()`](Self::byte_of_utf16_code_unit()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::Utf16OffsetOutOfBounds`] if the UTF-16 code unit
+    /// offset is out of bounds (i.e. greater than
+    /// [`utf16_len()`](Self::utf16_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[inline]
+    pub fn try_byte_of_utf16_code_unit(
+        &self,
+        utf16_offset: usize,
+    ) -> Result<usize, RopeError> {
+        self.as_slice().try_byte_of_utf16_code_unit(utf16_offset)
+    }
+
+    /// Non-panicking version of [`byte_slice()`](Self::byte_slice()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteStartAfterEnd`] if the start of the range is
+    /// greater than the end, [`RopeError::ByteOffsetOutOfBounds`] if the end
+    /// is out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())),
+    /// or [`RopeError::ByteOffsetNotCharBoundary`] if the start or the end
+    /// don't lie on a code point boundary.
+    #[inline]
+    pub fn try_byte_slice<R>(
+        &self,
+        byte_range: R,
+    ) -> Result<RopeSlice<'_>, RopeError>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.as_slice().try_byte_slice(byte_range)
+    }
+
+    /// Non-panicking version of
+    /// [`is_char_boundary()`](Self::is_char_boundary()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteOffsetOutOfBounds`] if the byte offset is out
+    /// of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    #[inline]
+    pub fn try_is_char_boundary(
+        &self,
+        byte_offset: usize,
+    ) -> Result<bool, RopeError> {
+        self.as_slice().try_is_char_boundary(byte_offset)
+    }
+
+    /// Non-panicking version of
+    /// [`is_grapheme_boundary()`](Self::is_grapheme_boundary()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteOffsetOutOfBounds`] if the byte offset is out
+    /// of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn try_is_grapheme_boundary(
+        &self,
+        byte_offset: usize,
+    ) -> Result<bool, RopeError> {
+        self.as_slice().try_is_grapheme_boundary(byte_offset)
+    }
+
+    /// Non-panicking version of [`line()`](Self::line()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::LineIndexOutOfBounds`] if the line index is out of
+    /// bounds (i.e. greater than or equal to
+    /// [`line_len()`](Self::line_len())).
+    #[inline]
+    pub fn try_line(
+        &self,
+        line_index: usize,
+    ) -> Result<RopeSlice<'_>, RopeError> {
+        self.as_slice().try_line(line_index)
+    }
+
+    /// Non-panicking version of [`line_of_byte()`](Self::line_of_byte()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteOffsetOutOfBounds`] if the byte offset is out
+    /// of bounds (i.e. greater than [`byte_len()`](Self::byte_len())), or
+    /// [`RopeError::ByteOffsetNotCharBoundary`] if it doesn't lie on a code
+    /// point boundary.
+    #[inline]
+    pub fn try_line_of_byte(
+        &self,
+        byte_offset: usize,
+    ) -> Result<usize, RopeError> {
+        self.as_slice().try_line_of_byte(byte_offset)
+    }
+
+    /// Non-panicking version of [`line_slice()`](Self::line_slice()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::LineStartAfterEnd`] if the start of the range is
+    /// greater than the end, or [`RopeError::LineOffsetOutOfBounds`] if the
+    /// end is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    #[inline]
+    pub fn try_line_slice<R>(
+        &self,
+        line_range: R,
+    ) -> Result<RopeSlice<'_>, RopeError>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.as_slice().try_line_slice(line_range)
+    }
+
+    /// Non-panicking version of
+    /// [`utf16_code_unit_of_byte()`](Self::utf16_code_unit_of_byte()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::ByteOffsetOutOfBounds`] if the byte offset is out
+    /// of bounds (i.e. greater than [`byte_len()`](Self::byte_len())), or
+    /// [`RopeError::ByteOffsetNotCharBoundary`] if it doesn't lie on a code
+    /// point boundary.
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[inline]
+    pub fn try_utf16_code_unit_of_byte(
+        &self,
+        byte_offset: usize,
+    ) -> Result<usize, RopeError> {
+        self.as_slice().try_utf16_code_unit_of_byte(byte_offset)
+    }
+
+    /// Non-panicking version of [`utf16_slice()`](Self::utf16_slice()).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RopeError::Utf16StartAfterEnd`] if the start of the range is
+    /// greater than the end, or [`RopeError::Utf16OffsetOutOfBounds`] if the
+    /// end is out of bounds (i.e. greater than
+    /// [`utf16_len()`](Self::utf16_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[inline]
+    pub fn try_utf16_slice<R>(
+        &self,
+        utf16_range: R,
+    ) -> Result<RopeSlice<'_>, RopeError>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.as_slice().try_utf16_slice(utf16_range)
+    }
+}
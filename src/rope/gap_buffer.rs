@@ -1,9 +1,13 @@
 //! This module exports the [`GapBuffer`] struct and a few of its methods.
 //!
 //! It also implements several traits exported by the [tree](crate::tree)
-//! module on it to be able to use it as the leaf of our [`Rope`](crate::Rope).
+//! module on it to be able to use it as the leaf of our [`Rope`](crate::Rope)
+//! and of our [`ByteRope`](crate::byte_rope::ByteRope).
 
-use core::ops::{Range, RangeBounds};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::{Index, Range, RangeBounds, RangeFrom, RangeTo};
 
 use super::gap_slice::GapSlice;
 use super::metrics::{ByteMetric, ChunkSummary};
@@ -13,9 +17,16 @@ use crate::tree::{
     AsSlice,
     BalancedLeaf,
     BaseMeasured,
+    CompactableLeaf,
     ReplaceableLeaf,
     Summarize,
 };
+#[cfg(feature = "content-hash")]
+use crate::tree::{HashableLeaf, RollingHash};
+#[cfg(feature = "bracket-depth")]
+use crate::tree::{Nesting, NestingLeaf};
+#[cfg(feature = "text-stats")]
+use crate::tree::{StatsLeaf, TextStats};
 
 /// A [gap buffer] with a max capacity of `2^16 - 1` bytes.
 ///
@@ -32,32 +43,182 @@ use crate::tree::{
 /// valid. The gap is located between the two chunks and has a length of
 /// `len_gap() = MAX_BYTES - len_left - len_right`.
 ///
+/// The contents of the buffer are `str`s by default, but they can be any
+/// type implementing `Contents`, which decides at which byte offsets the
+/// buffer can be split.
+///
 /// [gap buffer]: https://en.wikipedia.org/wiki/Gap_buffer
-#[derive(Clone)]
-pub struct GapBuffer<const MAX_BYTES: usize> {
+pub struct GapBuffer<const MAX_BYTES: usize, T: ?Sized + Contents = str> {
     pub(super) bytes: Box<[u8; MAX_BYTES]>,
     pub(super) left_summary: ChunkSummary,
     pub(super) len_right: u16,
+    pub(super) contents: PhantomData<T>,
+}
+
+/// The contents of a [`GapBuffer`].
+///
+/// This is implemented for `str`, whose byte offsets have to lie on char
+/// boundaries, and for `[u8]`, which can be split at any byte offset.
+pub trait Contents:
+    'static
+    + Index<Range<usize>, Output = Self>
+    + Index<RangeFrom<usize>, Output = Self>
+    + Index<RangeTo<usize>, Output = Self>
+{
+    /// The empty slice.
+    const EMPTY: &'static Self;
+
+    /// Panics with a nicely formatted error message if the given byte offset
+    /// can't be split at.
+    #[track_caller]
+    fn assert_boundary(&self, byte_offset: usize);
+
+    fn as_bytes(&self) -> &[u8];
+
+    /// Formats the contents without the surrounding quotes.
+    fn debug_no_quotes(
+        &self,
+        f: &mut core::fmt::Formatter,
+    ) -> core::fmt::Result;
+
+    /// # Safety
+    ///
+    /// The bytes must be a sequence of valid contents, i.e. they must have
+    /// been obtained by splitting valid contents at boundaries.
+    unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self;
+
+    /// Returns whether the contents can be split at the given byte offset.
+    fn is_boundary(&self, byte_offset: usize) -> bool;
+
+    fn len(&self) -> usize;
+
+    fn summarize(&self) -> ChunkSummary;
 }
 
-impl<const MAX_BYTES: usize> core::fmt::Debug for GapBuffer<MAX_BYTES> {
+impl Contents for str {
+    const EMPTY: &'static Self = "";
+
+    #[track_caller]
+    #[inline]
+    fn assert_boundary(&self, byte_offset: usize) {
+        if !self.is_char_boundary(byte_offset) {
+            panic::byte_offset_not_char_boundary(self, byte_offset)
+        }
+    }
+
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+
+    #[inline]
+    fn debug_no_quotes(
+        &self,
+        f: &mut core::fmt::Formatter,
+    ) -> core::fmt::Result {
+        debug_no_quotes(self, f)
+    }
+
+    #[inline]
+    unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        core::str::from_utf8_unchecked(bytes)
+    }
+
+    #[inline]
+    fn is_boundary(&self, byte_offset: usize) -> bool {
+        self.is_char_boundary(byte_offset)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        str::len(self)
+    }
+
+    #[inline]
+    fn summarize(&self) -> ChunkSummary {
+        ChunkSummary::from(self)
+    }
+}
+
+impl Contents for [u8] {
+    const EMPTY: &'static Self = &[];
+
+    #[inline]
+    fn assert_boundary(&self, byte_offset: usize) {
+        debug_assert!(byte_offset <= self.len());
+    }
+
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    #[inline]
+    fn debug_no_quotes(
+        &self,
+        f: &mut core::fmt::Formatter,
+    ) -> core::fmt::Result {
+        self.iter().try_for_each(|byte| write!(f, "\\x{byte:02X}"))
+    }
+
+    #[inline]
+    unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        bytes
+    }
+
+    #[inline]
+    fn is_boundary(&self, byte_offset: usize) -> bool {
+        byte_offset <= self.len()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+
+    #[inline]
+    fn summarize(&self) -> ChunkSummary {
+        ChunkSummary::from(self)
+    }
+}
+
+impl<const MAX_BYTES: usize, T: ?Sized + Contents> Clone
+    for GapBuffer<MAX_BYTES, T>
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            bytes: self.bytes.clone(),
+            left_summary: self.left_summary,
+            len_right: self.len_right,
+            contents: PhantomData,
+        }
+    }
+}
+
+impl<const MAX_BYTES: usize, T: ?Sized + Contents> core::fmt::Debug
+    for GapBuffer<MAX_BYTES, T>
+{
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         f.write_str("\"")?;
-        debug_no_quotes(self.left_chunk(), f)?;
+        self.left_chunk().debug_no_quotes(f)?;
         write!(f, "{:~^1$}", "", self.len_gap())?;
-        debug_no_quotes(self.right_chunk(), f)?;
+        self.right_chunk().debug_no_quotes(f)?;
         f.write_str("\"")
     }
 }
 
-impl<const MAX_BYTES: usize> Default for GapBuffer<MAX_BYTES> {
+impl<const MAX_BYTES: usize, T: ?Sized + Contents> Default
+    for GapBuffer<MAX_BYTES, T>
+{
     #[inline]
     fn default() -> Self {
         Self {
             bytes: Box::new([0u8; MAX_BYTES]),
             left_summary: ChunkSummary::default(),
             len_right: 0,
+            contents: PhantomData,
         }
     }
 }
@@ -82,18 +243,20 @@ impl<const N: usize> PartialEq<GapBuffer<N>> for GapBuffer<N> {
     }
 }
 
-impl<const MAX_BYTES: usize> From<&str> for GapBuffer<MAX_BYTES> {
+impl<const MAX_BYTES: usize, T: ?Sized + Contents> From<&T>
+    for GapBuffer<MAX_BYTES, T>
+{
     /// # Panics
     ///
     /// Panics if the string's byte length is greater than `MAX_BYTES`.
     #[inline]
-    fn from(s: &str) -> Self {
+    fn from(s: &T) -> Self {
         debug_assert!(s.len() <= MAX_BYTES);
         Self::from_chunks(&[s])
     }
 }
 
-impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
+impl<const MAX_BYTES: usize, T: ?Sized + Contents> GapBuffer<MAX_BYTES, T> {
     /// Moves `bytes_to_add` bytes from the start of the right buffer to the
     /// end of this buffer, returning the summary of what's been added to this
     /// buffer.
@@ -128,7 +291,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
 
         if bytes_to_add <= right.len_left() {
             let (move_left, _) =
-                split_adjusted::<false>(right.left_chunk(), bytes_to_add);
+                split_adjusted::<false, _>(right.left_chunk(), bytes_to_add);
 
             let summary = right.summarize_left_chunk_up_to(move_left.len());
 
@@ -138,12 +301,12 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
 
             summary
         } else {
-            let (move_left, _) = split_adjusted::<false>(
+            let (move_left, _) = split_adjusted::<false, _>(
                 right.right_chunk(),
                 bytes_to_add - right.len_left(),
             );
 
-            let summary = right.left_summary + ChunkSummary::from(move_left);
+            let summary = right.left_summary + move_left.summarize();
 
             self.append_two(right.left_chunk(), move_left);
 
@@ -227,7 +390,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
     /// assert_eq!(buffer.right_chunk(), "bbcc");
     /// ```
     #[inline]
-    pub fn append_str(&mut self, s: &str) {
+    pub fn append_str(&mut self, s: &T) {
         debug_assert!(s.len() <= self.len_gap());
 
         let start = MAX_BYTES - self.len_right();
@@ -260,7 +423,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
     /// assert_eq!(buffer.right_chunk(), "bbccdd");
     /// ```
     #[inline]
-    pub fn append_two(&mut self, a: &str, b: &str) {
+    pub fn append_two(&mut self, a: &T, b: &T) {
         debug_assert!(a.len() + b.len() <= self.len_gap());
 
         // Shift the second chunk to the left.
@@ -285,18 +448,10 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
     fn assert_char_boundary(&self, byte_offset: usize) {
         debug_assert!(byte_offset <= self.len());
 
-        if !self.is_char_boundary(byte_offset) {
-            if byte_offset < self.len_left() {
-                panic::byte_offset_not_char_boundary(
-                    self.left_chunk(),
-                    byte_offset,
-                )
-            } else {
-                panic::byte_offset_not_char_boundary(
-                    self.right_chunk(),
-                    byte_offset - self.len_left(),
-                )
-            }
+        if byte_offset <= self.len_left() {
+            self.left_chunk().assert_boundary(byte_offset)
+        } else {
+            self.right_chunk().assert_boundary(byte_offset - self.len_left())
         }
     }
 
@@ -323,7 +478,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
     /// assert_eq!(buffer.right_chunk(), "ccdd");
     /// ```
     #[inline]
-    pub fn from_chunks(chunks: &[&str]) -> Self {
+    pub fn from_chunks(chunks: &[&T]) -> Self {
         let total_len = chunks.iter().map(|s| s.len()).sum::<usize>();
 
         if total_len == 0 {
@@ -350,9 +505,9 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
 
                 bytes[range].copy_from_slice(chunk.as_bytes());
 
-                summary_left += ChunkSummary::from(chunk);
+                summary_left += chunk.summarize();
             } else {
-                let (to_first, to_second) = split_adjusted::<true>(
+                let (to_first, to_second) = split_adjusted::<true, _>(
                     chunk,
                     to_left - summary_left.bytes(),
                 );
@@ -365,7 +520,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
 
                 bytes[range].copy_from_slice(to_first.as_bytes());
 
-                summary_left += ChunkSummary::from(to_first);
+                summary_left += to_first.summarize();
 
                 let len_right = total_len - summary_left.bytes();
 
@@ -395,6 +550,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
                     bytes,
                     left_summary: summary_left,
                     len_right: len_right as u16,
+                    contents: PhantomData,
                 };
             }
         }
@@ -402,12 +558,6 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
         unreachable!("This can only be reached if the total length is zero");
     }
 
-    /// Returns `true` if the buffer ends with a newline ('\n') character.
-    #[inline]
-    pub(super) fn has_trailing_newline(&self) -> bool {
-        self.last_chunk().ends_with('\n')
-    }
-
     /// Inserts the string at the given byte offset, moving the gap to the new
     /// insertion point if necessary.
     ///
@@ -419,7 +569,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
     pub(super) fn insert(
         &mut self,
         insert_at: usize,
-        s: &str,
+        s: &T,
         summary: ChunkSummary,
     ) -> ChunkSummary {
         debug_assert!(insert_at <= self.len());
@@ -439,7 +589,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
 
         self.bytes[insert_range].copy_from_slice(s.as_bytes());
 
-        let inserted_summary = ChunkSummary::from(s);
+        let inserted_summary = s.summarize();
 
         self.left_summary += inserted_summary;
 
@@ -451,9 +601,9 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
         debug_assert!(byte_offset <= self.len());
 
         if byte_offset <= self.len_left() {
-            self.left_chunk().is_char_boundary(byte_offset)
+            self.left_chunk().is_boundary(byte_offset)
         } else {
-            self.right_chunk().is_char_boundary(byte_offset - self.len_left())
+            self.right_chunk().is_boundary(byte_offset - self.len_left())
         }
     }
 
@@ -463,24 +613,12 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
         self.len() == 0
     }
 
-    /// The right chunk if it's not empty, or the left one otherwise.
-    #[inline]
-    pub(super) fn last_chunk(&self) -> &str {
-        if self.len_right() == 0 {
-            self.left_chunk()
-        } else {
-            self.right_chunk()
-        }
-    }
-
     /// Returns the left chunk of this buffer as a string slice.
     #[inline]
-    pub fn left_chunk(&self) -> &str {
+    pub fn left_chunk(&self) -> &T {
         // SAFETY: all the methods are guaranteed to always keep the first
-        // `len_left()` bytes valid UTF-8.
-        unsafe {
-            core::str::from_utf8_unchecked(&self.bytes[..self.len_left()])
-        }
+        // `len_left()` bytes valid contents, e.g. valid UTF-8 for `str`s.
+        unsafe { T::from_bytes_unchecked(&self.bytes[..self.len_left()]) }
     }
 
     /// Returns the combined byte length of the buffer's left and right chunks.
@@ -629,12 +767,12 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
         debug_assert_eq!(summary, self.summarize());
 
         if bytes_to_move <= self.len_right() {
-            let (_, move_right) = split_adjusted::<true>(
+            let (_, move_right) = split_adjusted::<true, _>(
                 self.right_chunk(),
                 self.len_right() - bytes_to_move,
             );
 
-            let moved_summary = ChunkSummary::from(move_right);
+            let moved_summary = move_right.summarize();
 
             right.prepend(move_right, moved_summary);
 
@@ -642,12 +780,12 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
 
             moved_summary
         } else {
-            let (_, move_right) = split_adjusted::<true>(
+            let (_, move_right) = split_adjusted::<true, _>(
                 self.left_chunk(),
                 self.len_left() - (bytes_to_move - self.len_right()),
             );
 
-            let move_right_summary = ChunkSummary::from(move_right);
+            let move_right_summary = move_right.summarize();
 
             let moved_summary =
                 move_right_summary + self.right_summary(summary);
@@ -683,9 +821,9 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
     /// assert_eq!(buf, "Hello, World!");
     /// ```
     #[inline]
-    pub fn prepend(&mut self, s: &str, prepended_summary: ChunkSummary) {
+    pub fn prepend(&mut self, s: &T, prepended_summary: ChunkSummary) {
         debug_assert!(s.len() <= self.len_gap());
-        debug_assert_eq!(prepended_summary, ChunkSummary::from(s));
+        debug_assert_eq!(prepended_summary, s.summarize());
 
         // Shift the left chunk over.
         let len_left = self.len_left();
@@ -725,16 +863,13 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
     #[inline]
     pub fn prepend_two(
         &mut self,
-        a: &str,
-        b: &str,
+        a: &T,
+        b: &T,
         prepended_summary: ChunkSummary,
     ) {
         debug_assert!(a.len() + b.len() <= self.len_gap());
 
-        debug_assert_eq!(
-            prepended_summary,
-            ChunkSummary::from(a) + ChunkSummary::from(b)
-        );
+        debug_assert_eq!(prepended_summary, a.summarize() + b.summarize());
 
         // Shift the first segment to the right.
         let len_first = self.len_left();
@@ -825,7 +960,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
     pub fn replace_non_overflowing(
         &mut self,
         Range { start, end }: Range<usize>,
-        s: &str,
+        s: &T,
         summary: ChunkSummary,
     ) -> ChunkSummary {
         debug_assert!(start <= end);
@@ -838,7 +973,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
 
         let removed_summary = self.summarize_range(start..end, summary);
 
-        let added_summary = ChunkSummary::from(s);
+        let added_summary = s.summarize();
 
         self.bytes[start..start + s.len()].copy_from_slice(s.as_bytes());
 
@@ -889,7 +1024,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
     pub fn replace_overflowing(
         &mut self,
         byte_range: Range<usize>,
-        s: &str,
+        s: &T,
         summary: ChunkSummary,
     ) -> (ChunkSummary, Vec<Self>) {
         let Range { start, end } = byte_range;
@@ -904,7 +1039,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
             (&self.left_chunk()[end..], self.right_chunk())
         } else {
             let end = end - self.len_left();
-            ("", &self.right_chunk()[end..])
+            (T::EMPTY, &self.right_chunk()[end..])
         };
 
         if start < Self::min_bytes() {
@@ -915,7 +1050,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
             let missing = Self::min_bytes() - start;
 
             let extras = if s.len() >= missing {
-                let (left, right) = split_adjusted::<true>(s, missing);
+                let (left, right) = split_adjusted::<true, _>(s, missing);
 
                 replacement = left;
 
@@ -924,7 +1059,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
                 let missing = missing - s.len();
 
                 let (left, right) =
-                    split_adjusted::<true>(extra_left, missing);
+                    split_adjusted::<true, _>(extra_left, missing);
 
                 truncate_from += left.len();
 
@@ -933,7 +1068,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
                 let missing = missing - s.len() - extra_left.len();
 
                 let (left, right) =
-                    split_adjusted::<true>(extra_right, missing);
+                    split_adjusted::<true, _>(extra_right, missing);
 
                 truncate_from += extra_left.len() + left.len();
 
@@ -952,7 +1087,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
             let missing = Self::min_bytes() - s.len() - (self.len() - end);
 
             let (new_left, new_right) = if start <= self.len_left() {
-                (&self.left_chunk()[..start], "")
+                (&self.left_chunk()[..start], T::EMPTY)
             } else {
                 let start = start - self.len_left();
                 (self.left_chunk(), &self.right_chunk()[..start])
@@ -961,19 +1096,21 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
             let (add_to_extras_1, add_to_extras_2) = if missing
                 <= new_right.len()
             {
-                let (keep_in_self, add_to_extras) = split_adjusted::<true>(
+                let (keep_in_self, add_to_extras) = split_adjusted::<true, _>(
                     new_right,
                     new_right.len() - missing,
                 );
 
                 truncate_from = new_left.len() + keep_in_self.len();
 
-                ("", add_to_extras)
+                (T::EMPTY, add_to_extras)
             } else {
                 let missing = missing - new_right.len();
 
-                let (keep_in_self, add_to_extras) =
-                    split_adjusted::<true>(new_left, new_left.len() - missing);
+                let (keep_in_self, add_to_extras) = split_adjusted::<true, _>(
+                    new_left,
+                    new_left.len() - missing,
+                );
 
                 truncate_from = keep_in_self.len();
 
@@ -1004,11 +1141,11 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
 
     /// Returns the right chunk of this buffer as a string slice.
     #[inline]
-    pub fn right_chunk(&self) -> &str {
+    pub fn right_chunk(&self) -> &T {
         // SAFETY: all the methods are guaranteed to always keep the last
-        // `len_right()` bytes valid UTF-8.
+        // `len_right()` bytes valid contents, e.g. valid UTF-8 for `str`s.
         unsafe {
-            core::str::from_utf8_unchecked(
+            T::from_bytes_unchecked(
                 &self.bytes[MAX_BYTES - self.len_right()..],
             )
         }
@@ -1023,7 +1160,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
     }
 
     #[inline]
-    pub(super) fn segmenter(s: &str) -> Segmenter<'_, MAX_BYTES> {
+    pub(super) fn segmenter(s: &T) -> Segmenter<'_, MAX_BYTES, T> {
         Segmenter { s, yielded: 0 }
     }
 
@@ -1031,13 +1168,12 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
     #[inline]
     fn summarize_left_chunk_up_to(&self, byte_offset: usize) -> ChunkSummary {
         debug_assert!(byte_offset <= self.len_left());
-        debug_assert!(self.left_chunk().is_char_boundary(byte_offset));
+        debug_assert!(self.left_chunk().is_boundary(byte_offset));
 
         if byte_offset <= self.len_left() / 2 {
-            ChunkSummary::from(&self.left_chunk()[..byte_offset])
+            self.left_chunk()[..byte_offset].summarize()
         } else {
-            self.left_summary
-                - ChunkSummary::from(&self.left_chunk()[byte_offset..])
+            self.left_summary - self.left_chunk()[byte_offset..].summarize()
         }
     }
 
@@ -1080,8 +1216,8 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
         debug_assert_eq!(summary, self.summarize());
 
         #[inline(always)]
-        fn summarize_range<const MAX_BYTES: usize>(
-            buffer: &GapBuffer<MAX_BYTES>,
+        fn summarize_range<const MAX_BYTES: usize, T: ?Sized + Contents>(
+            buffer: &GapBuffer<MAX_BYTES, T>,
             mut start: usize,
             mut end: usize,
             summary: ChunkSummary,
@@ -1089,13 +1225,13 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
             // The whole range is inside the left chunk.
             if end <= buffer.len_left() {
                 let chunk = &buffer.left_chunk()[start..end];
-                ChunkSummary::from(chunk)
+                chunk.summarize()
             }
             // The start is on the left chunk and the end is on the right.
             else if start <= buffer.len_left() {
                 let left_chunk = &buffer.left_chunk()[start..];
 
-                ChunkSummary::from(left_chunk)
+                left_chunk.summarize()
                     + buffer.summarize_right_chunk_up_to(
                         end - buffer.len_left(),
                         summary,
@@ -1106,7 +1242,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
                 start -= buffer.len_left();
                 end -= buffer.len_left();
                 let chunk = &buffer.right_chunk()[start..end];
-                ChunkSummary::from(chunk)
+                chunk.summarize()
             }
         }
 
@@ -1126,7 +1262,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
     /// Computes and returns the summary of the right chunk.
     #[inline]
     fn summarize_right_chunk(&self) -> ChunkSummary {
-        ChunkSummary::from(self.right_chunk())
+        self.right_chunk().summarize()
     }
 
     /// Returns the summary of the right chunk up to the given byte offset.
@@ -1140,15 +1276,15 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
         summary: ChunkSummary,
     ) -> ChunkSummary {
         debug_assert!(byte_offset <= self.len_right());
-        debug_assert!(self.right_chunk().is_char_boundary(byte_offset));
+        debug_assert!(self.right_chunk().is_boundary(byte_offset));
         debug_assert_eq!(summary, self.summarize());
 
         if byte_offset <= self.len_right() / 2 {
-            ChunkSummary::from(&self.right_chunk()[..byte_offset])
+            self.right_chunk()[..byte_offset].summarize()
         } else {
             summary
                 - self.left_summary
-                - ChunkSummary::from(&self.right_chunk()[byte_offset..])
+                - self.right_chunk()[byte_offset..].summarize()
         }
     }
 
@@ -1199,7 +1335,9 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
     }
 }
 
-impl<const MAX_BYTES: usize> Summarize for GapBuffer<MAX_BYTES> {
+impl<const MAX_BYTES: usize, T: ?Sized + Contents> Summarize
+    for GapBuffer<MAX_BYTES, T>
+{
     type Summary = ChunkSummary;
 
     #[inline]
@@ -1208,13 +1346,17 @@ impl<const MAX_BYTES: usize> Summarize for GapBuffer<MAX_BYTES> {
     }
 }
 
-impl<const MAX_BYTES: usize> BaseMeasured for GapBuffer<MAX_BYTES> {
+impl<const MAX_BYTES: usize, T: ?Sized + Contents> BaseMeasured
+    for GapBuffer<MAX_BYTES, T>
+{
     type BaseMetric = ByteMetric;
 }
 
-impl<const MAX_BYTES: usize> From<GapSlice<'_>> for GapBuffer<MAX_BYTES> {
+impl<const MAX_BYTES: usize, T: ?Sized + Contents> From<GapSlice<'_, T>>
+    for GapBuffer<MAX_BYTES, T>
+{
     #[inline]
-    fn from(slice: GapSlice<'_>) -> Self {
+    fn from(slice: GapSlice<'_, T>) -> Self {
         let mut bytes = Box::new([0u8; MAX_BYTES]);
 
         bytes[..slice.len_left()]
@@ -1227,15 +1369,18 @@ impl<const MAX_BYTES: usize> From<GapSlice<'_>> for GapBuffer<MAX_BYTES> {
             bytes,
             left_summary: slice.left_summary,
             len_right: slice.len_right,
+            contents: PhantomData,
         }
     }
 }
 
-impl<const MAX_BYTES: usize> AsSlice for GapBuffer<MAX_BYTES> {
-    type Slice<'a> = GapSlice<'a>;
+impl<const MAX_BYTES: usize, T: ?Sized + Contents> AsSlice
+    for GapBuffer<MAX_BYTES, T>
+{
+    type Slice<'a> = GapSlice<'a, T>;
 
     #[inline]
-    fn as_slice(&self) -> GapSlice<'_> {
+    fn as_slice(&self) -> GapSlice<'_, T> {
         let bytes = match (self.len_left() > 0, self.len_right() > 0) {
             (true, true) => &*self.bytes,
             (true, false) => &self.bytes[..self.len_left()],
@@ -1247,11 +1392,85 @@ impl<const MAX_BYTES: usize> AsSlice for GapBuffer<MAX_BYTES> {
             bytes,
             left_summary: self.left_summary,
             len_right: self.len_right,
+            contents: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "content-hash")]
+impl<const MAX_BYTES: usize> HashableLeaf for GapBuffer<MAX_BYTES> {
+    #[inline]
+    fn rolling_hash(slice: GapSlice<'_>) -> RollingHash {
+        RollingHash::from_bytes(slice.left_chunk().as_bytes())
+            .push_bytes(slice.right_chunk().as_bytes())
+    }
+}
+
+#[cfg(feature = "text-stats")]
+impl<const MAX_BYTES: usize> StatsLeaf for GapBuffer<MAX_BYTES> {
+    #[inline]
+    fn text_stats(slice: GapSlice<'_>) -> TextStats {
+        TextStats::from_text(slice.left_chunk())
+            .concat(TextStats::from_text(slice.right_chunk()))
+    }
+}
+
+#[cfg(feature = "bracket-depth")]
+impl<const MAX_BYTES: usize> NestingLeaf for GapBuffer<MAX_BYTES> {
+    #[inline]
+    fn nesting(slice: GapSlice<'_>) -> Nesting {
+        let mut depth = 0;
+
+        let mut min_depth = 0;
+
+        for chunk in [slice.left_chunk(), slice.right_chunk()] {
+            for byte in chunk.bytes() {
+                depth += bracket_delta(byte);
+                min_depth = min_depth.min(depth);
+            }
+        }
+
+        Nesting { net: depth, min_depth }
+    }
+
+    #[inline]
+    fn find_unmatched_close(
+        slice: GapSlice<'_>,
+        ByteMetric(from): ByteMetric,
+        depth: &mut isize,
+    ) -> Option<ByteMetric> {
+        for offset in from..slice.len() {
+            *depth += bracket_delta(slice.byte(offset));
+
+            if *depth < 0 {
+                return Some(ByteMetric(offset));
+            }
         }
+
+        None
+    }
+
+    #[inline]
+    fn find_unmatched_open(
+        slice: GapSlice<'_>,
+        ByteMetric(to): ByteMetric,
+        depth: &mut isize,
+    ) -> Option<ByteMetric> {
+        for offset in (0..to).rev() {
+            *depth -= bracket_delta(slice.byte(offset));
+
+            if *depth < 0 {
+                return Some(ByteMetric(offset));
+            }
+        }
+
+        None
     }
 }
 
-impl<const MAX_BYTES: usize> BalancedLeaf for GapBuffer<MAX_BYTES> {
+impl<const MAX_BYTES: usize, T: ?Sized + Contents> BalancedLeaf
+    for GapBuffer<MAX_BYTES, T>
+{
     #[inline]
     fn is_underfilled(&self, summary: &ChunkSummary) -> bool {
         summary.bytes() < Self::min_bytes()
@@ -1301,10 +1520,36 @@ impl<const MAX_BYTES: usize> BalancedLeaf for GapBuffer<MAX_BYTES> {
     }
 }
 
-impl<const MAX_BYTES: usize> ReplaceableLeaf<ByteMetric>
-    for GapBuffer<MAX_BYTES>
+impl<const MAX_BYTES: usize, T: ?Sized + Contents> CompactableLeaf
+    for GapBuffer<MAX_BYTES, T>
+{
+    #[inline]
+    fn fill_leaves(
+        (left, left_summary): (&mut Self, &mut ChunkSummary),
+        (right, right_summary): (&mut Self, &mut ChunkSummary),
+    ) {
+        // The right leaf fits entirely in the left one.
+        if left.len() + right.len() <= MAX_BYTES {
+            left.append_other(*left_summary, right);
+            *left_summary += *right_summary;
+            *right_summary = ChunkSummary::new();
+        }
+        // Fill the left leaf up to the last char boundary that fits.
+        else {
+            let moved = left.add_from_right(MAX_BYTES - left.len(), right);
+            *left_summary += moved;
+            *right_summary -= moved;
+        }
+
+        debug_assert_eq!(*left_summary, left.summarize());
+        debug_assert_eq!(*right_summary, right.summarize());
+    }
+}
+
+impl<const MAX_BYTES: usize, T: ?Sized + Contents> ReplaceableLeaf<ByteMetric>
+    for GapBuffer<MAX_BYTES, T>
 {
-    type Replacement<'a> = &'a str;
+    type Replacement<'a> = &'a T;
 
     type ExtraLeaves = alloc::vec::IntoIter<Self>;
 
@@ -1314,7 +1559,7 @@ impl<const MAX_BYTES: usize> ReplaceableLeaf<ByteMetric>
         &mut self,
         summary: &mut ChunkSummary,
         range: R,
-        replacement: &str,
+        replacement: &T,
     ) -> Option<Self::ExtraLeaves>
     where
         R: RangeBounds<ByteMetric>,
@@ -1358,7 +1603,7 @@ impl<const MAX_BYTES: usize> ReplaceableLeaf<ByteMetric>
     #[track_caller]
     #[inline]
     fn remove_up_to(&mut self, summary: &mut ChunkSummary, up_to: ByteMetric) {
-        self.replace(summary, ..up_to, "");
+        self.replace(summary, ..up_to, T::EMPTY);
     }
 }
 
@@ -1368,13 +1613,19 @@ impl<const MAX_BYTES: usize> ReplaceableLeaf<ByteMetric>
 /// The only exception is if the string is shorter than
 /// [`GapBuffer::chunk_min()`], in which case this will only yield a single gap
 /// buffer with the entire string.
-pub(super) struct Segmenter<'a, const MAX_BYTES: usize> {
-    s: &'a str,
+pub(super) struct Segmenter<
+    'a,
+    const MAX_BYTES: usize,
+    T: ?Sized + Contents = str,
+> {
+    s: &'a T,
     yielded: usize,
 }
 
-impl<'a, const MAX_BYTES: usize> Iterator for Segmenter<'a, MAX_BYTES> {
-    type Item = &'a str;
+impl<'a, const MAX_BYTES: usize, T: ?Sized + Contents> Iterator
+    for Segmenter<'a, MAX_BYTES, T>
+{
+    type Item = &'a T;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
@@ -1383,7 +1634,7 @@ impl<'a, const MAX_BYTES: usize> Iterator for Segmenter<'a, MAX_BYTES> {
         let chunk = if remaining == 0 {
             return None;
         } else if remaining > MAX_BYTES {
-            let min = GapBuffer::<MAX_BYTES>::min_bytes();
+            let min = GapBuffer::<MAX_BYTES, T>::min_bytes();
 
             let chunk_len = if remaining - MAX_BYTES >= min {
                 MAX_BYTES
@@ -1392,13 +1643,13 @@ impl<'a, const MAX_BYTES: usize> Iterator for Segmenter<'a, MAX_BYTES> {
                 remaining - min
             };
 
-            let mut adjusted_len = adjust_split_point::<false>(
+            let mut adjusted_len = adjust_split_point::<false, _>(
                 &self.s[self.yielded..],
                 chunk_len,
             );
 
             if adjusted_len == 0 {
-                adjusted_len = adjust_split_point::<true>(
+                adjusted_len = adjust_split_point::<true, _>(
                     &self.s[self.yielded..],
                     chunk_len,
                 );
@@ -1408,7 +1659,7 @@ impl<'a, const MAX_BYTES: usize> Iterator for Segmenter<'a, MAX_BYTES> {
         } else {
             debug_assert!(
                 self.yielded == 0
-                    || remaining >= GapBuffer::<MAX_BYTES>::chunk_min()
+                    || remaining >= GapBuffer::<MAX_BYTES, T>::chunk_min()
             );
 
             &self.s[self.s.len() - remaining..]
@@ -1425,29 +1676,37 @@ impl<'a, const MAX_BYTES: usize> Iterator for Segmenter<'a, MAX_BYTES> {
 /// The yielded [`GapBuffer`]s should be equal to the ones yielded by the
 /// [`Segmenter`] iterator initialized with a string that is the concatenation
 /// of the strings passed to this iterator.
-pub(super) struct Resegmenter<'a, const CHUNKS: usize, const MAX_BYTES: usize>
-{
-    segments: [&'a str; CHUNKS],
+pub(super) struct Resegmenter<
+    'a,
+    const CHUNKS: usize,
+    const MAX_BYTES: usize,
+    T: ?Sized + Contents = str,
+> {
+    segments: [&'a T; CHUNKS],
     start: usize,
     yielded: usize,
     total: usize,
 }
 
-impl<'a, const CHUNKS: usize, const MAX_BYTES: usize>
-    Resegmenter<'a, CHUNKS, MAX_BYTES>
+impl<'a, const CHUNKS: usize, const MAX_BYTES: usize, T>
+    Resegmenter<'a, CHUNKS, MAX_BYTES, T>
+where
+    T: ?Sized + Contents,
 {
     #[inline]
-    fn new(segments: [&'a str; CHUNKS]) -> Self {
+    fn new(segments: [&'a T; CHUNKS]) -> Self {
         let total = segments.iter().map(|s| s.len()).sum::<usize>();
-        debug_assert!(total >= GapBuffer::<MAX_BYTES>::chunk_min());
+        debug_assert!(total >= GapBuffer::<MAX_BYTES, T>::chunk_min());
         Self { total, segments, yielded: 0, start: 0 }
     }
 }
 
-impl<'a, const CHUNKS: usize, const MAX_BYTES: usize> Iterator
-    for Resegmenter<'a, CHUNKS, MAX_BYTES>
+impl<'a, const CHUNKS: usize, const MAX_BYTES: usize, T> Iterator
+    for Resegmenter<'a, CHUNKS, MAX_BYTES, T>
+where
+    T: ?Sized + Contents,
 {
-    type Item = GapBuffer<MAX_BYTES>;
+    type Item = GapBuffer<MAX_BYTES, T>;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
@@ -1460,7 +1719,7 @@ impl<'a, const CHUNKS: usize, const MAX_BYTES: usize> Iterator
 
             let mut bytes_in_next = 0;
 
-            let min_bytes = GapBuffer::<MAX_BYTES>::min_bytes();
+            let min_bytes = GapBuffer::<MAX_BYTES, T>::min_bytes();
 
             for (idx, &segment) in
                 self.segments[self.start..].iter().enumerate()
@@ -1486,7 +1745,7 @@ impl<'a, const CHUNKS: usize, const MAX_BYTES: usize> Iterator
                 last_segment_len = remaining - bytes_in_next - min_bytes
             }
 
-            let (mut left, mut right) = split_adjusted::<false>(
+            let (mut left, mut right) = split_adjusted::<false, _>(
                 self.segments[idx_last],
                 last_segment_len,
             );
@@ -1500,7 +1759,7 @@ impl<'a, const CHUNKS: usize, const MAX_BYTES: usize> Iterator
                 + left.len())
                 == 0
             {
-                (left, right) = split_adjusted::<true>(
+                (left, right) = split_adjusted::<true, _>(
                     self.segments[idx_last],
                     last_segment_len,
                 );
@@ -1510,7 +1769,7 @@ impl<'a, const CHUNKS: usize, const MAX_BYTES: usize> Iterator
                 self.segments[idx_last] = left;
             }
 
-            let next = GapBuffer::<MAX_BYTES>::from_chunks(
+            let next = GapBuffer::<MAX_BYTES, T>::from_chunks(
                 &self.segments[self.start..=idx_last],
             );
 
@@ -1520,11 +1779,13 @@ impl<'a, const CHUNKS: usize, const MAX_BYTES: usize> Iterator
 
             next
         } else {
-            debug_assert!(remaining >= GapBuffer::<MAX_BYTES>::chunk_min());
-            GapBuffer::<MAX_BYTES>::from_chunks(&self.segments[self.start..])
+            debug_assert!(remaining >= GapBuffer::<MAX_BYTES, T>::chunk_min());
+            GapBuffer::<MAX_BYTES, T>::from_chunks(
+                &self.segments[self.start..],
+            )
         };
 
-        debug_assert!(next.len() >= GapBuffer::<MAX_BYTES>::chunk_min());
+        debug_assert!(next.len() >= GapBuffer::<MAX_BYTES, T>::chunk_min());
 
         self.yielded += next.len();
 
@@ -1,22 +1,45 @@
+use core::marker::PhantomData;
+
+use super::gap_buffer::Contents;
 use super::metrics::{ChunkSummary, SummaryUpTo, ToByteOffset};
-use super::utils::{debug_no_quotes, panic_messages as panic};
 use crate::tree::{Metric, Summarize};
 
 /// A slice of a [`GapBuffer`](super::gap_buffer::GapBuffer).
-#[derive(Copy, Clone, Default)]
-pub struct GapSlice<'a> {
+pub struct GapSlice<'a, T: ?Sized + Contents = str> {
     pub(super) bytes: &'a [u8],
     pub(super) left_summary: ChunkSummary,
     pub(super) len_right: u16,
+    pub(super) contents: PhantomData<T>,
+}
+
+impl<T: ?Sized + Contents> Copy for GapSlice<'_, T> {}
+
+impl<T: ?Sized + Contents> Clone for GapSlice<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized + Contents> Default for GapSlice<'_, T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            bytes: &[],
+            left_summary: ChunkSummary::default(),
+            len_right: 0,
+            contents: PhantomData,
+        }
+    }
 }
 
-impl core::fmt::Debug for GapSlice<'_> {
+impl<T: ?Sized + Contents> core::fmt::Debug for GapSlice<'_, T> {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         f.write_str("\"")?;
-        debug_no_quotes(self.left_chunk(), f)?;
+        self.left_chunk().debug_no_quotes(f)?;
         write!(f, "{:~^1$}", "", self.len_gap())?;
-        debug_no_quotes(self.right_chunk(), f)?;
+        self.right_chunk().debug_no_quotes(f)?;
         f.write_str("\"")
     }
 }
@@ -30,7 +53,7 @@ impl PartialEq<GapSlice<'_>> for &str {
     }
 }
 
-impl<'a> GapSlice<'a> {
+impl<'a, T: ?Sized + Contents> GapSlice<'a, T> {
     /// Panics with a nicely formatted error message if the given byte offset
     /// is not a character boundary.
     #[track_caller]
@@ -38,23 +61,15 @@ impl<'a> GapSlice<'a> {
     pub(super) fn assert_char_boundary(&self, byte_offset: usize) {
         debug_assert!(byte_offset <= self.len());
 
-        if !self.is_char_boundary(byte_offset) {
-            if byte_offset < self.len_left() {
-                panic::byte_offset_not_char_boundary(
-                    self.left_chunk(),
-                    byte_offset,
-                )
-            } else {
-                panic::byte_offset_not_char_boundary(
-                    self.right_chunk(),
-                    byte_offset - self.len_left(),
-                )
-            }
+        if byte_offset <= self.len_left() {
+            self.left_chunk().assert_boundary(byte_offset)
+        } else {
+            self.right_chunk().assert_boundary(byte_offset - self.len_left())
         }
     }
 
     pub(super) fn assert_invariants(&self) {
-        assert_eq!(self.left_summary, ChunkSummary::from(self.left_chunk()));
+        assert_eq!(self.left_summary, self.left_chunk().summarize());
 
         if self.len_right() == 0 {
             assert_eq!(self.len_left(), self.bytes.len());
@@ -89,83 +104,34 @@ impl<'a> GapSlice<'a> {
     }
 
     #[inline]
-    pub(super) fn truncate_last_char(
-        &mut self,
-        summary: ChunkSummary,
-    ) -> ChunkSummary {
-        debug_assert!(self.len() > 0);
-        debug_assert_eq!(summary, self.summarize());
-
-        use core::cmp::Ordering;
-
-        let last_char = self
-            .last_chunk()
-            .chars()
-            .next_back()
-            .expect("this slice isn't empty");
-
-        let removed_summary = ChunkSummary::from(last_char);
-
-        let len_utf8 = removed_summary.bytes();
-
-        match self.len_right.cmp(&(len_utf8 as u16)) {
-            // The slice doesn't have a right chunk, so we shorten the left
-            // chunk.
-            Ordering::Less => {
-                self.left_summary -= removed_summary;
-                self.bytes = &self.bytes[..self.len_left()];
-                self.left_summary
-            },
-
-            // The right chunk has 2 or more characters, so we shorten the right
-            // chunk.
-            Ordering::Greater => {
-                self.len_right -= len_utf8 as u16;
-                self.bytes = &self.bytes[..self.bytes.len() - len_utf8];
-                summary - removed_summary
-            },
-
-            // The right chunk has exactly 1 character, so we can keep just the
-            // left chunk.
-            Ordering::Equal => {
-                self.len_right = 0;
-                self.bytes = &self.bytes[..self.len_left()];
-                self.left_summary
-            },
-        }
+    pub(super) fn empty() -> Self {
+        Self::default()
     }
 
-    /// Removes the trailing line break (if it has one), returning the new
-    /// summary.
+    /// Returns the smallest byte offset greater than or equal to the given
+    /// one that lies on a char boundary.
     #[inline]
-    pub(super) fn truncate_trailing_line_break(
-        &mut self,
-        summary: ChunkSummary,
-    ) -> ChunkSummary {
-        debug_assert_eq!(summary, self.summarize());
-
-        if !self.has_trailing_newline() {
-            return summary;
-        }
-
-        let mut new_summary = self.truncate_last_char(summary);
+    pub(super) fn ceil_char_boundary(&self, mut byte_offset: usize) -> usize {
+        debug_assert!(byte_offset <= self.len());
 
-        if self.last_chunk().ends_with('\r') {
-            new_summary = self.truncate_last_char(new_summary)
+        while !self.is_char_boundary(byte_offset) {
+            byte_offset += 1;
         }
 
-        new_summary
+        byte_offset
     }
 
+    /// Returns the largest byte offset less than or equal to the given one
+    /// that lies on a char boundary.
     #[inline]
-    pub(super) fn empty() -> Self {
-        Self::default()
-    }
+    pub(super) fn floor_char_boundary(&self, mut byte_offset: usize) -> usize {
+        debug_assert!(byte_offset <= self.len());
 
-    /// Returns `true` if it ends with a newline.
-    #[inline]
-    pub(super) fn has_trailing_newline(&self) -> bool {
-        self.last_chunk().ends_with('\n')
+        while !self.is_char_boundary(byte_offset) {
+            byte_offset -= 1;
+        }
+
+        byte_offset
     }
 
     #[inline]
@@ -173,15 +139,15 @@ impl<'a> GapSlice<'a> {
         debug_assert!(byte_offset <= self.len());
 
         if byte_offset <= self.len_left() {
-            self.left_chunk().is_char_boundary(byte_offset)
+            self.left_chunk().is_boundary(byte_offset)
         } else {
-            self.right_chunk().is_char_boundary(byte_offset - self.len_left())
+            self.right_chunk().is_boundary(byte_offset - self.len_left())
         }
     }
 
     /// The second segment if it's not empty, or the first one otherwise.
     #[inline]
-    pub(super) fn last_chunk(&self) -> &'a str {
+    pub(super) fn last_chunk(&self) -> &'a T {
         if self.len_right() == 0 {
             self.left_chunk()
         } else {
@@ -190,11 +156,10 @@ impl<'a> GapSlice<'a> {
     }
 
     #[inline]
-    pub(super) fn left_chunk(&self) -> &'a str {
-        // SAFETY: the first `len_left` bytes are valid UTF-8.
-        unsafe {
-            core::str::from_utf8_unchecked(&self.bytes[..self.len_left()])
-        }
+    pub(super) fn left_chunk(&self) -> &'a T {
+        // SAFETY: the first `len_left` bytes are valid contents, e.g. valid
+        // UTF-8 for `str`s.
+        unsafe { T::from_bytes_unchecked(&self.bytes[..self.len_left()]) }
     }
 
     #[inline]
@@ -218,10 +183,11 @@ impl<'a> GapSlice<'a> {
     }
 
     #[inline]
-    pub(super) fn right_chunk(&self) -> &'a str {
-        // SAFETY: the last `len_right` bytes are valid UTF-8.
+    pub(super) fn right_chunk(&self) -> &'a T {
+        // SAFETY: the last `len_right` bytes are valid contents, e.g. valid
+        // UTF-8 for `str`s.
         unsafe {
-            core::str::from_utf8_unchecked(
+            T::from_bytes_unchecked(
                 &self.bytes[self.bytes.len() - self.len_right()..],
             )
         }
@@ -262,7 +228,7 @@ impl<'a> GapSlice<'a> {
         summary: ChunkSummary,
     ) -> ((Self, ChunkSummary), (Self, ChunkSummary))
     where
-        M: Metric<ChunkSummary> + ToByteOffset + SummaryUpTo,
+        M: Metric<ChunkSummary> + ToByteOffset<T> + SummaryUpTo<T>,
     {
         debug_assert_eq!(summary, self.summarize());
 
@@ -284,12 +250,14 @@ impl<'a> GapSlice<'a> {
                 bytes: bytes_left,
                 left_summary: left_left_summary,
                 len_right: 0,
+                contents: PhantomData,
             };
 
             let right = Self {
                 bytes: bytes_right,
                 left_summary: self.left_summary - left_left_summary,
                 len_right: self.len_right,
+                contents: PhantomData,
             };
 
             ((left, left.left_summary), (right, summary - left.left_summary))
@@ -312,12 +280,14 @@ impl<'a> GapSlice<'a> {
                 bytes: bytes_left,
                 left_summary: self.left_summary,
                 len_right: right_left_summary.bytes() as u16,
+                contents: PhantomData,
             };
 
             let right = Self {
                 bytes: bytes_right,
                 left_summary: self.right_summary(summary) - right_left_summary,
                 len_right: 0,
+                contents: PhantomData,
             };
 
             ((left, summary - right.left_summary), (right, right.left_summary))
@@ -348,11 +318,88 @@ impl<'a> GapSlice<'a> {
 
     #[inline]
     fn summarize_right_chunk(&self) -> ChunkSummary {
-        ChunkSummary::from(self.right_chunk())
+        self.right_chunk().summarize()
+    }
+}
+
+impl<'a> GapSlice<'a> {
+    /// Returns `true` if it ends with a newline.
+    #[inline]
+    pub(super) fn has_trailing_newline(&self) -> bool {
+        self.last_chunk().ends_with('\n')
+    }
+
+    #[inline]
+    pub(super) fn truncate_last_char(
+        &mut self,
+        summary: ChunkSummary,
+    ) -> ChunkSummary {
+        debug_assert!(self.len() > 0);
+        debug_assert_eq!(summary, self.summarize());
+
+        use core::cmp::Ordering;
+
+        let last_char = self
+            .last_chunk()
+            .chars()
+            .next_back()
+            .expect("this slice isn't empty");
+
+        let removed_summary = ChunkSummary::from(last_char);
+
+        let len_utf8 = removed_summary.bytes();
+
+        match self.len_right.cmp(&(len_utf8 as u16)) {
+            // The slice doesn't have a right chunk, so we shorten the left
+            // chunk.
+            Ordering::Less => {
+                self.left_summary -= removed_summary;
+                self.bytes = &self.bytes[..self.len_left()];
+                self.left_summary
+            },
+
+            // The right chunk has 2 or more characters, so we shorten the right
+            // chunk.
+            Ordering::Greater => {
+                self.len_right -= len_utf8 as u16;
+                self.bytes = &self.bytes[..self.bytes.len() - len_utf8];
+                summary - removed_summary
+            },
+
+            // The right chunk has exactly 1 character, so we can keep just the
+            // left chunk.
+            Ordering::Equal => {
+                self.len_right = 0;
+                self.bytes = &self.bytes[..self.len_left()];
+                self.left_summary
+            },
+        }
+    }
+
+    /// Removes the trailing line break (if it has one), returning the new
+    /// summary.
+    #[inline]
+    pub(super) fn truncate_trailing_line_break(
+        &mut self,
+        summary: ChunkSummary,
+    ) -> ChunkSummary {
+        debug_assert_eq!(summary, self.summarize());
+
+        if !self.has_trailing_newline() {
+            return summary;
+        }
+
+        let mut new_summary = self.truncate_last_char(summary);
+
+        if self.last_chunk().ends_with('\r') {
+            new_summary = self.truncate_last_char(new_summary)
+        }
+
+        new_summary
     }
 }
 
-impl Summarize for GapSlice<'_> {
+impl<T: ?Sized + Contents> Summarize for GapSlice<'_, T> {
     type Summary = ChunkSummary;
 
     #[inline]
@@ -363,6 +410,8 @@ impl Summarize for GapSlice<'_> {
 
 #[cfg(test)]
 mod tests {
+    use std::format;
+
     use crate::rope::gap_buffer::GapBuffer;
     use crate::tree::{AsSlice, Summarize};
 
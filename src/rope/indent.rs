@@ -0,0 +1,136 @@
+use super::iterators::Lines;
+use super::RopeSlice;
+
+/// The indentation style of a [`Rope`](crate::Rope), returned by
+/// [`Rope::detect_indent()`](crate::Rope::detect_indent).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Indent {
+    /// Lines are indented with tabs.
+    Tabs,
+
+    /// Lines are indented with the given number of spaces per level.
+    Spaces(usize),
+}
+
+/// The largest indentation width, in spaces, that `detect()` will consider.
+const MAX_SPACES: usize = 8;
+
+/// Returns the number of bytes of leading whitespace to remove from the line
+/// to dedent it by `width` columns, where tabs advance to the next multiple
+/// of `tab_width`.
+///
+/// Whitespace that would only be partially removed (i.e. a tab spanning past
+/// `width`) is kept.
+#[inline]
+pub(super) fn dedent_len(
+    line: RopeSlice<'_>,
+    width: usize,
+    tab_width: usize,
+) -> usize {
+    debug_assert!(tab_width > 0);
+
+    let mut column = 0;
+
+    line.bytes()
+        .take_while(|&byte| {
+            let next_column = match byte {
+                b' ' => column + 1,
+                b'\t' => (column / tab_width + 1) * tab_width,
+                _ => return false,
+            };
+
+            if next_column > width {
+                return false;
+            }
+
+            column = next_column;
+
+            true
+        })
+        .count()
+}
+
+/// Guesses the indentation style from the leading whitespace of the lines.
+///
+/// If more lines start with a tab than with a space the indentation is
+/// [`Indent::Tabs`]. Otherwise it's the most common (non-zero) difference in
+/// the number of leading spaces between two consecutive non-blank lines.
+#[inline]
+pub(super) fn detect(lines: Lines<'_>) -> Option<Indent> {
+    let mut tab_lines = 0;
+
+    let mut space_lines = 0;
+
+    // `deltas[n]` is the number of times the indentation changed by `n`
+    // spaces between two consecutive non-blank lines.
+    let mut deltas = [0usize; MAX_SPACES + 1];
+
+    let mut prev_spaces = 0;
+
+    for line in lines {
+        let whitespace = leading_whitespace(line);
+
+        // Skip the lines that only contain whitespace.
+        if whitespace == line.byte_len() {
+            continue;
+        }
+
+        let spaces = match line.byte(0) {
+            b'\t' => {
+                tab_lines += 1;
+                continue;
+            },
+
+            b' ' => {
+                space_lines += 1;
+                line.bytes().take_while(|&byte| byte == b' ').count()
+            },
+
+            _ => 0,
+        };
+
+        let delta = spaces.abs_diff(prev_spaces);
+
+        if delta <= MAX_SPACES {
+            deltas[delta] += 1;
+        }
+
+        prev_spaces = spaces;
+    }
+
+    if tab_lines == 0 && space_lines == 0 {
+        return None;
+    }
+
+    if tab_lines > space_lines {
+        return Some(Indent::Tabs);
+    }
+
+    // Ties are broken in favour of the smaller width.
+    let (width, _) = deltas
+        .iter()
+        .enumerate()
+        .skip(1)
+        .rev()
+        .max_by_key(|&(_, &count)| count)
+        .filter(|&(_, &count)| count > 0)?;
+
+    Some(Indent::Spaces(width))
+}
+
+/// Returns whether the line only contains its line terminator.
+#[inline]
+pub(super) fn is_blank(line: RopeSlice<'_>) -> bool {
+    let mut bytes = line.bytes();
+
+    matches!(
+        (bytes.next(), bytes.next()),
+        (Some(b'\n'), None) | (Some(b'\r'), Some(b'\n'))
+    )
+}
+
+/// Returns the number of leading spaces and tabs in the line.
+#[inline]
+pub(super) fn leading_whitespace(line: RopeSlice<'_>) -> usize {
+    line.bytes().take_while(|&byte| byte == b' ' || byte == b'\t').count()
+}
@@ -1,3 +1,4 @@
+use super::block::{self, ColumnUnit};
 use super::metrics::{ByteMetric, LineMetric, RawLineMetric};
 use super::rope::RopeChunk;
 use super::{Rope, RopeSlice};
@@ -379,7 +380,9 @@ pub struct RawLines<'a> {
     /// The number of lines that have been yielded so far.
     lines_yielded: usize,
 
-    /// The total number of bytes this iterator will yield.
+    /// The total number of lines this iterator will yield. The units
+    /// iterator doesn't know when the forward and backward ends meet, so this
+    /// is also used to stop yielding lines once they do.
     lines_total: usize,
 }
 
@@ -410,6 +413,10 @@ impl<'a> Iterator for RawLines<'a> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
+        if self.lines_yielded == self.lines_total {
+            return None;
+        }
+
         let (tree_slice, _) = self.units.next()?;
         self.lines_yielded += 1;
         Some(RopeSlice::from(tree_slice))
@@ -425,6 +432,10 @@ impl<'a> Iterator for RawLines<'a> {
 impl DoubleEndedIterator for RawLines<'_> {
     #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
+        if self.lines_yielded == self.lines_total {
+            return None;
+        }
+
         let (tree_slice, _) = self.units.next_back()?;
         self.lines_yielded += 1;
         Some(RopeSlice::from(tree_slice))
@@ -452,7 +463,9 @@ pub struct Lines<'a> {
     /// The number of lines that have been yielded so far.
     lines_yielded: usize,
 
-    /// The total number of bytes this iterator will yield.
+    /// The total number of lines this iterator will yield. The units
+    /// iterator doesn't know when the forward and backward ends meet, so this
+    /// is also used to stop yielding lines once they do.
     lines_total: usize,
 }
 
@@ -483,6 +496,10 @@ impl<'a> Iterator for Lines<'a> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
+        if self.lines_yielded == self.lines_total {
+            return None;
+        }
+
         let (tree_slice, ByteMetric(advance)) = self.units.next()?;
         self.lines_yielded += 1;
 
@@ -511,6 +528,10 @@ impl<'a> Iterator for Lines<'a> {
 impl DoubleEndedIterator for Lines<'_> {
     #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
+        if self.lines_yielded == self.lines_total {
+            return None;
+        }
+
         let (tree_slice, ByteMetric(advance)) = self.units.next_back()?;
         self.lines_yielded += 1;
 
@@ -536,13 +557,174 @@ impl ExactSizeIterator for Lines<'_> {
 
 impl core::iter::FusedIterator for Lines<'_> {}
 
+/// An iterator over the rectangular block of text covered by a column range
+/// in each line of a `Rope`, not including the line terminators.
+///
+/// This struct is created by the
+/// [`block_slice`](crate::Rope::block_slice()) method on `Rope`. See its
+/// documentation for more.
+#[derive(Clone)]
+pub struct BlockSlices<'a> {
+    lines: Lines<'a>,
+
+    /// The start of the column range.
+    start: usize,
+
+    /// The end of the column range.
+    end: usize,
+
+    unit: ColumnUnit,
+}
+
+impl<'a> BlockSlices<'a> {
+    #[inline]
+    pub(super) fn new(
+        lines: Lines<'a>,
+        start: usize,
+        end: usize,
+        unit: ColumnUnit,
+    ) -> Self {
+        debug_assert!(start <= end);
+        Self { lines, start, end, unit }
+    }
+
+    #[inline]
+    fn slice_line(&self, line: RopeSlice<'a>) -> RopeSlice<'a> {
+        let span =
+            block::span_of_columns(line, self.start, self.end, self.unit);
+        line.byte_slice(span.start..span.end)
+    }
+}
+
+impl<'a> Iterator for BlockSlices<'a> {
+    type Item = RopeSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.lines.next()?;
+        Some(self.slice_line(line))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lines.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for BlockSlices<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let line = self.lines.next_back()?;
+        Some(self.slice_line(line))
+    }
+}
+
+impl ExactSizeIterator for BlockSlices<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.lines.len()
+    }
+}
+
+impl core::iter::FusedIterator for BlockSlices<'_> {}
+
+/// An iterator over a range of lines of a [`Rope`], yielding the index of
+/// each line, the byte offset of its start and its contents (not including
+/// the line terminator).
+///
+/// This struct is created by the [`lines_in`](crate::Rope::lines_in())
+/// method on `Rope`. See its documentation for more.
+#[derive(Clone)]
+pub struct IndexedLines<'a> {
+    raw_lines: RawLines<'a>,
+
+    /// The index of the next line yielded by [`Self::next()`].
+    front_line: usize,
+
+    /// The byte offset of the start of the next line yielded by
+    /// [`Self::next()`].
+    front_byte: usize,
+
+    /// The byte offset of the end of the next line yielded by
+    /// [`Self::next_back()`], including its line terminator.
+    back_byte: usize,
+}
+
+impl<'a> IndexedLines<'a> {
+    #[inline]
+    pub(super) fn new(
+        raw_lines: RawLines<'a>,
+        line_start: usize,
+        byte_start: usize,
+        byte_end: usize,
+    ) -> Self {
+        debug_assert!(byte_start <= byte_end);
+
+        Self {
+            raw_lines,
+            front_line: line_start,
+            front_byte: byte_start,
+            back_byte: byte_end,
+        }
+    }
+}
+
+impl<'a> Iterator for IndexedLines<'a> {
+    type Item = (usize, usize, RopeSlice<'a>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let raw_line = self.raw_lines.next()?;
+
+        let (line_idx, byte_start) = (self.front_line, self.front_byte);
+
+        self.front_line += 1;
+        self.front_byte += raw_line.byte_len();
+
+        let (line, _) = block::split_terminator(raw_line);
+
+        Some((line_idx, byte_start, line))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.raw_lines.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for IndexedLines<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let raw_line = self.raw_lines.next_back()?;
+
+        self.back_byte -= raw_line.byte_len();
+
+        // All the lines that are left come before this one.
+        let line_idx = self.front_line + self.raw_lines.len();
+
+        let (line, _) = block::split_terminator(raw_line);
+
+        Some((line_idx, self.back_byte, line))
+    }
+}
+
+impl ExactSizeIterator for IndexedLines<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.raw_lines.len()
+    }
+}
+
+impl core::iter::FusedIterator for IndexedLines<'_> {}
+
 #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
 #[cfg(feature = "graphemes")]
 pub use graphemes::Graphemes;
 
 #[cfg(feature = "graphemes")]
 mod graphemes {
-    use alloc::borrow::Cow;
+    use alloc::borrow::{Cow, ToOwned};
+    use alloc::string::String;
 
     use unicode_segmentation::{GraphemeCursor, GraphemeIncomplete};
 
@@ -909,3 +1091,384 @@ mod graphemes {
 
     impl core::iter::FusedIterator for Graphemes<'_> {}
 }
+
+#[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+#[cfg(feature = "graphemes")]
+pub use segments::{Sentences, WordBounds, Words};
+
+#[cfg(feature = "graphemes")]
+mod segments {
+    use alloc::borrow::{Cow, ToOwned};
+    use alloc::collections::VecDeque;
+    use core::ops::Range;
+
+    use unicode_segmentation::UnicodeSegmentation;
+
+    use super::super::utils::{
+        grow_end,
+        grow_start,
+        next_chunk_end,
+        prev_chunk_start,
+        sentence_boundaries,
+        word_boundaries,
+        SegmentedWindow,
+        Segmenter,
+    };
+    use super::*;
+
+    /// An iterator over the segments of a `RopeSlice`.
+    ///
+    /// The slice is segmented one chunk at a time, and a segment is only
+    /// collected into a `String` if it spans several chunks. A chunk's
+    /// segments are only yielded once we've seen enough of the text after
+    /// (or before, when iterating backward) them, which might require
+    /// segmenting a window that extends into the following chunks.
+    #[derive(Clone)]
+    struct Segments<'a> {
+        slice: RopeSlice<'a>,
+
+        segmenter: Segmenter,
+
+        /// The byte offset of the end of the last segment queued by
+        /// [`Segments::next()`].
+        front: usize,
+
+        /// The byte offset of the start of the last segment queued by
+        /// [`Segments::next_back()`].
+        back: usize,
+
+        /// The window the segments used when calling [`Segments::next()`]
+        /// are taken from.
+        forward_window: Cow<'a, str>,
+
+        /// The ranges of the segments of
+        /// [`forward_window`](Self::forward_window) that haven't been
+        /// yielded yet.
+        forward_segments: VecDeque<Range<usize>>,
+
+        /// The window the segments used when calling
+        /// [`Segments::next_back()`] are taken from.
+        backward_window: Cow<'a, str>,
+
+        /// The ranges of the segments of
+        /// [`backward_window`](Self::backward_window) that haven't been
+        /// yielded yet.
+        backward_segments: VecDeque<Range<usize>>,
+    }
+
+    impl<'a> Segments<'a> {
+        #[inline]
+        fn new(slice: RopeSlice<'a>, segmenter: Segmenter) -> Self {
+            Self {
+                slice,
+                segmenter,
+                front: 0,
+                back: slice.byte_len(),
+                forward_window: Cow::Borrowed(""),
+                forward_segments: VecDeque::new(),
+                backward_window: Cow::Borrowed(""),
+                backward_segments: VecDeque::new(),
+            }
+        }
+
+        /// Queues the segments starting in the chunk containing
+        /// [`front`](Self::front), or does nothing if it has reached
+        /// [`back`](Self::back).
+        #[inline]
+        fn queue_forward(&mut self) {
+            if self.front == self.back {
+                return;
+            }
+
+            let bounds = self.front..self.back;
+
+            let chunk_end = next_chunk_end(self.slice, self.front, self.back);
+
+            let mut window = self.front..chunk_end;
+
+            loop {
+                let segmented = SegmentedWindow::new(
+                    self.slice,
+                    window.clone(),
+                    bounds.clone(),
+                    self.segmenter,
+                );
+
+                let segments = segmented
+                    .boundaries
+                    .windows(2)
+                    .take_while(|bounds| {
+                        bounds[0] < chunk_end
+                            && segmented.has_context_after(bounds[1])
+                    })
+                    .map(|bounds| {
+                        bounds[0] - window.start..bounds[1] - window.start
+                    });
+
+                self.forward_segments.extend(segments);
+
+                if let Some(last) = self.forward_segments.back() {
+                    self.front = window.start + last.end;
+                    self.forward_window = segmented.text;
+                    return;
+                }
+
+                window.end = grow_end(self.slice, &window, self.back);
+            }
+        }
+
+        /// Queues the segments ending in the chunk containing the byte
+        /// before [`back`](Self::back), or does nothing if it has reached
+        /// [`front`](Self::front).
+        #[inline]
+        fn queue_backward(&mut self) {
+            if self.front == self.back {
+                return;
+            }
+
+            let bounds = self.front..self.back;
+
+            let chunk_start =
+                prev_chunk_start(self.slice, self.front, self.back);
+
+            let mut window = chunk_start..self.back;
+
+            loop {
+                let segmented = SegmentedWindow::new(
+                    self.slice,
+                    window.clone(),
+                    bounds.clone(),
+                    self.segmenter,
+                );
+
+                let segments = segmented
+                    .boundaries
+                    .windows(2)
+                    .rev()
+                    .take_while(|bounds| {
+                        bounds[1] > chunk_start
+                            && segmented.has_context_before(bounds[0])
+                    })
+                    .map(|bounds| {
+                        bounds[0] - window.start..bounds[1] - window.start
+                    });
+
+                for segment in segments {
+                    self.backward_segments.push_front(segment);
+                }
+
+                if let Some(first) = self.backward_segments.front() {
+                    self.back = window.start + first.start;
+                    self.backward_window = segmented.text;
+                    return;
+                }
+
+                window.start = grow_start(self.slice, &window, self.front);
+            }
+        }
+    }
+
+    #[inline]
+    fn segment<'a>(
+        window: &Cow<'a, str>,
+        range: Range<usize>,
+    ) -> Cow<'a, str> {
+        match window {
+            Cow::Borrowed(window) => Cow::Borrowed(&window[range]),
+            Cow::Owned(window) => Cow::Owned(window[range].to_owned()),
+        }
+    }
+
+    impl<'a> Iterator for Segments<'a> {
+        type Item = Cow<'a, str>;
+
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.forward_segments.is_empty() {
+                self.queue_forward();
+            }
+
+            if let Some(range) = self.forward_segments.pop_front() {
+                return Some(segment(&self.forward_window, range));
+            }
+
+            // We've reached the segments queued by `next_back()`.
+            let range = self.backward_segments.pop_front()?;
+            Some(segment(&self.backward_window, range))
+        }
+    }
+
+    impl DoubleEndedIterator for Segments<'_> {
+        #[inline]
+        fn next_back(&mut self) -> Option<Self::Item> {
+            if self.backward_segments.is_empty() {
+                self.queue_backward();
+            }
+
+            if let Some(range) = self.backward_segments.pop_back() {
+                return Some(segment(&self.backward_window, range));
+            }
+
+            // We've reached the segments queued by `next()`.
+            let range = self.forward_segments.pop_back()?;
+            Some(segment(&self.forward_window, range))
+        }
+    }
+
+    impl core::iter::FusedIterator for Segments<'_> {}
+
+    /// Returns whether the word bound contains a word, i.e. whether it's
+    /// yielded by [`UnicodeSegmentation::unicode_words()`].
+    #[inline]
+    fn is_word(word_bound: &str) -> bool {
+        word_bound.unicode_words().next().is_some()
+    }
+
+    /// An iterator over the sentences of `Rope`s and `RopeSlice`s, as defined
+    /// by the [Unicode Standard Annex #29][uax29].
+    ///
+    /// This struct is created by the `sentences` method on
+    /// [`Rope`](Rope::sentences()) and [`RopeSlice`](RopeSlice::sentences()).
+    /// See their documentation for more.
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Sentence_Boundaries
+    #[derive(Clone)]
+    pub struct Sentences<'a> {
+        segments: Segments<'a>,
+    }
+
+    impl<'a> From<&'a Rope> for Sentences<'a> {
+        #[inline]
+        fn from(rope: &'a Rope) -> Self {
+            Self {
+                segments: Segments::new(
+                    rope.byte_slice(..),
+                    sentence_boundaries,
+                ),
+            }
+        }
+    }
+
+    impl<'a> From<&RopeSlice<'a>> for Sentences<'a> {
+        #[inline]
+        fn from(slice: &RopeSlice<'a>) -> Self {
+            Self { segments: Segments::new(*slice, sentence_boundaries) }
+        }
+    }
+
+    impl<'a> Iterator for Sentences<'a> {
+        type Item = Cow<'a, str>;
+
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            self.segments.next()
+        }
+    }
+
+    impl DoubleEndedIterator for Sentences<'_> {
+        #[inline]
+        fn next_back(&mut self) -> Option<Self::Item> {
+            self.segments.next_back()
+        }
+    }
+
+    impl core::iter::FusedIterator for Sentences<'_> {}
+
+    /// An iterator over the segments of `Rope`s and `RopeSlice`s separated by
+    /// word boundaries, as defined by the [Unicode Standard Annex #29][uax29].
+    ///
+    /// This struct is created by the `word_bounds` method on
+    /// [`Rope`](Rope::word_bounds()) and
+    /// [`RopeSlice`](RopeSlice::word_bounds()). See their documentation for
+    /// more.
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    #[derive(Clone)]
+    pub struct WordBounds<'a> {
+        segments: Segments<'a>,
+    }
+
+    impl<'a> From<&'a Rope> for WordBounds<'a> {
+        #[inline]
+        fn from(rope: &'a Rope) -> Self {
+            Self {
+                segments: Segments::new(rope.byte_slice(..), word_boundaries),
+            }
+        }
+    }
+
+    impl<'a> From<&RopeSlice<'a>> for WordBounds<'a> {
+        #[inline]
+        fn from(slice: &RopeSlice<'a>) -> Self {
+            Self { segments: Segments::new(*slice, word_boundaries) }
+        }
+    }
+
+    impl<'a> Iterator for WordBounds<'a> {
+        type Item = Cow<'a, str>;
+
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            self.segments.next()
+        }
+    }
+
+    impl DoubleEndedIterator for WordBounds<'_> {
+        #[inline]
+        fn next_back(&mut self) -> Option<Self::Item> {
+            self.segments.next_back()
+        }
+    }
+
+    impl core::iter::FusedIterator for WordBounds<'_> {}
+
+    /// An iterator over the words of `Rope`s and `RopeSlice`s, as defined by
+    /// the [Unicode Standard Annex #29][uax29].
+    ///
+    /// Unlike [`WordBounds`], this skips the segments that don't contain any
+    /// alphanumeric character, like whitespace and punctuation.
+    ///
+    /// This struct is created by the `words` method on
+    /// [`Rope`](Rope::words()) and [`RopeSlice`](RopeSlice::words()). See
+    /// their documentation for more.
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    #[derive(Clone)]
+    pub struct Words<'a> {
+        segments: Segments<'a>,
+    }
+
+    impl<'a> From<&'a Rope> for Words<'a> {
+        #[inline]
+        fn from(rope: &'a Rope) -> Self {
+            Self {
+                segments: Segments::new(rope.byte_slice(..), word_boundaries),
+            }
+        }
+    }
+
+    impl<'a> From<&RopeSlice<'a>> for Words<'a> {
+        #[inline]
+        fn from(slice: &RopeSlice<'a>) -> Self {
+            Self { segments: Segments::new(*slice, word_boundaries) }
+        }
+    }
+
+    impl<'a> Iterator for Words<'a> {
+        type Item = Cow<'a, str>;
+
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            self.segments.find(|segment| is_word(segment))
+        }
+    }
+
+    impl DoubleEndedIterator for Words<'_> {
+        #[inline]
+        fn next_back(&mut self) -> Option<Self::Item> {
+            self.segments.rfind(|segment| is_word(segment))
+        }
+    }
+
+    impl core::iter::FusedIterator for Words<'_> {}
+}
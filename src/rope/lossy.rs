@@ -0,0 +1,346 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::Rope;
+
+/// The UTF-8 encoding of `char::REPLACEMENT_CHARACTER`.
+const REPLACEMENT: &[u8] = "\u{FFFD}".as_bytes();
+
+/// A sequence of bytes that wasn't valid UTF-8 and was replaced by a single
+/// `U+FFFD REPLACEMENT CHARACTER` when building a [`Rope`].
+///
+/// The replacement follows the same rules as
+/// [`String::from_utf8_lossy()`](alloc::string::String::from_utf8_lossy()),
+/// so a sequence is at most 3 bytes long.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InvalidSequence {
+    byte_offset: usize,
+    original_range: Range<usize>,
+    bytes: [u8; 3],
+}
+
+impl InvalidSequence {
+    /// Returns the byte offset of the replacement character in the `Rope`.
+    #[inline]
+    pub fn byte_offset(&self) -> usize {
+        self.byte_offset
+    }
+
+    /// Returns the invalid bytes.
+    #[inline]
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes[..self.original_range.len()]
+    }
+
+    /// Returns the byte range of the invalid bytes in the original input,
+    /// i.e. in the concatenation of everything that was appended to the
+    /// [`RopeBuilder`](crate::RopeBuilder).
+    #[inline]
+    pub fn original_range(&self) -> Range<usize> {
+        self.original_range.clone()
+    }
+}
+
+/// The invalid UTF-8 sequences found while building a [`Rope`] with
+/// [`Rope::from_utf8_lossy()`] or
+/// [`RopeBuilder::append_bytes_lossy()`](crate::RopeBuilder::append_bytes_lossy()),
+/// sorted by their offset.
+///
+/// As long as the `Rope` isn't edited, this can be used to get back the exact
+/// bytes it was built from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InvalidUtf8 {
+    sequences: Vec<InvalidSequence>,
+}
+
+impl<'a> IntoIterator for &'a InvalidUtf8 {
+    type Item = &'a InvalidSequence;
+    type IntoIter = core::slice::Iter<'a, InvalidSequence>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl InvalidUtf8 {
+    /// Calls `f` with the contents of the `Rope`, putting back the original
+    /// invalid bytes in place of their replacement characters.
+    #[inline]
+    fn for_each_original<F, E>(&self, rope: &Rope, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&[u8]) -> Result<(), E>,
+    {
+        let mut last_end = 0;
+
+        for sequence in &self.sequences {
+            let start = sequence.byte_offset;
+            let end = start + REPLACEMENT.len();
+
+            // The `Rope` might've been edited since it was built, in which
+            // case we only restore the sequences whose replacement character
+            // is still there.
+            if start < last_end
+                || end > rope.byte_len()
+                || !rope.is_char_boundary(start)
+                || !rope.is_char_boundary(end)
+                || rope.byte_slice(start..end) != "\u{FFFD}"
+            {
+                continue;
+            }
+
+            for chunk in rope.byte_slice(last_end..start).chunks() {
+                f(chunk.as_bytes())?;
+            }
+
+            f(sequence.bytes())?;
+
+            last_end = end;
+        }
+
+        for chunk in rope.byte_slice(last_end..).chunks() {
+            f(chunk.as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if no invalid sequences were found.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+
+    /// Returns an iterator over the invalid sequences.
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, InvalidSequence> {
+        self.sequences.iter()
+    }
+
+    /// Returns the number of invalid sequences.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    /// Returns the contents of the `Rope` with every replacement character
+    /// substituted back with the invalid bytes it replaced.
+    ///
+    /// Replacement characters that are no longer at the offset they were
+    /// inserted at because the `Rope` has been edited are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let bytes = b"foo\xF0\x90\x80bar\xFF";
+    ///
+    /// let (r, invalid) = Rope::from_utf8_lossy(bytes);
+    ///
+    /// assert_eq!(r, "foo\u{FFFD}bar\u{FFFD}");
+    /// assert_eq!(invalid.original_bytes(&r), bytes);
+    /// ```
+    #[inline]
+    pub fn original_bytes(&self, rope: &Rope) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(rope.byte_len());
+
+        let _ = self.for_each_original::<_, core::convert::Infallible>(
+            rope,
+            |chunk| {
+                bytes.extend_from_slice(chunk);
+                Ok(())
+            },
+        );
+
+        bytes
+    }
+
+    /// Like [`original_bytes()`](Self::original_bytes()), but writes the
+    /// bytes to the writer instead of collecting them.
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn write_original<W: std::io::Write>(
+        &self,
+        rope: &Rope,
+        mut writer: W,
+    ) -> std::io::Result<()> {
+        self.for_each_original(rope, |chunk| writer.write_all(chunk))
+    }
+}
+
+/// A piece of text produced by the [`Utf8Decoder`].
+pub(super) enum Decoded<'a> {
+    /// A valid slice of the input.
+    Str(&'a str),
+
+    /// Either a character whose bytes were split across two inputs or a
+    /// replacement character.
+    Char(char),
+}
+
+/// An incremental lossy UTF-8 decoder, which keeps track of the invalid
+/// sequences it replaces and of incomplete sequences at the end of an input
+/// which could be completed by the next one.
+#[derive(Clone, Default)]
+pub(super) struct Utf8Decoder {
+    /// The start of an incomplete sequence at the end of the last input.
+    pending: [u8; 3],
+
+    pending_len: usize,
+
+    /// The number of bytes of decoded text produced so far.
+    byte_len: usize,
+
+    /// The number of bytes of the input consumed so far, excluding the
+    /// pending ones.
+    original_len: usize,
+
+    invalid: Vec<InvalidSequence>,
+}
+
+impl Utf8Decoder {
+    /// Decodes the next piece of text from `bytes`, advancing it past the
+    /// bytes that were consumed. Returns `None` once all of them have been
+    /// consumed, possibly leaving an incomplete sequence pending.
+    #[inline]
+    pub(super) fn decode_next<'a>(
+        &mut self,
+        bytes: &mut &'a [u8],
+    ) -> Option<Decoded<'a>> {
+        if self.pending_len > 0 {
+            return self.decode_pending(bytes);
+        }
+
+        if bytes.is_empty() {
+            return None;
+        }
+
+        let (valid_up_to, error_len) = match core::str::from_utf8(bytes) {
+            Ok(s) => {
+                *bytes = &[];
+                self.push_valid(s.len());
+                return Some(Decoded::Str(s));
+            },
+
+            Err(err) => (err.valid_up_to(), err.error_len()),
+        };
+
+        if valid_up_to > 0 {
+            let (valid, rest) = bytes.split_at(valid_up_to);
+
+            *bytes = rest;
+
+            self.push_valid(valid.len());
+
+            // SAFETY: `from_utf8()` just checked that the bytes up to
+            // `valid_up_to` are valid UTF-8.
+            let valid = unsafe { core::str::from_utf8_unchecked(valid) };
+
+            return Some(Decoded::Str(valid));
+        }
+
+        match error_len {
+            Some(len) => {
+                let (invalid, rest) = bytes.split_at(len);
+                *bytes = rest;
+                self.push_invalid(invalid);
+                Some(Decoded::Char(char::REPLACEMENT_CHARACTER))
+            },
+
+            // The input ends with the start of a valid sequence.
+            None => {
+                self.pending[..bytes.len()].copy_from_slice(bytes);
+                self.pending_len = bytes.len();
+                *bytes = &[];
+                None
+            },
+        }
+    }
+
+    /// Tries to complete the pending sequence with the first bytes of the
+    /// input.
+    #[inline]
+    fn decode_pending<'a>(
+        &mut self,
+        bytes: &mut &'a [u8],
+    ) -> Option<Decoded<'a>> {
+        while let Some((&byte, rest)) = bytes.split_first() {
+            let mut buf = [0; 4];
+            buf[..self.pending_len]
+                .copy_from_slice(&self.pending[..self.pending_len]);
+            buf[self.pending_len] = byte;
+
+            match core::str::from_utf8(&buf[..self.pending_len + 1]) {
+                Ok(s) => {
+                    *bytes = rest;
+                    self.pending_len = 0;
+                    self.push_valid(s.len());
+                    return s.chars().next().map(Decoded::Char);
+                },
+
+                Err(err) if err.error_len().is_none() => {
+                    *bytes = rest;
+                    self.pending[self.pending_len] = byte;
+                    self.pending_len += 1;
+                },
+
+                // The pending bytes were a valid prefix, so the new byte is
+                // the one that can't continue the sequence. We leave it in
+                // the input to be decoded on its own.
+                Err(_) => return self.finish().map(Decoded::Char),
+            }
+        }
+
+        None
+    }
+
+    /// Replaces the pending sequence (if any) with a replacement character,
+    /// which is returned.
+    #[inline]
+    pub(super) fn finish(&mut self) -> Option<char> {
+        if self.pending_len == 0 {
+            return None;
+        }
+
+        let pending = self.pending;
+        let len = core::mem::take(&mut self.pending_len);
+
+        self.push_invalid(&pending[..len]);
+
+        Some(char::REPLACEMENT_CHARACTER)
+    }
+
+    /// Returns the invalid sequences found so far.
+    #[inline]
+    pub(super) fn into_invalid(self) -> InvalidUtf8 {
+        debug_assert_eq!(self.pending_len, 0);
+        InvalidUtf8 { sequences: self.invalid }
+    }
+
+    /// Records an invalid sequence, which is replaced by a replacement
+    /// character.
+    #[inline]
+    fn push_invalid(&mut self, invalid: &[u8]) {
+        let mut bytes = [0; 3];
+        bytes[..invalid.len()].copy_from_slice(invalid);
+
+        self.invalid.push(InvalidSequence {
+            byte_offset: self.byte_len,
+            original_range: self.original_len
+                ..self.original_len + invalid.len(),
+            bytes,
+        });
+
+        self.byte_len += REPLACEMENT.len();
+        self.original_len += invalid.len();
+    }
+
+    /// Records `len` bytes of valid text.
+    #[inline]
+    pub(super) fn push_valid(&mut self, len: usize) {
+        self.byte_len += len;
+        self.original_len += len;
+    }
+}
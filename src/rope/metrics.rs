@@ -1,6 +1,6 @@
 use core::ops::{Add, AddAssign, Sub, SubAssign};
 
-use super::gap_buffer::GapBuffer;
+use super::gap_buffer::{Contents, GapBuffer};
 use super::gap_slice::GapSlice;
 use crate::tree::{DoubleEndedUnitMetric, Metric, SlicingMetric, UnitMetric};
 
@@ -9,6 +9,8 @@ use crate::tree::{DoubleEndedUnitMetric, Metric, SlicingMetric, UnitMetric};
 pub struct ChunkSummary {
     bytes: usize,
     line_breaks: usize,
+    #[cfg(feature = "text-stats")]
+    chars: usize,
     #[cfg(feature = "utf16-metric")]
     utf16_code_units: usize,
 }
@@ -19,18 +21,38 @@ impl From<&str> for ChunkSummary {
         Self {
             bytes: s.len(),
             line_breaks: count::line_breaks(s),
+            #[cfg(feature = "text-stats")]
+            chars: count::chars(s),
             #[cfg(feature = "utf16-metric")]
             utf16_code_units: count::utf16_code_units(s),
         }
     }
 }
 
+/// Arbitrary bytes are only measured in bytes and line feeds, so every other
+/// field of their summary is zero.
+impl From<&[u8]> for ChunkSummary {
+    #[inline]
+    fn from(bytes: &[u8]) -> Self {
+        Self {
+            bytes: bytes.len(),
+            line_breaks: bytes.iter().filter(|&&byte| byte == b'\n').count(),
+            #[cfg(feature = "text-stats")]
+            chars: 0,
+            #[cfg(feature = "utf16-metric")]
+            utf16_code_units: 0,
+        }
+    }
+}
+
 impl From<char> for ChunkSummary {
     #[inline]
     fn from(ch: char) -> Self {
         Self {
             bytes: ch.len_utf8(),
             line_breaks: (ch == '\n') as usize,
+            #[cfg(feature = "text-stats")]
+            chars: 1,
             #[cfg(feature = "utf16-metric")]
             utf16_code_units: ch.len_utf16(),
         }
@@ -43,6 +65,12 @@ impl ChunkSummary {
         self.bytes
     }
 
+    #[cfg(feature = "text-stats")]
+    #[inline]
+    pub fn chars(&self) -> usize {
+        self.chars
+    }
+
     #[inline]
     pub fn line_breaks(&self) -> usize {
         self.line_breaks
@@ -106,6 +134,10 @@ impl AddAssign<Self> for ChunkSummary {
     fn add_assign(&mut self, rhs: Self) {
         self.bytes += rhs.bytes;
         self.line_breaks += rhs.line_breaks;
+        #[cfg(feature = "text-stats")]
+        {
+            self.chars += rhs.chars;
+        }
         #[cfg(feature = "utf16-metric")]
         {
             self.utf16_code_units += rhs.utf16_code_units;
@@ -118,6 +150,10 @@ impl SubAssign<Self> for ChunkSummary {
     fn sub_assign(&mut self, rhs: Self) {
         self.bytes -= rhs.bytes;
         self.line_breaks -= rhs.line_breaks;
+        #[cfg(feature = "text-stats")]
+        {
+            self.chars -= rhs.chars;
+        }
         #[cfg(feature = "utf16-metric")]
         {
             self.utf16_code_units -= rhs.utf16_code_units;
@@ -141,19 +177,23 @@ impl SubAssign<&Self> for ChunkSummary {
 
 /// Conversion trait from the metric implement this trait to the corresponding
 /// byte offset.
-pub trait ToByteOffset: Metric<ChunkSummary> {
+pub trait ToByteOffset<T: ?Sized + Contents = str>:
+    Metric<ChunkSummary>
+{
     /// Should return the byte offset of `self` in the given string.
-    fn to_byte_offset(&self, in_str: &str) -> usize;
+    fn to_byte_offset(&self, in_str: &T) -> usize;
 }
 
 /// Trait to get the summary of a string up to a given offset.
-pub trait SummaryUpTo: Metric<ChunkSummary> {
+pub trait SummaryUpTo<T: ?Sized + Contents = str>:
+    Metric<ChunkSummary>
+{
     /// Return the summary of the given string up to `offset`, where
     ///
     /// * `str_summary` is the string's summary,
     /// * `byte_offset` is byte offset of `offset`.
     fn up_to(
-        in_str: &str,
+        in_str: &T,
         str_summary: ChunkSummary,
         offset: Self,
         byte_offset: usize,
@@ -211,9 +251,9 @@ impl From<ByteMetric> for usize {
     }
 }
 
-impl ToByteOffset for ByteMetric {
+impl<T: ?Sized + Contents> ToByteOffset<T> for ByteMetric {
     #[inline]
-    fn to_byte_offset(&self, _: &str) -> usize {
+    fn to_byte_offset(&self, _: &T) -> usize {
         self.0
     }
 }
@@ -237,6 +277,9 @@ impl SummaryUpTo for ByteMetric {
                 str_summary.line_breaks,
             ),
 
+            #[cfg(feature = "text-stats")]
+            chars: count::chars_up_to(in_str, byte_offset, str_summary.chars),
+
             #[cfg(feature = "utf16-metric")]
             utf16_code_units: count::utf16_code_units_up_to(
                 in_str,
@@ -247,6 +290,19 @@ impl SummaryUpTo for ByteMetric {
     }
 }
 
+impl SummaryUpTo<[u8]> for ByteMetric {
+    #[inline]
+    fn up_to(
+        in_bytes: &[u8],
+        _: ChunkSummary,
+        offset: Self,
+        byte_offset: usize,
+    ) -> ChunkSummary {
+        debug_assert_eq!(offset.0, byte_offset);
+        ChunkSummary::from(&in_bytes[..byte_offset])
+    }
+}
+
 impl Metric<ChunkSummary> for ByteMetric {
     #[inline]
     fn zero() -> Self {
@@ -264,16 +320,19 @@ impl Metric<ChunkSummary> for ByteMetric {
     }
 }
 
-impl<const MAX_BYTES: usize> SlicingMetric<GapBuffer<MAX_BYTES>>
+impl<const MAX_BYTES: usize, T> SlicingMetric<GapBuffer<MAX_BYTES, T>>
     for ByteMetric
+where
+    T: ?Sized + Contents,
+    Self: SummaryUpTo<T>,
 {
     #[track_caller]
     #[inline]
     fn slice_up_to<'a>(
-        chunk: GapSlice<'a>,
+        chunk: GapSlice<'a, T>,
         byte_offset: Self,
         &summary: &ChunkSummary,
-    ) -> (GapSlice<'a>, ChunkSummary)
+    ) -> (GapSlice<'a, T>, ChunkSummary)
     where
         'a: 'a,
     {
@@ -286,10 +345,10 @@ impl<const MAX_BYTES: usize> SlicingMetric<GapBuffer<MAX_BYTES>>
     #[track_caller]
     #[inline]
     fn slice_from<'a>(
-        chunk: GapSlice<'a>,
+        chunk: GapSlice<'a, T>,
         byte_offset: Self,
         &summary: &ChunkSummary,
-    ) -> (GapSlice<'a>, ChunkSummary)
+    ) -> (GapSlice<'a, T>, ChunkSummary)
     where
         'a: 'a,
     {
@@ -342,8 +401,47 @@ impl ToByteOffset for RawLineMetric {
     }
 }
 
+impl ToByteOffset<[u8]> for RawLineMetric {
+    #[inline]
+    fn to_byte_offset(&self, in_bytes: &[u8]) -> usize {
+        if self.0 == 0 {
+            return 0;
+        }
+
+        in_bytes
+            .iter()
+            .enumerate()
+            .filter(|(_, &byte)| byte == b'\n')
+            .nth(self.0 - 1)
+            .map(|(idx, _)| idx + 1)
+            .unwrap_or(in_bytes.len())
+    }
+}
+
+impl SummaryUpTo<[u8]> for RawLineMetric {
+    #[inline]
+    fn up_to(
+        _: &[u8],
+        _: ChunkSummary,
+        Self(line_offset): Self,
+        byte_offset: usize,
+    ) -> ChunkSummary {
+        ChunkSummary {
+            bytes: byte_offset,
+            line_breaks: line_offset,
+            #[cfg(feature = "text-stats")]
+            chars: 0,
+            #[cfg(feature = "utf16-metric")]
+            utf16_code_units: 0,
+        }
+    }
+}
+
 impl SummaryUpTo for RawLineMetric {
-    #[cfg_attr(not(feature = "utf16-metric"), allow(unused_variables))]
+    #[cfg_attr(
+        not(any(feature = "text-stats", feature = "utf16-metric")),
+        allow(unused_variables)
+    )]
     #[inline]
     fn up_to(
         in_str: &str,
@@ -356,6 +454,9 @@ impl SummaryUpTo for RawLineMetric {
 
             line_breaks: line_offset,
 
+            #[cfg(feature = "text-stats")]
+            chars: count::chars_up_to(in_str, byte_offset, str_summary.chars),
+
             #[cfg(feature = "utf16-metric")]
             utf16_code_units: count::utf16_code_units_up_to(
                 in_str,
@@ -383,15 +484,18 @@ impl Metric<ChunkSummary> for RawLineMetric {
     }
 }
 
-impl<const MAX_BYTES: usize> SlicingMetric<GapBuffer<MAX_BYTES>>
+impl<const MAX_BYTES: usize, T> SlicingMetric<GapBuffer<MAX_BYTES, T>>
     for RawLineMetric
+where
+    T: ?Sized + Contents,
+    Self: ToByteOffset<T> + SummaryUpTo<T>,
 {
     #[inline]
     fn slice_up_to<'a>(
-        chunk: GapSlice<'a>,
+        chunk: GapSlice<'a, T>,
         line_offset: Self,
         &summary: &ChunkSummary,
-    ) -> (GapSlice<'a>, ChunkSummary)
+    ) -> (GapSlice<'a, T>, ChunkSummary)
     where
         'a: 'a,
     {
@@ -401,10 +505,10 @@ impl<const MAX_BYTES: usize> SlicingMetric<GapBuffer<MAX_BYTES>>
 
     #[inline]
     fn slice_from<'a>(
-        chunk: GapSlice<'a>,
+        chunk: GapSlice<'a, T>,
         line_offset: Self,
         &summary: &ChunkSummary,
-    ) -> (GapSlice<'a>, ChunkSummary)
+    ) -> (GapSlice<'a, T>, ChunkSummary)
     where
         'a: 'a,
     {
@@ -643,6 +747,13 @@ mod utf16_metric {
                     str_summary.line_breaks,
                 ),
 
+                #[cfg(feature = "text-stats")]
+                chars: count::chars_up_to(
+                    in_str,
+                    byte_offset,
+                    str_summary.chars,
+                ),
+
                 utf16_code_units: utf16_code_unit_offset,
             }
         }
@@ -703,6 +814,8 @@ mod utf16_metric {
 use str_utils::*;
 
 mod str_utils {
+    #[cfg(all(not(miri), feature = "text-stats"))]
+    use str_indices::chars;
     #[cfg(not(miri))]
     use str_indices::lines_lf as lines;
     #[cfg(all(not(miri), feature = "utf16-metric"))]
@@ -712,6 +825,29 @@ mod str_utils {
         #[cfg(not(miri))]
         use super::*;
 
+        #[cfg(feature = "text-stats")]
+        #[inline]
+        pub fn chars(s: &str) -> usize {
+            #[cfg(not(miri))]
+            {
+                chars::count(s)
+            }
+            #[cfg(miri)]
+            {
+                s.chars().count()
+            }
+        }
+
+        #[cfg(feature = "text-stats")]
+        #[inline(always)]
+        pub fn chars_up_to(
+            s: &str,
+            byte_offset: usize,
+            tot_chars: usize,
+        ) -> usize {
+            metric_up_to(s, byte_offset, tot_chars, chars)
+        }
+
         #[inline]
         pub fn line_breaks(s: &str) -> usize {
             #[cfg(not(miri))]
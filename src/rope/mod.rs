@@ -1,12 +1,39 @@
+mod block;
+pub(crate) mod byte_rope;
+#[cfg(feature = "crdt")]
+pub(crate) mod crdt;
+#[cfg(feature = "encoding")]
+pub(crate) mod encoding;
+mod error;
+mod fallible;
 pub(crate) mod gap_buffer;
 pub(crate) mod gap_slice;
+mod indent;
 pub(crate) mod iterators;
+mod lossy;
 pub mod metrics;
+pub(crate) mod ot;
+// `cfg(fuzzing)` is set by `cargo fuzz`.
+#[allow(unexpected_cfgs)]
 mod rope;
 mod rope_builder;
 mod rope_slice;
+mod rope_span;
+mod selection;
+mod stats;
+#[cfg(feature = "graphemes")]
+pub(crate) mod transforms;
 mod utils;
+#[cfg(feature = "crdt")]
+mod vec_chunk;
 
+pub use block::ColumnUnit;
+pub use error::RopeError;
+pub use indent::Indent;
+pub use lossy::{InvalidSequence, InvalidUtf8};
 pub use rope::Rope;
 pub use rope_builder::RopeBuilder;
 pub use rope_slice::RopeSlice;
+pub use rope_span::RopeSpan;
+pub use selection::{Selection, SelectionSet};
+pub use stats::RopeStats;
@@ -0,0 +1,737 @@
+//! This module contains the operational transform primitives exported in the
+//! [`ot`](crate::ot) module.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{Rope, RopeBuilder};
+
+/// The unit in which the lengths of a [`Delta`]'s operations are measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    /// Lengths are measured in bytes.
+    Bytes,
+
+    /// Lengths are measured in UTF-16 code units.
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    Utf16,
+}
+
+impl Unit {
+    /// Returns the length of the string in this unit.
+    #[inline]
+    fn len_of(self, s: &str) -> usize {
+        match self {
+            Self::Bytes => s.len(),
+            #[cfg(feature = "utf16-metric")]
+            Self::Utf16 => s.encode_utf16().count(),
+        }
+    }
+
+    /// Returns the length of the `Rope` in this unit.
+    #[inline]
+    fn len_of_rope(self, rope: &Rope) -> usize {
+        match self {
+            Self::Bytes => rope.byte_len(),
+            #[cfg(feature = "utf16-metric")]
+            Self::Utf16 => rope.utf16_len(),
+        }
+    }
+
+    /// Splits the string after `len` units, or returns `None` if that doesn't
+    /// lie on a code point boundary.
+    #[inline]
+    fn split_str(self, s: &str, len: usize) -> Option<(&str, &str)> {
+        let byte_offset = match self {
+            Self::Bytes => len,
+            #[cfg(feature = "utf16-metric")]
+            Self::Utf16 => {
+                let mut units = 0;
+                let mut byte_offset = 0;
+
+                for ch in s.chars() {
+                    if units >= len {
+                        break;
+                    }
+                    units += ch.len_utf16();
+                    byte_offset += ch.len_utf8();
+                }
+
+                if units != len {
+                    return None;
+                }
+
+                byte_offset
+            },
+        };
+
+        s.is_char_boundary(byte_offset).then(|| s.split_at(byte_offset))
+    }
+
+    /// Converts an offset in this unit into a byte offset in the `Rope`, or
+    /// returns `None` if it doesn't lie on a code point boundary.
+    #[inline]
+    fn to_byte_offset(self, rope: &Rope, offset: usize) -> Option<usize> {
+        match self {
+            Self::Bytes => rope.is_char_boundary(offset).then_some(offset),
+            #[cfg(feature = "utf16-metric")]
+            Self::Utf16 => {
+                let byte_offset = rope.byte_of_utf16_code_unit(offset);
+                (rope.utf16_code_unit_of_byte(byte_offset) == offset)
+                    .then_some(byte_offset)
+            },
+        }
+    }
+}
+
+/// A single operation of a [`Delta`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    /// Keeps the given number of units of the text unchanged.
+    Retain(usize),
+
+    /// Inserts the given text.
+    Insert(String),
+
+    /// Deletes the given number of units of the text.
+    Delete(usize),
+}
+
+/// The error type returned by the methods of [`Delta`] and by
+/// [`Rope::apply_delta()`](crate::Rope::apply_delta).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DeltaError {
+    /// The length of the text a delta applies to didn't match the expected
+    /// one, e.g. the length of the `Rope` it was applied to.
+    LengthMismatch {
+        /// The expected length.
+        expected: usize,
+
+        /// The base length of the delta.
+        found: usize,
+    },
+
+    /// An offset of the delta didn't lie on a code point boundary of the text
+    /// it applies to, or an inserted string would've had to be split inside
+    /// a code point.
+    NotCodePointBoundary {
+        /// The offset, in the delta's unit, into the text the delta applies
+        /// to.
+        offset: usize,
+    },
+
+    /// Two deltas measuring their lengths in different units were combined.
+    UnitMismatch,
+}
+
+impl core::fmt::Display for DeltaError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::LengthMismatch { expected, found } => write!(
+                f,
+                "delta length mismatch: expected a base length of {expected} \
+                 but the delta's is {found}"
+            ),
+
+            Self::NotCodePointBoundary { offset } => write!(
+                f,
+                "delta offset {offset} is not on a code point boundary"
+            ),
+
+            Self::UnitMismatch => f.write_str(
+                "the deltas' lengths are measured in different units",
+            ),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+impl std::error::Error for DeltaError {}
+
+/// A sequence of [`Operation`]s describing an edit of a whole text, going
+/// from left to right.
+///
+/// Deltas are built by chaining calls to [`retain()`](Self::retain()),
+/// [`insert()`](Self::insert()) and [`delete()`](Self::delete()), which merge
+/// consecutive operations of the same kind and always put an insertion before
+/// a deletion at the same offset, so that two deltas doing the same edit
+/// compare equal.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::ot::{Delta, Unit};
+/// # use crop::Rope;
+/// #
+/// let mut r = Rope::from("Hello world");
+///
+/// let mut delta = Delta::new(Unit::Bytes);
+/// delta.retain(6).insert("big ").retain(5).insert("!");
+///
+/// assert_eq!(delta.base_len(), 11);
+/// assert_eq!(delta.target_len(), 16);
+///
+/// r.apply_delta(&delta).unwrap();
+/// assert_eq!(r, "Hello big world!");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delta {
+    ops: Vec<Operation>,
+
+    /// The length of the text the delta applies to.
+    base_len: usize,
+
+    /// The length of the text after applying the delta.
+    target_len: usize,
+
+    unit: Unit,
+}
+
+impl Delta {
+    /// Returns the length of the text the delta can be applied to, i.e. the
+    /// sum of its retains and deletions.
+    #[inline]
+    pub fn base_len(&self) -> usize {
+        self.base_len
+    }
+
+    /// Returns the delta equivalent to applying `self` and then `other`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeltaError::UnitMismatch`] if the two deltas use different
+    /// units, [`DeltaError::LengthMismatch`] if the base length of `other`
+    /// isn't the target length of `self`, and
+    /// [`DeltaError::NotCodePointBoundary`] if `other` retains or deletes
+    /// part of a code point inserted by `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ot::{Delta, Unit};
+    /// #
+    /// let mut a = Delta::new(Unit::Bytes);
+    /// a.retain(3).insert("bar");
+    ///
+    /// let mut b = Delta::new(Unit::Bytes);
+    /// b.delete(4).retain(2);
+    ///
+    /// let mut expected = Delta::new(Unit::Bytes);
+    /// expected.delete(3).insert("ar");
+    ///
+    /// assert_eq!(a.compose(&b).unwrap(), expected);
+    /// ```
+    #[inline]
+    pub fn compose(&self, other: &Self) -> Result<Self, DeltaError> {
+        if self.unit != other.unit {
+            return Err(DeltaError::UnitMismatch);
+        }
+
+        if self.target_len != other.base_len {
+            return Err(DeltaError::LengthMismatch {
+                expected: self.target_len,
+                found: other.base_len,
+            });
+        }
+
+        let unit = self.unit;
+
+        let mut composed = Self::new(unit);
+
+        // How much of the text `other` applies to has been consumed so far.
+        let mut offset = 0;
+
+        let mut ops1 = self.ops.iter().cloned();
+        let mut ops2 = other.ops.iter().cloned();
+
+        let mut op1 = ops1.next();
+        let mut op2 = ops2.next();
+
+        loop {
+            match (op1.take(), op2.take()) {
+                (None, None) => break,
+
+                (Some(Operation::Delete(len)), next2) => {
+                    composed.delete(len);
+                    op1 = ops1.next();
+                    op2 = next2;
+                },
+
+                (next1, Some(Operation::Insert(text))) => {
+                    composed.insert(text);
+                    op1 = next1;
+                    op2 = ops2.next();
+                },
+
+                (
+                    Some(Operation::Retain(len1)),
+                    Some(Operation::Retain(len2)),
+                ) => {
+                    let len = len1.min(len2);
+                    composed.retain(len);
+                    offset += len;
+                    op1 = remaining(Operation::Retain, len1, len, &mut ops1);
+                    op2 = remaining(Operation::Retain, len2, len, &mut ops2);
+                },
+
+                (
+                    Some(Operation::Retain(len1)),
+                    Some(Operation::Delete(len2)),
+                ) => {
+                    let len = len1.min(len2);
+                    composed.delete(len);
+                    offset += len;
+                    op1 = remaining(Operation::Retain, len1, len, &mut ops1);
+                    op2 = remaining(Operation::Delete, len2, len, &mut ops2);
+                },
+
+                (
+                    Some(Operation::Insert(text)),
+                    Some(Operation::Retain(len2)),
+                ) => {
+                    let len1 = unit.len_of(&text);
+                    let len = len1.min(len2);
+
+                    if len < len1 {
+                        let (head, rest) = split(unit, &text, len, offset)?;
+                        composed.insert(head);
+                        op1 = Some(Operation::Insert(rest.into()));
+                    } else {
+                        composed.insert(text);
+                        op1 = ops1.next();
+                    }
+
+                    offset += len;
+                    op2 = remaining(Operation::Retain, len2, len, &mut ops2);
+                },
+
+                (
+                    Some(Operation::Insert(text)),
+                    Some(Operation::Delete(len2)),
+                ) => {
+                    let len1 = unit.len_of(&text);
+                    let len = len1.min(len2);
+
+                    // The deleted part of the insertion simply disappears.
+                    if len < len1 {
+                        let (_, rest) = split(unit, &text, len, offset)?;
+                        op1 = Some(Operation::Insert(rest.into()));
+                    } else {
+                        op1 = ops1.next();
+                    }
+
+                    offset += len;
+                    op2 = remaining(Operation::Delete, len2, len, &mut ops2);
+                },
+
+                // The lengths have been checked to match.
+                (None, Some(_)) | (Some(_), None) => unreachable!(),
+            }
+        }
+
+        Ok(composed)
+    }
+
+    /// Deletes the next `len` units of the text.
+    #[inline]
+    pub fn delete(&mut self, len: usize) -> &mut Self {
+        if len == 0 {
+            return self;
+        }
+
+        self.base_len += len;
+
+        if let Some(Operation::Delete(last)) = self.ops.last_mut() {
+            *last += len;
+        } else {
+            self.ops.push(Operation::Delete(len));
+        }
+
+        self
+    }
+
+    /// Inserts the given text at the current offset.
+    #[inline]
+    pub fn insert<T>(&mut self, text: T) -> &mut Self
+    where
+        T: AsRef<str>,
+    {
+        let text = text.as_ref();
+
+        if text.is_empty() {
+            return self;
+        }
+
+        self.target_len += self.unit.len_of(text);
+
+        // Insertions always come before a deletion at the same offset.
+        let idx = match self.ops.last() {
+            Some(Operation::Delete(_)) => self.ops.len() - 1,
+            _ => self.ops.len(),
+        };
+
+        match idx.checked_sub(1).map(|prev| &mut self.ops[prev]) {
+            Some(Operation::Insert(prev)) => prev.push_str(text),
+            _ => self.ops.insert(idx, Operation::Insert(text.into())),
+        }
+
+        self
+    }
+
+    /// Returns the delta undoing `self`, where `base` is the text `self` was
+    /// applied to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeltaError::LengthMismatch`] if the base length of the delta
+    /// isn't the length of `base`, and [`DeltaError::NotCodePointBoundary`] if
+    /// any of its offsets doesn't lie on a code point boundary of `base`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ot::{Delta, Unit};
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello world");
+    ///
+    /// let mut delta = Delta::new(Unit::Bytes);
+    /// delta.retain(5).delete(6).insert("!");
+    ///
+    /// let inverse = delta.invert(&r).unwrap();
+    ///
+    /// r.apply_delta(&delta).unwrap();
+    /// assert_eq!(r, "Hello!");
+    ///
+    /// r.apply_delta(&inverse).unwrap();
+    /// assert_eq!(r, "Hello world");
+    /// ```
+    #[inline]
+    pub fn invert(&self, base: &Rope) -> Result<Self, DeltaError> {
+        self.check_base(base)?;
+
+        let mut inverse = Self::new(self.unit);
+
+        let mut offset = 0;
+
+        for op in &self.ops {
+            match op {
+                Operation::Retain(len) => {
+                    inverse.retain(*len);
+                    offset += len;
+                },
+
+                Operation::Insert(text) => {
+                    inverse.delete(self.unit.len_of(text));
+                },
+
+                Operation::Delete(len) => {
+                    let start = self.byte_offset(base, offset)?;
+                    let end = self.byte_offset(base, offset + len)?;
+
+                    for chunk in base.byte_slice(start..end).chunks() {
+                        inverse.insert(chunk);
+                    }
+
+                    offset += len;
+                },
+            }
+        }
+
+        Ok(inverse)
+    }
+
+    /// Returns whether applying the delta doesn't change the text, i.e.
+    /// whether it only retains it.
+    #[inline]
+    pub fn is_noop(&self) -> bool {
+        self.ops.iter().all(|op| matches!(op, Operation::Retain(_)))
+    }
+
+    /// Returns an empty delta measuring its lengths in the given unit.
+    #[inline]
+    pub fn new(unit: Unit) -> Self {
+        Self { ops: Vec::new(), base_len: 0, target_len: 0, unit }
+    }
+
+    /// Returns the operations of the delta.
+    #[inline]
+    pub fn ops(&self) -> &[Operation] {
+        &self.ops
+    }
+
+    /// Keeps the next `len` units of the text unchanged.
+    #[inline]
+    pub fn retain(&mut self, len: usize) -> &mut Self {
+        if len == 0 {
+            return self;
+        }
+
+        self.base_len += len;
+        self.target_len += len;
+
+        if let Some(Operation::Retain(last)) = self.ops.last_mut() {
+            *last += len;
+        } else {
+            self.ops.push(Operation::Retain(len));
+        }
+
+        self
+    }
+
+    /// Returns the length of the text after applying the delta, i.e. the sum
+    /// of its retains and insertions.
+    #[inline]
+    pub fn target_len(&self) -> usize {
+        self.target_len
+    }
+
+    /// Transforms two concurrent deltas applying to the same text, returning
+    /// a `(self', other')` pair such that applying `self` and then `other'`
+    /// gives the same text as applying `other` and then `self'`.
+    ///
+    /// If both deltas insert text at the same offset the text inserted by
+    /// `self` comes first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeltaError::UnitMismatch`] if the two deltas use different
+    /// units, and [`DeltaError::LengthMismatch`] if their base lengths
+    /// differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ot::{Delta, Unit};
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("abc");
+    ///
+    /// let mut a = Delta::new(Unit::Bytes);
+    /// a.retain(1).insert("X").retain(2);
+    ///
+    /// let mut b = Delta::new(Unit::Bytes);
+    /// b.delete(1).retain(1).insert("Y").retain(1);
+    ///
+    /// let (a_prime, b_prime) = a.transform(&b).unwrap();
+    ///
+    /// let mut r1 = r.clone();
+    /// r1.apply_delta(&a).unwrap();
+    /// r1.apply_delta(&b_prime).unwrap();
+    ///
+    /// let mut r2 = r.clone();
+    /// r2.apply_delta(&b).unwrap();
+    /// r2.apply_delta(&a_prime).unwrap();
+    ///
+    /// assert_eq!(r1, "XbYc");
+    /// assert_eq!(r1, r2);
+    /// ```
+    #[inline]
+    pub fn transform(&self, other: &Self) -> Result<(Self, Self), DeltaError> {
+        if self.unit != other.unit {
+            return Err(DeltaError::UnitMismatch);
+        }
+
+        if self.base_len != other.base_len {
+            return Err(DeltaError::LengthMismatch {
+                expected: self.base_len,
+                found: other.base_len,
+            });
+        }
+
+        let unit = self.unit;
+
+        let mut self_prime = Self::new(unit);
+        let mut other_prime = Self::new(unit);
+
+        let mut ops1 = self.ops.iter().cloned();
+        let mut ops2 = other.ops.iter().cloned();
+
+        let mut op1 = ops1.next();
+        let mut op2 = ops2.next();
+
+        loop {
+            match (op1.take(), op2.take()) {
+                (None, None) => break,
+
+                (Some(Operation::Insert(text)), next2) => {
+                    other_prime.retain(unit.len_of(&text));
+                    self_prime.insert(text);
+                    op1 = ops1.next();
+                    op2 = next2;
+                },
+
+                (next1, Some(Operation::Insert(text))) => {
+                    self_prime.retain(unit.len_of(&text));
+                    other_prime.insert(text);
+                    op1 = next1;
+                    op2 = ops2.next();
+                },
+
+                (
+                    Some(Operation::Retain(len1)),
+                    Some(Operation::Retain(len2)),
+                ) => {
+                    let len = len1.min(len2);
+                    self_prime.retain(len);
+                    other_prime.retain(len);
+                    op1 = remaining(Operation::Retain, len1, len, &mut ops1);
+                    op2 = remaining(Operation::Retain, len2, len, &mut ops2);
+                },
+
+                // Both deltas delete the same text.
+                (
+                    Some(Operation::Delete(len1)),
+                    Some(Operation::Delete(len2)),
+                ) => {
+                    let len = len1.min(len2);
+                    op1 = remaining(Operation::Delete, len1, len, &mut ops1);
+                    op2 = remaining(Operation::Delete, len2, len, &mut ops2);
+                },
+
+                (
+                    Some(Operation::Delete(len1)),
+                    Some(Operation::Retain(len2)),
+                ) => {
+                    let len = len1.min(len2);
+                    self_prime.delete(len);
+                    op1 = remaining(Operation::Delete, len1, len, &mut ops1);
+                    op2 = remaining(Operation::Retain, len2, len, &mut ops2);
+                },
+
+                (
+                    Some(Operation::Retain(len1)),
+                    Some(Operation::Delete(len2)),
+                ) => {
+                    let len = len1.min(len2);
+                    other_prime.delete(len);
+                    op1 = remaining(Operation::Retain, len1, len, &mut ops1);
+                    op2 = remaining(Operation::Delete, len2, len, &mut ops2);
+                },
+
+                // The lengths have been checked to match.
+                (None, Some(_)) | (Some(_), None) => unreachable!(),
+            }
+        }
+
+        Ok((self_prime, other_prime))
+    }
+
+    /// Returns the unit the lengths of the delta are measured in.
+    #[inline]
+    pub fn unit(&self) -> Unit {
+        self.unit
+    }
+
+    /// Converts an offset of the delta into a byte offset in the `Rope`.
+    #[inline]
+    fn byte_offset(
+        &self,
+        rope: &Rope,
+        offset: usize,
+    ) -> Result<usize, DeltaError> {
+        self.unit
+            .to_byte_offset(rope, offset)
+            .ok_or(DeltaError::NotCodePointBoundary { offset })
+    }
+
+    /// Checks that the delta can be applied to the `Rope`.
+    #[inline]
+    fn check_base(&self, rope: &Rope) -> Result<(), DeltaError> {
+        let expected = self.unit.len_of_rope(rope);
+
+        if self.base_len != expected {
+            return Err(DeltaError::LengthMismatch {
+                expected,
+                found: self.base_len,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Applies the delta to the `Rope`, leaving it untouched if the delta is
+/// invalid.
+#[inline]
+pub(super) fn apply(rope: &mut Rope, delta: &Delta) -> Result<(), DeltaError> {
+    delta.check_base(rope)?;
+
+    if delta.is_noop() {
+        return Ok(());
+    }
+
+    // The edited text is built in a single pass over the operations and
+    // only replaces the `Rope` once all of them have been converted, so that
+    // it's left untouched if any of the offsets is invalid.
+    let mut builder = RopeBuilder::new();
+
+    let mut offset = 0;
+
+    let mut byte_offset = 0;
+
+    for op in &delta.ops {
+        match op {
+            Operation::Retain(len) => {
+                offset += len;
+                let end = delta.byte_offset(rope, offset)?;
+
+                for chunk in rope.byte_slice(byte_offset..end).chunks() {
+                    builder.append(chunk);
+                }
+
+                byte_offset = end;
+            },
+
+            Operation::Insert(text) => {
+                builder.append(text);
+            },
+
+            Operation::Delete(len) => {
+                offset += len;
+                byte_offset = delta.byte_offset(rope, offset)?;
+            },
+        }
+    }
+
+    *rope = builder.build();
+
+    Ok(())
+}
+
+/// Returns what's left of an operation of length `len` after consuming
+/// `consumed` units of it, or the next operation if nothing is left.
+#[inline]
+fn remaining<I>(
+    op: fn(usize) -> Operation,
+    len: usize,
+    consumed: usize,
+    ops: &mut I,
+) -> Option<Operation>
+where
+    I: Iterator<Item = Operation>,
+{
+    if consumed < len {
+        Some(op(len - consumed))
+    } else {
+        ops.next()
+    }
+}
+
+/// Splits a string inserted by the first delta of a composition after `len`
+/// units, where `offset` is where the string starts in the text the second
+/// delta applies to.
+#[inline]
+fn split(
+    unit: Unit,
+    text: &str,
+    len: usize,
+    offset: usize,
+) -> Result<(&str, &str), DeltaError> {
+    unit.split_str(text, len)
+        .ok_or(DeltaError::NotCodePointBoundary { offset: offset + len })
+}
@@ -1,10 +1,19 @@
+use alloc::string::String;
 use core::ops::RangeBounds;
 
 use super::gap_buffer::GapBuffer;
-use super::iterators::{Bytes, Chars, Chunks, Lines, RawLines};
-use super::metrics::{ByteMetric, RawLineMetric};
+use super::iterators::{
+    BlockSlices,
+    Bytes,
+    Chars,
+    Chunks,
+    IndexedLines,
+    Lines,
+    RawLines,
+};
+use super::metrics::{ByteMetric, ChunkSummary, RawLineMetric};
 use super::utils::{panic_messages as panic, *};
-use super::RopeSlice;
+use super::{RopeSlice, RopeStats};
 use crate::range_bounds_to_start_end;
 use crate::tree::Tree;
 
@@ -27,6 +36,11 @@ const CHUNK_MAX_BYTES: usize = 2048;
 pub(super) type RopeChunk = GapBuffer<CHUNK_MAX_BYTES>;
 
 /// A UTF-8 text rope.
+///
+/// `Rope`s hash and compare exactly like the `str` they contain, no matter
+/// how their text is split into chunks. Since the text isn't stored in a
+/// contiguous buffer they can't implement `Borrow<str>`, so a
+/// `HashMap<Rope, _>` can't be queried with a `&str` directly.
 #[derive(Clone, Default)]
 pub struct Rope {
     pub(super) tree: Tree<{ Self::arity() }, RopeChunk>,
@@ -34,6 +48,69 @@ pub struct Rope {
 }
 
 impl Rope {
+    /// Appends another `Rope` to the end of this one.
+    ///
+    /// This runs in `O(log n)` time, and the nodes of `other` end up being
+    /// reused by this `Rope` instead of being copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello ");
+    ///
+    /// r.append(Rope::from("world!"));
+    /// assert_eq!(r, "Hello world!");
+    /// ```
+    #[inline]
+    pub fn append(&mut self, other: Self) {
+        if other.is_empty() {
+            return;
+        }
+
+        self.has_trailing_newline = other.has_trailing_newline;
+        self.tree.append(other.tree);
+    }
+
+    /// Applies the [`Delta`](crate::ot::Delta) to the `Rope` in place.
+    ///
+    /// The edited text is built in a single left-to-right pass over the
+    /// operations, copying the text the delta retains or inserts, so this
+    /// takes time proportional to the length of the edited `Rope` no matter
+    /// how many operations the delta has.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeltaError::LengthMismatch`](crate::ot::DeltaError) if the
+    /// base length of the delta isn't the length of the `Rope` in the delta's
+    /// unit, and
+    /// [`DeltaError::NotCodePointBoundary`](crate::ot::DeltaError) if any of
+    /// its offsets doesn't lie on a code point boundary. The `Rope` is left
+    /// untouched if an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ot::{Delta, Unit};
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello Earth 🌎!");
+    ///
+    /// let mut delta = Delta::new(Unit::Bytes);
+    /// delta.retain(6).delete(10).insert("Saturn 🪐").retain(1);
+    ///
+    /// r.apply_delta(&delta).unwrap();
+    /// assert_eq!(r, "Hello Saturn 🪐!");
+    /// ```
+    #[inline]
+    pub fn apply_delta(
+        &mut self,
+        delta: &crate::ot::Delta,
+    ) -> Result<(), crate::ot::DeltaError> {
+        super::ot::apply(self, delta)
+    }
+
     #[doc(hidden)]
     pub fn assert_invariants(&self) {
         self.tree.assert_invariants();
@@ -64,6 +141,53 @@ impl Rope {
         }
     }
 
+    /// Returns an iterator over the rectangular block of text spanning the
+    /// given line and column ranges, yielding the part of each line (not
+    /// including its line terminator) that lies inside the column range.
+    ///
+    /// A character is part of the block if the column it starts at is inside
+    /// the column range, so lines shorter than the start of the column range
+    /// yield an empty slice. Zero-width characters (e.g. combining marks)
+    /// always stay with the character they follow. Columns are measured in the
+    /// given [`ColumnUnit`](crate::ColumnUnit).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of either range is greater than its end or if the
+    /// end of the line range is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{ColumnUnit, Rope};
+    /// #
+    /// let r = Rope::from("foo = 1;\nbarbaz = 2;\nq\n");
+    ///
+    /// let mut block = r.block_slice(.., 3..7, ColumnUnit::Chars);
+    ///
+    /// assert_eq!(block.next().unwrap(), " = 1");
+    /// assert_eq!(block.next().unwrap(), "baz ");
+    /// assert_eq!(block.next().unwrap(), "");
+    /// assert_eq!(block.next(), None);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn block_slice<R, C>(
+        &self,
+        line_range: R,
+        column_range: C,
+        unit: super::ColumnUnit,
+    ) -> BlockSlices<'_>
+    where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+    {
+        let (start, end) = column_range_to_start_end(column_range);
+        let lines = self.line_slice(line_range).lines();
+        BlockSlices::new(lines, start, end, unit)
+    }
+
     /// Returns the byte at `byte_index`.
     ///
     /// # Panics
@@ -147,6 +271,33 @@ impl Rope {
         byte_offset
     }
 
+    /// Returns the byte offsets of the start and end of the given line range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the line range is greater than the end or if the
+    /// end is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    #[track_caller]
+    #[inline]
+    fn byte_range_of_lines<R>(&self, line_range: R) -> (usize, usize)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(line_range, 0, self.line_len());
+
+        if start > end {
+            panic::line_start_after_end(start, end);
+        }
+
+        if end > self.line_len() {
+            panic::line_offset_out_of_bounds(end, self.line_len());
+        }
+
+        (self.byte_of_line(start), self.byte_of_line(end))
+    }
+
     /// Returns the byte offset corresponding to the given UTF-16 code unit
     /// offset.
     ///
@@ -243,6 +394,86 @@ impl Rope {
         Bytes::from(self)
     }
 
+    /// Returns the smallest byte offset greater than or equal to `byte_offset`
+    /// that lies on a [`char`] boundary.
+    ///
+    /// Offsets greater than [`byte_len()`](Self::byte_len()) are clamped to
+    /// the byte length of the `Rope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Löwe 老虎 Léopard");
+    ///
+    /// assert_eq!(r.ceil_char_boundary(6), 6);
+    /// assert_eq!(r.ceil_char_boundary(7), 9);
+    /// assert_eq!(r.ceil_char_boundary(100), r.byte_len());
+    /// ```
+    #[inline]
+    pub fn ceil_char_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset >= self.byte_len() {
+            return self.byte_len();
+        }
+
+        let (chunk, ByteMetric(chunk_byte_offset)) =
+            self.tree.leaf_at_measure(ByteMetric(byte_offset));
+
+        chunk_byte_offset
+            + chunk.ceil_char_boundary(byte_offset - chunk_byte_offset)
+    }
+
+    /// Returns the smallest byte offset greater than or equal to `byte_offset`
+    /// that lies on a grapheme cluster boundary.
+    ///
+    /// Offsets greater than [`byte_len()`](Self::byte_len()) are clamped to
+    /// the byte length of the `Rope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("aargh!\r\n🐻‍❄️");
+    ///
+    /// assert_eq!(r.ceil_grapheme_boundary(7), 8); // between '\r' and '\n'
+    /// assert_eq!(r.ceil_grapheme_boundary(12), 21); // inside '🐻‍❄️'
+    /// assert_eq!(r.ceil_grapheme_boundary(21), 21);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn ceil_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        let byte_offset = self.ceil_char_boundary(byte_offset);
+
+        if is_grapheme_boundary(self.byte_slice(..), byte_offset) {
+            byte_offset
+        } else {
+            next_grapheme_boundary(self.byte_slice(..), byte_offset)
+        }
+    }
+
+    /// Returns the number of `char`s in the `Rope`.
+    ///
+    /// This is tracked together with the other metrics of the `Rope`, so
+    /// it runs in logarithmic time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a🦀b\r\n");
+    /// assert_eq!(r.char_count(), 5);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "text-stats")))]
+    #[cfg(feature = "text-stats")]
+    #[inline]
+    pub fn char_count(&self) -> usize {
+        self.tree.summary().chars()
+    }
+
     /// Returns an iterator over the [`char`]s of this `Rope`.
     ///
     /// # Examples
@@ -271,144 +502,309 @@ impl Rope {
         Chunks::from(self)
     }
 
-    /// Deletes the contents of the `Rope` within the specified byte range,
-    /// where the start and end of the range are interpreted as offsets.
+    /// Re-packs the text of the `Rope` into as few chunks as possible and
+    /// rebuilds the tree on top of them.
     ///
-    /// # Panics
+    /// After a long series of edits a `Rope` can end up with many partially
+    /// filled chunks, which waste memory and make the tree deeper than it
+    /// needs to be. Compacting it fills every chunk to capacity before moving
+    /// on to the next one. Parts of the `Rope` that are already that dense
+    /// are left as they are, so they keep being shared with any clones of the
+    /// `Rope`.
     ///
-    /// Panics if the start or the end of the byte range don't lie on a code
-    /// point boundary, if the start is greater than the end or if the end is
-    /// out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    /// This runs in `O(n)` time. See
+    /// [`compact_incremental()`](Self::compact_incremental()) for a version
+    /// that can be spread over multiple calls.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let mut r = Rope::from("Hello Earth 🌎!");
+    /// let mut r = Rope::from("a".repeat(100_000));
     ///
-    /// r.delete(5..16);
-    /// assert_eq!(r, "Hello!");
+    /// for offset in (0..r.byte_len()).step_by(1000).rev() {
+    ///     r.delete(offset..offset + 900);
+    /// }
+    ///
+    /// r.compact();
+    ///
+    /// // The 10,000 bytes that are left are packed in as few chunks as when
+    /// // building a new `Rope` from them.
+    /// assert_eq!(r, "a".repeat(10_000));
+    /// assert_eq!(
+    ///     r.stats().leaf_count,
+    ///     Rope::from("a".repeat(10_000)).stats().leaf_count
+    /// );
     /// ```
-    #[track_caller]
     #[inline]
-    pub fn delete<R>(&mut self, byte_range: R)
-    where
-        R: RangeBounds<usize>,
-    {
-        self.replace(byte_range, "");
+    pub fn compact(&mut self) {
+        self.tree.compact(is_dense);
     }
 
-    pub(super) const fn arity() -> usize {
-        ARITY
+    /// Compacts the chunks of the `Rope` starting from the one containing the
+    /// given byte offset, processing at most `max_chunks` of them.
+    ///
+    /// Returns the byte offset to pass to the next call to continue from
+    /// where this call left off, or `None` if the end of the `Rope` was
+    /// reached. Since compacting doesn't change the text of the `Rope`, the
+    /// offset stays valid as long as the `Rope` isn't edited in between
+    /// calls. If it is, compacting will still work correctly, it may just
+    /// skip or revisit a few chunks.
+    ///
+    /// Each call runs in `O(max_chunks + log n)` time, which makes it
+    /// suitable to compact large `Rope`s in the background, e.g. a bit at a
+    /// time in an editor's idle loop. See [`compact()`](Self::compact()) for
+    /// more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("a".repeat(100_000));
+    ///
+    /// for offset in (0..r.byte_len()).step_by(1000).rev() {
+    ///     r.delete(offset..offset + 900);
+    /// }
+    ///
+    /// let mut offset = 0;
+    ///
+    /// while let Some(next) = r.compact_incremental(offset, 8) {
+    ///     offset = next;
+    /// }
+    ///
+    /// assert_eq!(r, "a".repeat(10_000));
+    /// assert!(r.stats().fill_ratio >= 0.5);
+    /// ```
+    #[inline]
+    pub fn compact_incremental(
+        &mut self,
+        byte_offset: usize,
+        max_chunks: usize,
+    ) -> Option<usize> {
+        let len = self.byte_len();
+
+        if byte_offset >= len {
+            return None;
+        }
+
+        // Move the start back to the beginning of its chunk.
+        let (chunk, chunk_offset) =
+            self.tree.leaf_at_measure(ByteMetric(byte_offset));
+
+        let start = if chunk_offset.0 + chunk.len() == byte_offset {
+            byte_offset
+        } else {
+            chunk_offset.0
+        };
+
+        let end = start
+            + self
+                .tree
+                .slice(ByteMetric(start)..ByteMetric(len))
+                .leaves()
+                .filter(|chunk| chunk.len() > 0)
+                .take(max_chunks.max(1))
+                .map(|chunk| chunk.len())
+                .sum::<usize>();
+
+        if start == 0 && end == len {
+            self.compact();
+            return None;
+        }
+
+        let mut middle =
+            Tree::from(self.tree.slice(ByteMetric(start)..ByteMetric(end)));
+
+        middle.compact(is_dense);
+
+        let right =
+            Tree::from(self.tree.slice(ByteMetric(end)..ByteMetric(len)));
+
+        let mut tree = if start > 0 {
+            let mut left =
+                Tree::from(self.tree.slice(ByteMetric(0)..ByteMetric(start)));
+            left.append(middle);
+            left
+        } else {
+            middle
+        };
+
+        tree.append(right);
+
+        self.tree = tree;
+
+        (end < len).then_some(end)
     }
 
-    /// Returns an iterator over the extended grapheme clusters of this
-    /// `Rope`.
+    /// Returns a 64-bit hash of the text of the `Rope`.
+    ///
+    /// Unlike the [`Hash`](core::hash::Hash) implementation, which always
+    /// feeds the whole text to the hasher, this caches the hash of every
+    /// internal node of the `Rope`. The first call runs in `O(n)` time, but
+    /// after that only the nodes modified by an edit have to be re-hashed, so
+    /// calling this after each edit runs in `O(log n)` time.
+    ///
+    /// The hash only depends on the text and not on how it's split into
+    /// chunks, so two `Rope`s or [`RopeSlice`]s with the same text always
+    /// have the same content hash. It's not cryptographically secure and it's
+    /// not guaranteed to be stable across different versions of crop.
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("arg!\r\n🐻‍❄️");
+    /// let mut r = Rope::from("Hello, world!");
     ///
-    /// let mut graphemes = r.graphemes();
+    /// let before = r.content_hash();
     ///
-    /// assert_eq!(Some("a"), graphemes.next().as_deref());
-    /// assert_eq!(Some("r"), graphemes.next().as_deref());
-    /// assert_eq!(Some("g"), graphemes.next().as_deref());
-    /// assert_eq!(Some("!"), graphemes.next().as_deref());
-    /// assert_eq!(Some("\r\n"), graphemes.next().as_deref());
-    /// assert_eq!(Some("🐻‍❄️"), graphemes.next().as_deref());
-    /// assert_eq!(None, graphemes.next());
+    /// r.replace(7..12, "Earth");
+    ///
+    /// assert_ne!(r.content_hash(), before);
+    ///
+    /// let earth = Rope::from("Earth");
+    /// assert_eq!(r.byte_slice(7..12).content_hash(), earth.content_hash());
     /// ```
-    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
-    #[cfg(feature = "graphemes")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "content-hash")))]
+    #[cfg(feature = "content-hash")]
     #[inline]
-    pub fn graphemes(&self) -> crate::iter::Graphemes<'_> {
-        crate::iter::Graphemes::from(self)
+    pub fn content_hash(&self) -> u64 {
+        self.tree.rolling_hash().finish()
     }
 
-    /// Inserts `text` in the `Rope` at the given byte offset.
+    /// Deletes the contents of the `Rope` within the specified byte range,
+    /// where the start and end of the range are interpreted as offsets.
     ///
     /// # Panics
     ///
-    /// Panics if the byte offset doesn't lie on a code point boundary or if
-    /// it's out of bounds (i.e. greater than
-    /// [`byte_len()`](Self::byte_len())).
+    /// Panics if the start or the end of the byte range don't lie on a code
+    /// point boundary, if the start is greater than the end or if the end is
+    /// out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let mut r = Rope::from("Hello Earth!");
+    /// let mut r = Rope::from("Hello Earth 🌎!");
     ///
-    /// r.insert(11, " 🌎");
-    /// assert_eq!(r, "Hello Earth 🌎!");
+    /// r.delete(5..16);
+    /// assert_eq!(r, "Hello!");
     /// ```
     #[track_caller]
     #[inline]
-    pub fn insert<T>(&mut self, byte_offset: usize, text: T)
+    pub fn delete<R>(&mut self, byte_range: R)
     where
-        T: AsRef<str>,
+        R: RangeBounds<usize>,
     {
-        self.replace(byte_offset..byte_offset, text)
+        self.replace(byte_range, "");
     }
 
-    /// Returns `true` if the given byte offset lies on a [`char`] boundary.
+    /// Removes up to `width` columns of leading whitespace from every line in
+    /// the given line range, where spaces are one column wide and tabs
+    /// advance to the next multiple of `tab_width`.
+    ///
+    /// A tab that would only be partially removed is kept, as is everything
+    /// after the first character that isn't a space or a tab. The new text of
+    /// the whole range is built in a single pass and then spliced into the
+    /// `Rope` with a single edit.
     ///
     /// # Panics
     ///
-    /// Panics if the byte offset is out of bounds (i.e. greater than
-    /// [`byte_len()`](Self::byte_len())).
+    /// Panics if `tab_width` is zero, if the start of the line range is
+    /// greater than the end or if the end is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("Löwe 老虎 Léopard");
+    /// let mut r = Rope::from("fn main() {\n        foo();\n\tbar();\n  }\n");
     ///
-    /// assert!(r.is_char_boundary(0));
-    /// assert!(r.is_char_boundary(r.byte_len()));
-    /// assert!(r.is_char_boundary(6)); // between ' ' and '老'
-    /// assert!(!r.is_char_boundary(2)); // between the 1st and 2nd byte of 'ö'
+    /// r.dedent_lines(1..4, 4, 4);
+    /// assert_eq!(r, "fn main() {\n    foo();\nbar();\n}\n");
     /// ```
     #[track_caller]
     #[inline]
-    pub fn is_char_boundary(&self, byte_offset: usize) -> bool {
-        if byte_offset > self.byte_len() {
-            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
-        }
+    pub fn dedent_lines<R>(
+        &mut self,
+        line_range: R,
+        width: usize,
+        tab_width: usize,
+    ) where
+        R: RangeBounds<usize>,
+    {
+        assert!(tab_width > 0, "the tab width must be greater than zero");
 
-        let (chunk, ByteMetric(chunk_byte_offset)) =
-            self.tree.leaf_at_measure(ByteMetric(byte_offset));
+        let (start, end) = self.byte_range_of_lines(line_range);
 
-        chunk.is_char_boundary(byte_offset - chunk_byte_offset)
+        let mut builder = super::RopeBuilder::new();
+
+        let mut has_changed = false;
+
+        for line in self.byte_slice(start..end).raw_lines() {
+            let dedent = super::indent::dedent_len(line, width, tab_width);
+
+            has_changed |= dedent > 0;
+
+            for chunk in line.byte_slice(dedent..).chunks() {
+                builder.append(chunk);
+            }
+        }
+
+        if has_changed {
+            let dedented = builder.build();
+            self.replace_with_rope(start..end, dedented.byte_slice(..));
+        }
     }
 
-    /// Returns `true` if the `Rope`'s byte length is zero.
+    /// Guesses whether the lines of the `Rope` are indented with tabs or
+    /// spaces, and in the latter case how many spaces make up one level of
+    /// indentation.
+    ///
+    /// If more lines start with a tab than with a space this returns
+    /// [`Indent::Tabs`](crate::Indent::Tabs). Otherwise the width is the most
+    /// common difference in the number of leading spaces between two
+    /// consecutive non-blank lines, up to 8. Returns `None` if none of the
+    /// lines are indented.
+    ///
+    /// Note that this looks at every line of the `Rope`, so it runs in linear
+    /// time.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use crop::Rope;
+    /// # use crop::{Indent, Rope};
     /// #
-    /// let mut r = Rope::new();
-    /// assert!(r.is_empty());
+    /// let r = Rope::from("if foo {\n  if bar {\n    baz();\n  }\n}\n");
+    /// assert_eq!(r.detect_indent(), Some(Indent::Spaces(2)));
     ///
-    /// r.insert(0, "hey");
-    /// assert!(!r.is_empty());
+    /// let r = Rope::from("if foo {\n\tbar();\n}\n");
+    /// assert_eq!(r.detect_indent(), Some(Indent::Tabs));
+    ///
+    /// let r = Rope::from("foo\nbar\n");
+    /// assert_eq!(r.detect_indent(), None);
     /// ```
     #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.byte_len() == 0
+    pub fn detect_indent(&self) -> Option<super::Indent> {
+        super::indent::detect(self.lines())
     }
 
-    /// Returns `true` if the given byte offset lies on a grapheme cluster
-    /// boundary.
+    /// Returns the byte offsets of the innermost pair of brackets enclosing
+    /// `byte_offset`, i.e. the last unmatched opening bracket before it and
+    /// the first unmatched closing bracket at or after it.
+    ///
+    /// Round (`()`), square (`[]`) and curly (`{}`) brackets all count
+    /// towards the same nesting depth. If the two unmatched brackets aren't of
+    /// the same kind this returns `None`.
+    ///
+    /// When the `bracket-depth` feature is enabled every internal node of the
+    /// `Rope` caches the nesting depth of its subtree, so this runs in
+    /// logarithmic time after the first call, and edits only invalidate the
+    /// caches of the nodes they touch.
     ///
     /// # Panics
     ///
@@ -420,9 +816,521 @@ impl Rope {
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("aargh!\r\n🐻‍❄️");
+    /// let r = Rope::from("f(a[b], {c})");
     ///
-    /// assert!(r.is_grapheme_boundary(0));
+    /// assert_eq!(r.enclosing_brackets(4), Some((3, 5)));
+    /// assert_eq!(r.enclosing_brackets(7), Some((1, 11)));
+    /// assert_eq!(r.enclosing_brackets(3), Some((1, 11)));
+    /// assert_eq!(r.enclosing_brackets(0), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "bracket-depth")))]
+    #[cfg(feature = "bracket-depth")]
+    #[track_caller]
+    #[inline]
+    pub fn enclosing_brackets(
+        &self,
+        byte_offset: usize,
+    ) -> Option<(usize, usize)> {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let ByteMetric(open) =
+            self.tree.find_unmatched_open(ByteMetric(byte_offset))?;
+
+        let ByteMetric(close) =
+            self.tree.find_unmatched_close(ByteMetric(byte_offset))?;
+
+        (matching_bracket_byte(self.byte(open)) == Some(self.byte(close)))
+            .then_some((open, close))
+    }
+
+    /// Returns `true` if the `Rope` ends with the given string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar baz");
+    ///
+    /// assert!(r.ends_with("bar baz"));
+    /// assert!(r.ends_with(""));
+    /// assert!(!r.ends_with("bar"));
+    /// ```
+    #[inline]
+    pub fn ends_with(&self, suffix: &str) -> bool {
+        self.byte_len() >= suffix.len()
+            && chunks_end_with(self.chunks(), suffix.as_bytes())
+    }
+
+    /// Returns `true` if the `Rope` and the given string are equal when
+    /// ignoring the case of ASCII letters, like
+    /// [`str::eq_ignore_ascii_case()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello WORLD");
+    ///
+    /// assert!(r.eq_ignore_ascii_case("hello world"));
+    /// assert!(!r.eq_ignore_ascii_case("hello word"));
+    /// ```
+    #[inline]
+    pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        self.byte_len() == other.len()
+            && chunks_eq_str_ignore_ascii_case(self.chunks(), other)
+    }
+
+    /// Returns `true` if the `Rope` and the given string are canonically
+    /// equivalent, i.e. if they're equal after being normalized.
+    ///
+    /// Two strings have the same NFC normalization if and only if they have
+    /// the same NFD normalization, so the result doesn't depend on which of
+    /// the two forms is used. Characters are normalized one chunk at a time
+    /// as they're compared, without collecting them into a `String`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("cre\u{300}me bru\u{302}le\u{301}e");
+    ///
+    /// assert!(r.eq_normalized("cr\u{e8}me br\u{fb}l\u{e9}e"));
+    /// assert!(!r.eq_normalized("creme brulee"));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "normalization")))]
+    #[cfg(feature = "normalization")]
+    #[inline]
+    pub fn eq_normalized(&self, other: &str) -> bool {
+        self.byte_slice(..).eq_normalized(other)
+    }
+
+    /// Returns the largest byte offset less than or equal to `byte_offset`
+    /// that lies on a [`char`] boundary.
+    ///
+    /// Offsets greater than [`byte_len()`](Self::byte_len()) are clamped to
+    /// the byte length of the `Rope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Löwe 老虎 Léopard");
+    ///
+    /// assert_eq!(r.floor_char_boundary(6), 6);
+    /// assert_eq!(r.floor_char_boundary(7), 6);
+    /// assert_eq!(r.floor_char_boundary(100), r.byte_len());
+    /// ```
+    #[inline]
+    pub fn floor_char_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset >= self.byte_len() {
+            return self.byte_len();
+        }
+
+        let (chunk, ByteMetric(chunk_byte_offset)) =
+            self.tree.leaf_at_measure(ByteMetric(byte_offset));
+
+        chunk_byte_offset
+            + chunk.floor_char_boundary(byte_offset - chunk_byte_offset)
+    }
+
+    /// Returns the largest byte offset less than or equal to `byte_offset`
+    /// that lies on a grapheme cluster boundary.
+    ///
+    /// Offsets greater than [`byte_len()`](Self::byte_len()) are clamped to
+    /// the byte length of the `Rope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("aargh!\r\n🐻‍❄️");
+    ///
+    /// assert_eq!(r.floor_grapheme_boundary(7), 6); // between '\r' and '\n'
+    /// assert_eq!(r.floor_grapheme_boundary(12), 8); // inside '🐻‍❄️'
+    /// assert_eq!(r.floor_grapheme_boundary(100), r.byte_len());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn floor_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        let byte_offset = self.floor_char_boundary(byte_offset);
+
+        if is_grapheme_boundary(self.byte_slice(..), byte_offset) {
+            byte_offset
+        } else {
+            prev_grapheme_boundary(self.byte_slice(..), byte_offset)
+        }
+    }
+
+    pub(super) const fn arity() -> usize {
+        ARITY
+    }
+
+    /// Creates a new `Rope` by decoding the contents of the reader from the
+    /// given encoding, returning it together with the encoding it was decoded
+    /// from.
+    ///
+    /// If the contents start with a UTF-8, UTF-16LE or UTF-16BE byte order
+    /// mark, that encoding is used instead and the BOM is stripped from the
+    /// text. The returned [`TextEncoding`](crate::encoding::TextEncoding)
+    /// remembers whether there was one, so that passing it to
+    /// [`write_encoded()`](Self::write_encoded()) preserves it.
+    ///
+    /// The contents are decoded in fixed-size blocks which are appended to a
+    /// [`RopeBuilder`](crate::RopeBuilder), so the reader doesn't need to be
+    /// buffered.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EncodingError::Malformed`](crate::encoding::EncodingError)
+    /// if the contents aren't valid in the encoding, and
+    /// [`EncodingError::Io`](crate::encoding::EncodingError) if reading from
+    /// the reader fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::encoding::encoding_rs::{UTF_16LE, WINDOWS_1252};
+    /// # use crop::Rope;
+    /// #
+    /// let (r, encoding) =
+    ///     Rope::from_reader_with_encoding(&b"caf\xE9"[..], WINDOWS_1252)
+    ///         .unwrap();
+    ///
+    /// assert_eq!(r, "café");
+    /// assert_eq!(encoding.encoding, WINDOWS_1252);
+    ///
+    /// // The BOM overrides the given encoding.
+    /// let (r, encoding) =
+    ///     Rope::from_reader_with_encoding(&b"\xFF\xFEh\0i\0"[..], WINDOWS_1252)
+    ///         .unwrap();
+    ///
+    /// assert_eq!(r, "hi");
+    /// assert_eq!(encoding.encoding, UTF_16LE);
+    /// assert!(encoding.has_bom);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "encoding")))]
+    #[cfg(feature = "encoding")]
+    #[inline]
+    pub fn from_reader_with_encoding<R: std::io::Read>(
+        reader: R,
+        encoding: &'static crate::encoding::Encoding,
+    ) -> Result<
+        (Self, crate::encoding::TextEncoding),
+        crate::encoding::EncodingError,
+    > {
+        super::encoding::decode(reader, encoding)
+    }
+
+    /// Creates a new `Rope` from the string like [`From<&str>`](Self::from)
+    /// does, but copying its chunks and computing their summaries in parallel
+    /// on the [`rayon`](https://docs.rs/rayon) thread pool.
+    ///
+    /// The resulting `Rope` has exactly the same chunks as the one created
+    /// sequentially, so this is only worth it for large inputs, e.g. a file
+    /// that's hundreds of megabytes long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let s = "Hello, World!\n".repeat(1000);
+    ///
+    /// let r = Rope::from_str_parallel(&s);
+    ///
+    /// assert_eq!(r, s);
+    /// assert_eq!(r.line_len(), 1000);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub fn from_str_parallel(s: &str) -> Self {
+        use rayon::prelude::*;
+
+        // Segmenting the string only has to find the char boundaries closest
+        // to the split points, so it's cheap enough to do it sequentially.
+        let segments = RopeChunk::segmenter(s).collect::<alloc::vec::Vec<_>>();
+
+        let leaves = segments
+            .into_par_iter()
+            .map(RopeChunk::from)
+            .collect::<alloc::vec::Vec<_>>();
+
+        Rope {
+            has_trailing_newline: s.ends_with('\n'),
+            tree: Tree::from_leaves(leaves),
+        }
+    }
+
+    /// Creates a new `Rope` from the bytes, replacing any invalid UTF-8
+    /// sequence with `U+FFFD REPLACEMENT CHARACTER` like
+    /// [`String::from_utf8_lossy()`](alloc::string::String::from_utf8_lossy())
+    /// does.
+    ///
+    /// The replaced sequences are returned together with the `Rope`, and can
+    /// be used to get back the original bytes with
+    /// [`InvalidUtf8::original_bytes()`](crate::InvalidUtf8::original_bytes()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let (r, invalid) = Rope::from_utf8_lossy(b"Hello \xF0\x90\x80World");
+    ///
+    /// assert_eq!(r, "Hello \u{FFFD}World");
+    ///
+    /// let sequence = invalid.iter().next().unwrap();
+    ///
+    /// assert_eq!(sequence.byte_offset(), 6);
+    /// assert_eq!(sequence.original_range(), 6..9);
+    /// assert_eq!(sequence.bytes(), b"\xF0\x90\x80");
+    /// ```
+    #[inline]
+    pub fn from_utf8_lossy(bytes: &[u8]) -> (Self, super::InvalidUtf8) {
+        let mut builder = super::RopeBuilder::new();
+        builder.append_bytes_lossy(bytes);
+        builder.build_lossy()
+    }
+
+    /// Returns an iterator over the extended grapheme clusters of this
+    /// `Rope`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("arg!\r\n🐻‍❄️");
+    ///
+    /// let mut graphemes = r.graphemes();
+    ///
+    /// assert_eq!(Some("a"), graphemes.next().as_deref());
+    /// assert_eq!(Some("r"), graphemes.next().as_deref());
+    /// assert_eq!(Some("g"), graphemes.next().as_deref());
+    /// assert_eq!(Some("!"), graphemes.next().as_deref());
+    /// assert_eq!(Some("\r\n"), graphemes.next().as_deref());
+    /// assert_eq!(Some("🐻‍❄️"), graphemes.next().as_deref());
+    /// assert_eq!(None, graphemes.next());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn graphemes(&self) -> crate::iter::Graphemes<'_> {
+        crate::iter::Graphemes::from(self)
+    }
+
+    /// Prepends `indent` to every line in the given line range, skipping the
+    /// lines that are empty.
+    ///
+    /// Unlike calling [`insert()`](Self::insert()) once per line, this builds
+    /// the new text of the whole range in a single pass and then splices it
+    /// into the `Rope` with a single edit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the line range is greater than the end or if the
+    /// end is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("fn main() {\nfoo();\n\nbar();\n}\n");
+    ///
+    /// r.indent_lines(1..4, "    ");
+    /// assert_eq!(r, "fn main() {\n    foo();\n\n    bar();\n}\n");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn indent_lines<R>(&mut self, line_range: R, indent: &str)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = self.byte_range_of_lines(line_range);
+
+        if indent.is_empty() || start == end {
+            return;
+        }
+
+        let mut builder = super::RopeBuilder::new();
+
+        for line in self.byte_slice(start..end).raw_lines() {
+            if !super::indent::is_blank(line) {
+                builder.append(indent);
+            }
+
+            for chunk in line.chunks() {
+                builder.append(chunk);
+            }
+        }
+
+        let indented = builder.build();
+        self.replace_with_rope(start..end, indented.byte_slice(..));
+    }
+
+    /// Returns the leading spaces and tabs of the line at `line_index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line index is out of bounds (i.e. greater than or equal
+    /// to [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\n \t  bar\n    \n");
+    ///
+    /// assert_eq!(r.indentation_of_line(0), "");
+    /// assert_eq!(r.indentation_of_line(1), " \t  ");
+    /// assert_eq!(r.indentation_of_line(2), "    ");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn indentation_of_line(&self, line_index: usize) -> RopeSlice<'_> {
+        let line = self.line(line_index);
+        line.byte_slice(..super::indent::leading_whitespace(line))
+    }
+
+    /// Inserts `text` in the `Rope` at the given byte offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset doesn't lie on a code point boundary or if
+    /// it's out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello Earth!");
+    ///
+    /// r.insert(11, " 🌎");
+    /// assert_eq!(r, "Hello Earth 🌎!");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn insert<T>(&mut self, byte_offset: usize, text: T)
+    where
+        T: AsRef<str>,
+    {
+        self.replace(byte_offset..byte_offset, text)
+    }
+
+    /// Inserts the contents of a [`RopeSlice`] in the `Rope` at the given
+    /// byte offset.
+    ///
+    /// Unlike [`insert()`](Self::insert()) this doesn't copy the text of the
+    /// slice, and instead reuses the nodes of the `Rope` it was taken from.
+    /// This makes it run in `O(log n)` time regardless of the length of the
+    /// slice, e.g. when pasting or moving large blocks of text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset doesn't lie on a code point boundary or if
+    /// it's out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello Earth!");
+    ///
+    /// // Cloning a `Rope` is O(1), so we can copy text within the same
+    /// // `Rope` by taking a slice of its clone.
+    /// let clone = r.clone();
+    ///
+    /// r.insert_rope(11, clone.byte_slice(5..11));
+    /// assert_eq!(r, "Hello Earth Earth!");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn insert_rope(&mut self, byte_offset: usize, slice: RopeSlice<'_>) {
+        self.replace_with_rope(byte_offset..byte_offset, slice)
+    }
+
+    /// Returns `true` if the given byte offset lies on a [`char`] boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Löwe 老虎 Léopard");
+    ///
+    /// assert!(r.is_char_boundary(0));
+    /// assert!(r.is_char_boundary(r.byte_len()));
+    /// assert!(r.is_char_boundary(6)); // between ' ' and '老'
+    /// assert!(!r.is_char_boundary(2)); // between the 1st and 2nd byte of 'ö'
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn is_char_boundary(&self, byte_offset: usize) -> bool {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let (chunk, ByteMetric(chunk_byte_offset)) =
+            self.tree.leaf_at_measure(ByteMetric(byte_offset));
+
+        chunk.is_char_boundary(byte_offset - chunk_byte_offset)
+    }
+
+    /// Returns `true` if the `Rope`'s byte length is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::new();
+    /// assert!(r.is_empty());
+    ///
+    /// r.insert(0, "hey");
+    /// assert!(!r.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.byte_len() == 0
+    }
+
+    /// Returns `true` if the given byte offset lies on a grapheme cluster
+    /// boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("aargh!\r\n🐻‍❄️");
+    ///
+    /// assert!(r.is_grapheme_boundary(0));
     /// assert!(r.is_grapheme_boundary(r.byte_len()));
     /// assert!(!r.is_grapheme_boundary(7)); // between '\r' and '\n'
     /// assert!(r.is_grapheme_boundary(8)); // between '\n' and '🐻‍❄️'
@@ -437,7 +1345,7 @@ impl Rope {
             panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
         }
 
-        is_grapheme_boundary(self.chunks(), self.byte_len(), byte_offset)
+        is_grapheme_boundary(self.byte_slice(..), byte_offset)
     }
 
     /// Returns the line at `line_index`, without its line terminator.
@@ -601,18 +1509,444 @@ impl Rope {
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("foo\nbar\r\nbaz\n");
-    ///
-    /// let mut lines = r.lines();
+    /// let r = Rope::from("foo\nbar\r\nbaz\n");
+    ///
+    /// let mut lines = r.lines();
+    ///
+    /// assert_eq!("foo", lines.next().unwrap());
+    /// assert_eq!("bar", lines.next().unwrap());
+    /// assert_eq!("baz", lines.next().unwrap());
+    /// assert_eq!(None, lines.next());
+    /// ```
+    #[inline]
+    pub fn lines(&self) -> Lines<'_> {
+        Lines::from(self)
+    }
+
+    /// Returns an iterator over the lines in the given line range, yielding
+    /// the index of each line, the byte offset of its start and its contents
+    /// (not including the line terminator).
+    ///
+    /// The start of the range is only looked up once, so this is faster than
+    /// calling [`byte_of_line()`](Self::byte_of_line()) and
+    /// [`line()`](Self::line()) for every line in the range. The iterator
+    /// can also be consumed from the back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz\nqux\n");
+    ///
+    /// let mut lines = r.lines_in(1..3);
+    ///
+    /// let (line_idx, byte_start, line) = lines.next().unwrap();
+    /// assert_eq!((line_idx, byte_start), (1, 4));
+    /// assert_eq!(line, "bar");
+    ///
+    /// let (line_idx, byte_start, line) = lines.next_back().unwrap();
+    /// assert_eq!((line_idx, byte_start), (2, 9));
+    /// assert_eq!(line, "baz");
+    ///
+    /// assert!(lines.next().is_none());
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn lines_in<R>(&self, line_range: R) -> IndexedLines<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(line_range, 0, self.line_len());
+
+        let slice = self.line_slice(start..end);
+
+        let byte_start = self.byte_of_line(start);
+
+        IndexedLines::new(
+            slice.raw_lines(),
+            start,
+            byte_start,
+            byte_start + slice.byte_len(),
+        )
+    }
+
+    /// Replaces the text of the `Rope` within the specified byte range with
+    /// the result of passing it through `transform`, returning the byte range
+    /// of the new text.
+    ///
+    /// The text is fed to `transform` in pieces as it's read from the `Rope`,
+    /// without first collecting it into a `String`. Pieces always end on a
+    /// grapheme cluster boundary right after a whitespace, a control
+    /// character, an ASCII digit or an ASCII punctuation character other
+    /// than `'`, `.`, `:`, `^` and `` ` ``, so neither characters nor
+    /// grapheme clusters are ever split between two calls to `transform`, and
+    /// neither are words. The start and end of the range are treated as the
+    /// start and end of the text.
+    ///
+    /// Since how the text is split into pieces depends on how it's laid out
+    /// in the chunks of the `Rope`, applying `transform` to two adjacent
+    /// pieces separately has to give the same result as applying it to their
+    /// concatenation. Changing the case of the text satisfies this, including
+    /// the context-sensitive mapping of `'Σ'` done by
+    /// [`str::to_lowercase()`], since the characters pieces end with are
+    /// neither cased nor skipped when looking for the letters around a `'Σ'`.
+    /// On the other hand something like `|s| s.repeat(2)` doesn't.
+    ///
+    /// If `transform` doesn't change any of the pieces the `Rope` is left
+    /// untouched. See the [`transforms`](crate::transforms) module for some
+    /// built-in transforms.
+    ///
+    /// This needs the `graphemes` feature because the pieces are split on
+    /// grapheme cluster boundaries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start or the end of the byte range don't lie on a code
+    /// point boundary, if the start is greater than the end or if the end is
+    /// out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::borrow::Cow;
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello, world!");
+    ///
+    /// // Every "o" is doubled independently of the text around it.
+    /// let new_range = r.map_range(7..12, |s| Cow::Owned(s.replace('o', "oo")));
+    ///
+    /// assert_eq!(r, "Hello, woorld!");
+    /// assert_eq!(new_range, 7..13);
+    ///
+    /// r.map_range(.., crop::transforms::to_uppercase);
+    /// assert_eq!(r, "HELLO, WOORLD!");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn map_range<R, F>(
+        &mut self,
+        byte_range: R,
+        mut transform: F,
+    ) -> core::ops::Range<usize>
+    where
+        R: RangeBounds<usize>,
+        F: for<'a> FnMut(&'a str) -> alloc::borrow::Cow<'a, str>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.byte_len());
+
+        let slice = self.byte_slice(start..end);
+
+        let mut builder = super::RopeBuilder::new();
+
+        let mut has_changed = false;
+
+        // The text of the piece that's split between the previous chunks and
+        // the current one, if any.
+        let mut pending = String::new();
+
+        let mut chunk_end = 0;
+
+        for chunk in slice.chunks() {
+            let chunk_start = chunk_end;
+
+            chunk_end += chunk.len();
+
+            let boundary = if chunk_end == slice.byte_len() {
+                chunk_end
+            } else {
+                last_piece_end(slice, chunk, chunk_start).unwrap_or(chunk_start)
+            };
+
+            // The piece that started in a previous chunk continues in the
+            // next one.
+            if boundary <= chunk_start {
+                pending.push_str(chunk);
+                continue;
+            }
+
+            let (head, tail) = chunk.split_at(boundary - chunk_start);
+
+            let piece = if pending.is_empty() {
+                head
+            } else {
+                pending.push_str(head);
+                &pending
+            };
+
+            let transformed = transform(piece);
+            has_changed |= *transformed != *piece;
+            builder.append(&*transformed);
+
+            pending.clear();
+            pending.push_str(tail);
+        }
+
+        debug_assert!(pending.is_empty());
+
+        if !has_changed {
+            return start..end;
+        }
+
+        let transformed = builder.build();
+        let new_end = start + transformed.byte_len();
+        self.replace_with_rope(start..end, transformed.byte_slice(..));
+        start..new_end
+    }
+
+    /// Returns the byte offset of the bracket matching the one at
+    /// `byte_index`, or `None` if that byte isn't a bracket or if the bracket
+    /// is unmatched.
+    ///
+    /// Round (`()`), square (`[]`) and curly (`{}`) brackets all count
+    /// towards the same nesting depth, so if the bracket that closes (or
+    /// opens) the one at `byte_index` is of a different kind this returns
+    /// `None`.
+    ///
+    /// See [`enclosing_brackets()`](Self::enclosing_brackets()) for the time
+    /// complexity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte index is out of bounds (i.e. greater than or equal
+    /// to [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("f(a[b], {c})");
+    ///
+    /// assert_eq!(r.matching_bracket(1), Some(11));
+    /// assert_eq!(r.matching_bracket(11), Some(1));
+    /// assert_eq!(r.matching_bracket(3), Some(5));
+    /// assert_eq!(r.matching_bracket(10), Some(8));
+    /// assert_eq!(r.matching_bracket(0), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "bracket-depth")))]
+    #[cfg(feature = "bracket-depth")]
+    #[track_caller]
+    #[inline]
+    pub fn matching_bracket(&self, byte_index: usize) -> Option<usize> {
+        let bracket = self.byte(byte_index);
+
+        let matching = matching_bracket_byte(bracket)?;
+
+        let ByteMetric(found) = if bracket_delta(bracket) > 0 {
+            self.tree.find_unmatched_close(ByteMetric(byte_index + 1))?
+        } else {
+            self.tree.find_unmatched_open(ByteMetric(byte_index))?
+        };
+
+        (self.byte(found) == matching).then_some(found)
+    }
+
+    /// Returns the byte offset of the first grapheme cluster boundary after
+    /// `byte_offset`, or [`byte_len()`](Self::byte_len()) if there isn't one.
+    ///
+    /// The byte offset doesn't have to lie on a grapheme cluster or [`char`]
+    /// boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("aargh!\r\n🐻‍❄️");
+    ///
+    /// assert_eq!(r.next_grapheme_boundary(5), 6);
+    /// assert_eq!(r.next_grapheme_boundary(6), 8);
+    /// assert_eq!(r.next_grapheme_boundary(8), 21);
+    /// assert_eq!(r.next_grapheme_boundary(21), 21);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn next_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        if self.is_char_boundary(byte_offset) {
+            next_grapheme_boundary(self.byte_slice(..), byte_offset)
+        } else {
+            self.ceil_grapheme_boundary(byte_offset)
+        }
+    }
+
+    /// Returns the byte offset of the first word boundary after `byte_offset`,
+    /// or [`byte_len()`](Self::byte_len()) if there isn't one.
+    ///
+    /// This only segments the chunks around the byte offset, so it runs in
+    /// logarithmic time unless the segments around it span many chunks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar\r\nbaz");
+    ///
+    /// assert_eq!(r.next_word_boundary(0), 3);
+    /// assert_eq!(r.next_word_boundary(3), 4);
+    /// assert_eq!(r.next_word_boundary(5), 7);
+    /// assert_eq!(r.next_word_boundary(7), 9);
+    /// assert_eq!(r.next_word_boundary(12), 12);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn next_word_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        next_word_boundary(
+            self.byte_slice(..),
+            self.floor_char_boundary(byte_offset),
+        )
+    }
+
+    /// Returns the number of paragraphs in the `Rope`, where a paragraph is a
+    /// maximal run of lines containing at least one non-whitespace character.
+    ///
+    /// See [`word_count()`](Self::word_count()) for the time complexity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Lorem ipsum\ndolor sit\n\n  \namet\n");
+    /// assert_eq!(r.paragraph_count(), 2);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "text-stats")))]
+    #[cfg(feature = "text-stats")]
+    #[inline]
+    pub fn paragraph_count(&self) -> usize {
+        self.tree.text_stats().paragraphs()
+    }
+
+    /// Prepends another `Rope` to the start of this one.
+    ///
+    /// This runs in `O(log n)` time, and the nodes of `other` end up being
+    /// reused by this `Rope` instead of being copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("world!");
+    ///
+    /// r.prepend(Rope::from("Hello "));
+    /// assert_eq!(r, "Hello world!");
+    /// ```
+    #[inline]
+    pub fn prepend(&mut self, other: Self) {
+        let this = core::mem::replace(self, other);
+        self.append(this);
+    }
+
+    /// Returns the byte offset of the last grapheme cluster boundary before
+    /// `byte_offset`, or zero if there isn't one.
+    ///
+    /// The byte offset doesn't have to lie on a grapheme cluster or [`char`]
+    /// boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("aargh!\r\n🐻‍❄️");
+    ///
+    /// assert_eq!(r.prev_grapheme_boundary(21), 8);
+    /// assert_eq!(r.prev_grapheme_boundary(8), 6);
+    /// assert_eq!(r.prev_grapheme_boundary(6), 5);
+    /// assert_eq!(r.prev_grapheme_boundary(0), 0);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn prev_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        if self.is_char_boundary(byte_offset) {
+            prev_grapheme_boundary(self.byte_slice(..), byte_offset)
+        } else {
+            self.floor_grapheme_boundary(byte_offset)
+        }
+    }
+
+    /// Returns the byte offset of the last word boundary before `byte_offset`,
+    /// or zero if there isn't one.
+    ///
+    /// See [`next_word_boundary()`](Self::next_word_boundary()) for the time
+    /// complexity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar\r\nbaz");
     ///
-    /// assert_eq!("foo", lines.next().unwrap());
-    /// assert_eq!("bar", lines.next().unwrap());
-    /// assert_eq!("baz", lines.next().unwrap());
-    /// assert_eq!(None, lines.next());
+    /// assert_eq!(r.prev_word_boundary(12), 9);
+    /// assert_eq!(r.prev_word_boundary(9), 7);
+    /// assert_eq!(r.prev_word_boundary(5), 4);
+    /// assert_eq!(r.prev_word_boundary(3), 0);
+    /// assert_eq!(r.prev_word_boundary(0), 0);
     /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
     #[inline]
-    pub fn lines(&self) -> Lines<'_> {
-        Lines::from(self)
+    pub fn prev_word_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        prev_word_boundary(
+            self.byte_slice(..),
+            self.ceil_char_boundary(byte_offset),
+        )
     }
 
     /// Returns an iterator over the lines of this `Rope`, including the
@@ -718,6 +2052,285 @@ impl Rope {
         }
     }
 
+    /// Replaces the rectangular block of text spanning the given line and
+    /// column ranges with the given lines, one per line in the range.
+    ///
+    /// The block is the same one returned by
+    /// [`block_slice()`](Self::block_slice()). Lines shorter than the start of
+    /// the column range are padded with spaces before inserting their
+    /// replacement, lines left without a replacement if the iterator runs out
+    /// have their part of the block deleted, and extra replacements are
+    /// ignored. The new text of the whole line range is built in a single
+    /// pass and then spliced into the `Rope` with a single edit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of either range is greater than its end or if the
+    /// end of the line range is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{ColumnUnit, Rope};
+    /// #
+    /// let mut r = Rope::from("foo = 1;\nbarbaz = 2;\nq\n");
+    ///
+    /// r.replace_block(.., 3..7, ColumnUnit::Chars, ["", "", "...."]);
+    /// assert_eq!(r, "foo;\nbar= 2;\nq  ....\n");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn replace_block<R, C, I>(
+        &mut self,
+        line_range: R,
+        column_range: C,
+        unit: super::ColumnUnit,
+        lines: I,
+    ) where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        const SPACES: &str = "                ";
+
+        let (column_start, column_end) =
+            column_range_to_start_end(column_range);
+
+        let (start, end) = self.byte_range_of_lines(line_range);
+
+        let mut replacements = lines.into_iter();
+
+        let mut builder = super::RopeBuilder::new();
+
+        for raw_line in self.byte_slice(start..end).raw_lines() {
+            let (line, terminator) = super::block::split_terminator(raw_line);
+
+            let span = super::block::span_of_columns(
+                line,
+                column_start,
+                column_end,
+                unit,
+            );
+
+            for chunk in line.byte_slice(..span.start).chunks() {
+                builder.append(chunk);
+            }
+
+            if let Some(replacement) = replacements.next() {
+                let replacement = replacement.as_ref();
+
+                if !replacement.is_empty() {
+                    let mut missing = span.missing;
+
+                    while missing > 0 {
+                        let padding = missing.min(SPACES.len());
+                        builder.append(&SPACES[..padding]);
+                        missing -= padding;
+                    }
+
+                    builder.append(replacement);
+                }
+            }
+
+            for chunk in line.byte_slice(span.end..).chunks() {
+                builder.append(chunk);
+            }
+
+            for chunk in terminator.chunks() {
+                builder.append(chunk);
+            }
+        }
+
+        let replaced = builder.build();
+
+        self.replace_with_rope(start..end, replaced.byte_slice(..));
+    }
+
+    /// Replaces the contents of the `Rope` within the specified byte range
+    /// with the contents of a [`RopeSlice`], where the start and end of the
+    /// range are interpreted as byte offsets.
+    ///
+    /// Unlike [`replace()`](Self::replace()) this doesn't copy the text of
+    /// the slice, and instead reuses the nodes of the `Rope` it was taken
+    /// from. This makes it run in `O(log n)` time regardless of the length of
+    /// the slice and of the replaced range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start or the end of the byte range don't lie on a code
+    /// point boundary, if the start is greater than the end or if the end is
+    /// out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello Earth 🌎!");
+    ///
+    /// let planets = Rope::from("Mercury, Venus, Saturn 🪐, Uranus");
+    ///
+    /// r.replace_with_rope(6..16, planets.byte_slice(16..27));
+    /// assert_eq!(r, "Hello Saturn 🪐!");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn replace_with_rope<R>(&mut self, byte_range: R, slice: RopeSlice<'_>)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.byte_len());
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        if end > self.byte_len() {
+            panic::byte_offset_out_of_bounds(end, self.byte_len());
+        }
+
+        let mut rope = Self::from(self.byte_slice(..start));
+        rope.append(Self::from(slice));
+        rope.append(Self::from(self.byte_slice(end..)));
+        *self = rope;
+    }
+
+    /// Splits the `Rope` in two at the given byte offset, returning the part
+    /// after the offset and leaving the one before it in `self`.
+    ///
+    /// This runs in `O(log n)` time, and the two resulting `Rope`s keep
+    /// sharing most of their memory with each other and with any clones of
+    /// the original `Rope`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is not a char boundary or if it's out of
+    /// bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello world!");
+    ///
+    /// let world = r.split_off(6);
+    ///
+    /// assert_eq!(r, "Hello ");
+    /// assert_eq!(world, "world!");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn split_off(&mut self, byte_offset: usize) -> Self {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let right = Self::from(self.byte_slice(byte_offset..));
+        *self = Self::from(self.byte_slice(..byte_offset));
+        right
+    }
+
+    /// Returns an iterator over the sentences of this `Rope`, as defined by
+    /// the [Unicode Standard Annex #29][uax29].
+    ///
+    /// Concatenating all the sentences gives back the whole text.
+    ///
+    /// The text is segmented one chunk at a time, looking into the following
+    /// chunks only as far as needed to find where the last sentences of the
+    /// chunk end. A sentence is only collected into a `String` if it spans
+    /// several chunks, so the iterator never holds more text than a few
+    /// chunks and a few of the longest sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hi there. How are you?\nFine.");
+    ///
+    /// let mut sentences = r.sentences();
+    ///
+    /// assert_eq!(Some("Hi there. "), sentences.next().as_deref());
+    /// assert_eq!(Some("How are you?\n"), sentences.next().as_deref());
+    /// assert_eq!(Some("Fine."), sentences.next().as_deref());
+    /// assert_eq!(None, sentences.next());
+    /// ```
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Sentence_Boundaries
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn sentences(&self) -> crate::iter::Sentences<'_> {
+        crate::iter::Sentences::from(self)
+    }
+
+    /// Returns `true` if the `Rope` starts with the given string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar baz");
+    ///
+    /// assert!(r.starts_with("foo bar"));
+    /// assert!(r.starts_with(""));
+    /// assert!(!r.starts_with("bar"));
+    /// ```
+    #[inline]
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.byte_len() >= prefix.len()
+            && chunks_start_with(self.chunks(), prefix.as_bytes())
+    }
+
+    /// Returns a [`RopeStats`] describing the internal structure and memory
+    /// usage of the `Rope`.
+    ///
+    /// Note that this runs in time proportional to the number of nodes in
+    /// the `Rope`'s tree, so it shouldn't be called in hot loops.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello, world!\n".repeat(1000));
+    ///
+    /// let stats = r.stats();
+    /// assert!(stats.leaf_count > 1);
+    /// assert!(stats.allocated_bytes > r.byte_len());
+    /// assert_eq!(stats.shared_nodes, 0);
+    ///
+    /// // Cloning a `Rope` shares all of its nodes with the clone.
+    /// let r2 = r.clone();
+    /// assert!(r2.stats().shared_nodes > 0);
+    /// ```
+    #[inline]
+    pub fn stats(&self) -> RopeStats {
+        let leaf_count = self.tree.leaf_count();
+
+        let leaves_capacity = leaf_count * CHUNK_MAX_BYTES;
+
+        let fill_ratio = if leaves_capacity == 0 {
+            0.0
+        } else {
+            self.byte_len() as f64 / leaves_capacity as f64
+        };
+
+        RopeStats {
+            leaf_count,
+            depth: self.tree.depth(),
+            allocated_bytes: self.tree.heap_size() + leaves_capacity,
+            gap_bytes: leaves_capacity - self.byte_len(),
+            fill_ratio,
+            shared_nodes: self.tree.shared_node_count(),
+        }
+    }
+
     /// Returns the number of UTF-16 code units the `Rope` would have if it
     /// stored its text as UTF-16 instead of UTF-8.
     ///
@@ -814,6 +2427,196 @@ impl Rope {
 
         self.tree.slice(Utf16Metric(start)..Utf16Metric(end)).into()
     }
+
+    /// Returns an iterator over the segments of this `Rope` separated by
+    /// word boundaries, as defined by the [Unicode Standard Annex
+    /// #29][uax29].
+    ///
+    /// Unlike [`words()`](Self::words()) this also yields the whitespace and
+    /// punctuation between the words, so concatenating all the segments
+    /// gives back the whole text.
+    ///
+    /// The text is segmented one chunk at a time, looking into the following
+    /// chunks only as far as needed to find where the last segments of the
+    /// chunk end. A segment is only collected into a `String` if it spans
+    /// several chunks, so the iterator never holds more text than a few
+    /// chunks and a few of the longest segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello, world!");
+    ///
+    /// let mut bounds = r.word_bounds();
+    ///
+    /// assert_eq!(Some("Hello"), bounds.next().as_deref());
+    /// assert_eq!(Some(","), bounds.next().as_deref());
+    /// assert_eq!(Some(" "), bounds.next().as_deref());
+    /// assert_eq!(Some("world"), bounds.next().as_deref());
+    /// assert_eq!(Some("!"), bounds.next().as_deref());
+    /// assert_eq!(None, bounds.next());
+    /// ```
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn word_bounds(&self) -> crate::iter::WordBounds<'_> {
+        crate::iter::WordBounds::from(self)
+    }
+
+    /// Returns the number of words in the `Rope`, where a word is a maximal
+    /// run of non-whitespace characters, just like the ones yielded by
+    /// [`str::split_whitespace()`].
+    ///
+    /// The word counts of every internal node of the `Rope` are cached until
+    /// the node is mutated. The first call runs in linear time, but after
+    /// that an edit only invalidates the counts of the nodes it touched, so
+    /// calling this after each edit runs in logarithmic time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Lorem ipsum dolor");
+    /// assert_eq!(r.word_count(), 3);
+    ///
+    /// r.insert(5, "\n");
+    /// assert_eq!(r.word_count(), 3);
+    ///
+    /// r.delete(5..7);
+    /// assert_eq!(r.word_count(), 2);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "text-stats")))]
+    #[cfg(feature = "text-stats")]
+    #[inline]
+    pub fn word_count(&self) -> usize {
+        self.tree.text_stats().words()
+    }
+
+    /// Returns an iterator over the words of this `Rope`, as defined by the
+    /// [Unicode Standard Annex #29][uax29].
+    ///
+    /// Segments that don't contain any alphanumeric character, like
+    /// whitespace and punctuation, are skipped.
+    ///
+    /// The text is segmented one chunk at a time, looking into the following
+    /// chunks only as far as needed to find where the last segments of the
+    /// chunk end. A segment is only collected into a `String` if it spans
+    /// several chunks, so the iterator never holds more text than a few
+    /// chunks and a few of the longest segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello, world!\nit's me.");
+    ///
+    /// let mut words = r.words();
+    ///
+    /// assert_eq!(Some("Hello"), words.next().as_deref());
+    /// assert_eq!(Some("world"), words.next().as_deref());
+    /// assert_eq!(Some("it's"), words.next().as_deref());
+    /// assert_eq!(Some("me"), words.next().as_deref());
+    /// assert_eq!(None, words.next());
+    /// ```
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn words(&self) -> crate::iter::Words<'_> {
+        crate::iter::Words::from(self)
+    }
+
+    /// Encodes the contents of the `Rope` in the given encoding, writing them
+    /// to the writer one chunk at a time.
+    ///
+    /// Both an [`Encoding`](crate::encoding::Encoding) and the
+    /// [`TextEncoding`](crate::encoding::TextEncoding) returned by
+    /// [`from_reader_with_encoding()`](Self::from_reader_with_encoding()) can
+    /// be passed, and a byte order mark is written first if the latter says
+    /// the original text had one.
+    ///
+    /// # Errors
+    ///
+    /// Returns
+    /// [`EncodingError::Unmappable`](crate::encoding::EncodingError) with the
+    /// first character that can't be represented in the encoding instead of
+    /// replacing it, and [`EncodingError::Io`](crate::encoding::EncodingError)
+    /// if writing to the writer fails. In both cases the writer may have
+    /// already received part of the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::encoding::encoding_rs::{SHIFT_JIS, WINDOWS_1252};
+    /// # use crop::encoding::EncodingError;
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("café");
+    ///
+    /// let mut bytes = Vec::new();
+    /// r.write_encoded(&mut bytes, WINDOWS_1252).unwrap();
+    /// assert_eq!(bytes, b"caf\xE9");
+    ///
+    /// let r = Rope::from("日本 🦀");
+    ///
+    /// let err = r.write_encoded(Vec::new(), SHIFT_JIS).unwrap_err();
+    ///
+    /// assert!(matches!(
+    ///     err,
+    ///     EncodingError::Unmappable { byte_offset: 7, ch: '🦀', .. }
+    /// ));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "encoding")))]
+    #[cfg(feature = "encoding")]
+    #[inline]
+    pub fn write_encoded<W, E>(
+        &self,
+        writer: W,
+        encoding: E,
+    ) -> Result<(), crate::encoding::EncodingError>
+    where
+        W: std::io::Write,
+        E: Into<crate::encoding::TextEncoding>,
+    {
+        super::encoding::encode(self, writer, encoding.into())
+    }
+}
+
+/// Returns whether a subtree with the given summary and number of leaves is
+/// already dense enough that there's no point in compacting it.
+///
+/// Compacting fills every chunk up to the last char boundary that fits
+/// before moving on to the next one, so a compacted run of chunks is missing
+/// at most 3 bytes per chunk plus whatever is missing from its last chunk.
+/// Any subtree that's at least that dense couldn't lose a chunk by being
+/// compacted again.
+#[inline]
+fn is_dense(summary: &ChunkSummary, leaf_count: usize) -> bool {
+    summary.bytes() + CHUNK_MAX_BYTES >= leaf_count * (CHUNK_MAX_BYTES - 3)
+}
+
+impl core::ops::Add for Rope {
+    type Output = Self;
+
+    #[inline]
+    fn add(mut self, rhs: Self) -> Self {
+        self.append(rhs);
+        self
+    }
+}
+
+impl core::ops::AddAssign for Rope {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.append(rhs);
+    }
 }
 
 impl From<RopeSlice<'_>> for Rope {
@@ -886,6 +2689,23 @@ impl core::str::FromStr for Rope {
 impl core::cmp::PartialEq<Rope> for Rope {
     #[inline]
     fn eq(&self, rhs: &Rope) -> bool {
+        // Clones of the same `Rope` share their root until they're edited.
+        if self.tree.ptr_eq(&rhs.tree) {
+            return true;
+        }
+
+        // If both `Rope`s have already computed their content hash we can
+        // tell they're different without looking at their text. Equal hashes
+        // don't prove anything, so in that case we still compare the chunks.
+        #[cfg(feature = "content-hash")]
+        if let (Some(lhs), Some(rhs)) =
+            (self.tree.cached_rolling_hash(), rhs.tree.cached_rolling_hash())
+        {
+            if lhs != rhs {
+                return false;
+            }
+        }
+
         (self.byte_len() == rhs.byte_len())
             && (self.line_len() == rhs.line_len())
             && chunks_eq_chunks(self.chunks(), rhs.chunks())
@@ -958,3 +2778,31 @@ impl core::cmp::PartialEq<Rope> for alloc::borrow::Cow<'_, str> {
 }
 
 impl core::cmp::Eq for Rope {}
+
+impl core::hash::Hash for Rope {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        hash_chunks(self.chunks(), state)
+    }
+}
+
+impl core::cmp::Ord for Rope {
+    #[inline]
+    fn cmp(&self, rhs: &Self) -> core::cmp::Ordering {
+        chunks_cmp_chunks(self.chunks(), rhs.chunks())
+    }
+}
+
+impl core::cmp::PartialOrd<Rope> for Rope {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Rope) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSlice<'_>> for Rope {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSlice<'_>) -> Option<core::cmp::Ordering> {
+        Some(chunks_cmp_chunks(self.chunks(), rhs.chunks()))
+    }
+}
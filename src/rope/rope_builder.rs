@@ -1,4 +1,5 @@
 use super::gap_buffer::GapBuffer;
+use super::lossy::{Decoded, InvalidUtf8, Utf8Decoder};
 use super::metrics::ChunkSummary;
 use super::rope::RopeChunk;
 use super::utils::split_adjusted;
@@ -12,6 +13,7 @@ pub struct RopeBuilder {
     buffer: RopeChunk,
     buffer_len_left: usize,
     rope_has_trailing_newline: bool,
+    utf8_decoder: Utf8Decoder,
 }
 
 /// Pushes as mush of the slice as possible onto the left chunk of the gap
@@ -32,7 +34,7 @@ fn gap_buffer_push_with_remainder<'a, const MAX_BYTES: usize>(
 
     let space_left = MAX_BYTES - len_left;
 
-    let (push, rest) = split_adjusted::<false>(s, space_left);
+    let (push, rest) = split_adjusted::<false, _>(s, space_left);
 
     debug_assert!(push.len() <= space_left);
 
@@ -55,25 +57,56 @@ impl RopeBuilder {
     where
         T: AsRef<str>,
     {
-        let mut text = text.as_ref();
+        let text = text.as_ref();
 
-        while let Some(rest) = gap_buffer_push_with_remainder(
-            &mut self.buffer,
-            &mut self.buffer_len_left,
-            text,
-        ) {
-            self.buffer.left_summary =
-                ChunkSummary::from(self.buffer_left_chunk());
+        // An incomplete UTF-8 sequence left by `append_bytes_lossy()` can't
+        // be completed anymore.
+        if let Some(replacement) = self.utf8_decoder.finish() {
+            self.push_str(replacement.encode_utf8(&mut [0; 4]));
+        }
 
-            self.tree_builder.append(core::mem::take(&mut self.buffer));
+        self.utf8_decoder.push_valid(text.len());
 
-            self.buffer_len_left = 0;
+        self.push_str(text)
+    }
 
-            text = rest;
+    /// Appends the bytes to the end of the `Rope` being built, replacing any
+    /// invalid UTF-8 sequence with `U+FFFD REPLACEMENT CHARACTER` like
+    /// [`String::from_utf8_lossy()`](alloc::string::String::from_utf8_lossy())
+    /// does.
+    ///
+    /// A sequence that's cut off at the end of the bytes is kept aside until
+    /// the next call, so the input can be split at arbitrary byte offsets.
+    /// The invalid sequences are recorded and can be retrieved with
+    /// [`build_lossy()`](Self::build_lossy()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::RopeBuilder;
+    /// #
+    /// let mut builder = RopeBuilder::new();
+    ///
+    /// // The "ƒ" is encoded as `[0xC6, 0x92]`.
+    /// builder.append_bytes_lossy(b"\xC6").append_bytes_lossy(b"\x92oo\xFF");
+    ///
+    /// let (rope, invalid) = builder.build_lossy();
+    ///
+    /// assert_eq!(rope, "ƒoo\u{FFFD}");
+    /// assert_eq!(invalid.len(), 1);
+    /// assert_eq!(invalid.iter().next().unwrap().original_range(), 4..5);
+    /// ```
+    #[inline]
+    pub fn append_bytes_lossy(&mut self, mut bytes: &[u8]) -> &mut Self {
+        while let Some(decoded) = self.utf8_decoder.decode_next(&mut bytes) {
+            match decoded {
+                Decoded::Str(s) => self.push_str(s),
+                Decoded::Char(ch) => {
+                    self.push_str(ch.encode_utf8(&mut [0; 4]))
+                },
+            };
         }
 
-        self.rope_has_trailing_newline = self.buffer.has_trailing_newline();
-
         self
     }
 
@@ -105,21 +138,48 @@ impl RopeBuilder {
     /// assert_eq!(rope, "ƒoo\nbär\r\nbaz");
     /// ```
     #[inline]
-    pub fn build(mut self) -> Rope {
-        if self.buffer_len_left > 0 {
-            self.rope_has_trailing_newline =
-                self.buffer.has_trailing_newline();
+    pub fn build(self) -> Rope {
+        self.build_lossy().0
+    }
 
+    /// Like [`build()`](Self::build()), but also returns the invalid UTF-8
+    /// sequences that were replaced by
+    /// [`append_bytes_lossy()`](Self::append_bytes_lossy()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::RopeBuilder;
+    /// #
+    /// let mut builder = RopeBuilder::new();
+    ///
+    /// builder.append_bytes_lossy(b"foo\xF0\x9F");
+    ///
+    /// // The incomplete sequence at the end is replaced when building.
+    /// let (rope, invalid) = builder.build_lossy();
+    ///
+    /// assert_eq!(rope, "foo\u{FFFD}");
+    /// assert_eq!(invalid.iter().next().unwrap().bytes(), b"\xF0\x9F");
+    /// ```
+    #[inline]
+    pub fn build_lossy(mut self) -> (Rope, InvalidUtf8) {
+        if let Some(replacement) = self.utf8_decoder.finish() {
+            self.push_str(replacement.encode_utf8(&mut [0; 4]));
+        }
+
+        if self.buffer_len_left > 0 {
             self.buffer.left_summary =
                 ChunkSummary::from(self.buffer_left_chunk());
 
             self.tree_builder.append(self.buffer);
         }
 
-        Rope {
+        let rope = Rope {
             tree: self.tree_builder.build(),
             has_trailing_newline: self.rope_has_trailing_newline,
-        }
+        };
+
+        (rope, self.utf8_decoder.into_invalid())
     }
 
     /// Creates a new `RopeBuilder`.
@@ -127,4 +187,33 @@ impl RopeBuilder {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Pushes the string onto the `Rope` being built.
+    #[inline]
+    fn push_str(&mut self, mut text: &str) -> &mut Self {
+        while let Some(rest) = gap_buffer_push_with_remainder(
+            &mut self.buffer,
+            &mut self.buffer_len_left,
+            text,
+        ) {
+            self.buffer.left_summary =
+                ChunkSummary::from(self.buffer_left_chunk());
+
+            self.tree_builder.append(core::mem::take(&mut self.buffer));
+
+            self.buffer_len_left = 0;
+
+            text = rest;
+        }
+
+        // The summary of the buffer is only computed when it's flushed, so it
+        // doesn't know about the text we've just pushed and we have to look
+        // at the buffer's contents instead.
+        if self.buffer_len_left > 0 {
+            self.rope_has_trailing_newline =
+                self.buffer_left_chunk().ends_with('\n');
+        }
+
+        self
+    }
 }
@@ -1,3 +1,4 @@
+use alloc::string::String;
 use core::ops::RangeBounds;
 
 use super::iterators::{Bytes, Chars, Chunks, Lines, RawLines};
@@ -219,6 +220,88 @@ impl<'a> RopeSlice<'a> {
         Bytes::from(self)
     }
 
+    /// Returns the smallest byte offset greater than or equal to `byte_offset`
+    /// that lies on a [`char`] boundary.
+    ///
+    /// Offsets greater than [`byte_len()`](Self::byte_len()) are clamped to
+    /// the byte length of the `RopeSlice`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Löwe 老虎 Léopard");
+    /// let s = r.byte_slice(3..);
+    ///
+    /// assert_eq!(s.ceil_char_boundary(3), 3);
+    /// assert_eq!(s.ceil_char_boundary(4), 6);
+    /// assert_eq!(s.ceil_char_boundary(100), s.byte_len());
+    /// ```
+    #[inline]
+    pub fn ceil_char_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset >= self.byte_len() {
+            return self.byte_len();
+        }
+
+        let (chunk, ByteMetric(chunk_byte_offset)) =
+            self.tree_slice.leaf_at_measure(ByteMetric(byte_offset));
+
+        chunk_byte_offset
+            + chunk.ceil_char_boundary(byte_offset - chunk_byte_offset)
+    }
+
+    /// Returns the smallest byte offset greater than or equal to `byte_offset`
+    /// that lies on a grapheme cluster boundary.
+    ///
+    /// Offsets greater than [`byte_len()`](Self::byte_len()) are clamped to
+    /// the byte length of the `RopeSlice`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("👋\r\naargh!\r\n🐻‍❄️");
+    /// let s = r.line_slice(1..);
+    ///
+    /// assert_eq!(s.ceil_grapheme_boundary(7), 8); // between '\r' and '\n'
+    /// assert_eq!(s.ceil_grapheme_boundary(12), 21); // inside '🐻‍❄️'
+    /// assert_eq!(s.ceil_grapheme_boundary(21), 21);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn ceil_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        let byte_offset = self.ceil_char_boundary(byte_offset);
+
+        if is_grapheme_boundary(*self, byte_offset) {
+            byte_offset
+        } else {
+            next_grapheme_boundary(*self, byte_offset)
+        }
+    }
+
+    /// Returns the number of `char`s in the `RopeSlice`.
+    ///
+    /// This is tracked together with the other metrics of the `RopeSlice`, so
+    /// it runs in logarithmic time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a🦀b\r\n");
+    /// assert_eq!(r.byte_slice(1..6).char_count(), 2);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "text-stats")))]
+    #[cfg(feature = "text-stats")]
+    #[inline]
+    pub fn char_count(&self) -> usize {
+        self.tree_slice.summary().chars()
+    }
+
     /// Returns an iterator over the [`char`]s of this `RopeSlice`.
     ///
     /// # Examples
@@ -247,6 +330,165 @@ impl<'a> RopeSlice<'a> {
         Chunks::from(self)
     }
 
+    /// Returns a 64-bit hash of the text of the `RopeSlice`.
+    ///
+    /// This is equal to the [`content_hash()`](crate::Rope::content_hash())
+    /// of a `Rope` with the same text. Only the first and last chunks of the
+    /// slice are hashed from scratch, all the others use the hashes cached in
+    /// the `Rope` they're a part of, so this runs in `O(log n)` time once
+    /// those have been computed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar foo");
+    ///
+    /// let first_foo = r.byte_slice(..3);
+    /// let last_foo = r.byte_slice(8..);
+    ///
+    /// assert_eq!(first_foo.content_hash(), last_foo.content_hash());
+    /// assert_ne!(first_foo.content_hash(), r.byte_slice(4..7).content_hash());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "content-hash")))]
+    #[cfg(feature = "content-hash")]
+    #[inline]
+    pub fn content_hash(&self) -> u64 {
+        self.tree_slice.rolling_hash().finish()
+    }
+
+    /// Returns `true` if the `RopeSlice` ends with the given string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar baz");
+    /// let s = r.byte_slice(..7);
+    ///
+    /// assert!(s.ends_with("bar"));
+    /// assert!(s.ends_with(""));
+    /// assert!(!s.ends_with("baz"));
+    /// ```
+    #[inline]
+    pub fn ends_with(&self, suffix: &str) -> bool {
+        self.byte_len() >= suffix.len()
+            && chunks_end_with(self.chunks(), suffix.as_bytes())
+    }
+
+    /// Returns `true` if the `RopeSlice` and the given string are equal when
+    /// ignoring the case of ASCII letters, like
+    /// [`str::eq_ignore_ascii_case()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello WORLD");
+    ///
+    /// assert!(r.byte_slice(6..).eq_ignore_ascii_case("world"));
+    /// assert!(!r.byte_slice(6..).eq_ignore_ascii_case("word"));
+    /// ```
+    #[inline]
+    pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        self.byte_len() == other.len()
+            && chunks_eq_str_ignore_ascii_case(self.chunks(), other)
+    }
+
+    /// Returns `true` if the `RopeSlice` and the given string are canonically
+    /// equivalent, i.e. if they're equal after being normalized.
+    ///
+    /// Two strings have the same NFC normalization if and only if they have
+    /// the same NFD normalization, so the result doesn't depend on which of
+    /// the two forms is used. Characters are normalized one chunk at a time
+    /// as they're compared, without collecting them into a `String`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// // "é" as a single code point and as an "e" followed by a combining
+    /// // acute accent.
+    /// let r = Rope::from("caf\u{e9} cafe\u{301}");
+    ///
+    /// assert!(r.byte_slice(..5).eq_normalized("cafe\u{301}"));
+    /// assert!(r.byte_slice(6..).eq_normalized("caf\u{e9}"));
+    /// assert!(!r.byte_slice(6..).eq_normalized("cafe"));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "normalization")))]
+    #[cfg(feature = "normalization")]
+    #[inline]
+    pub fn eq_normalized(&self, other: &str) -> bool {
+        use unicode_normalization::UnicodeNormalization;
+        self.chars().nfd().eq(other.nfd())
+    }
+
+    /// Returns the largest byte offset less than or equal to `byte_offset`
+    /// that lies on a [`char`] boundary.
+    ///
+    /// Offsets greater than [`byte_len()`](Self::byte_len()) are clamped to
+    /// the byte length of the `RopeSlice`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Löwe 老虎 Léopard");
+    /// let s = r.byte_slice(3..);
+    ///
+    /// assert_eq!(s.floor_char_boundary(3), 3);
+    /// assert_eq!(s.floor_char_boundary(4), 3);
+    /// assert_eq!(s.floor_char_boundary(100), s.byte_len());
+    /// ```
+    #[inline]
+    pub fn floor_char_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset >= self.byte_len() {
+            return self.byte_len();
+        }
+
+        let (chunk, ByteMetric(chunk_byte_offset)) =
+            self.tree_slice.leaf_at_measure(ByteMetric(byte_offset));
+
+        chunk_byte_offset
+            + chunk.floor_char_boundary(byte_offset - chunk_byte_offset)
+    }
+
+    /// Returns the largest byte offset less than or equal to `byte_offset`
+    /// that lies on a grapheme cluster boundary.
+    ///
+    /// Offsets greater than [`byte_len()`](Self::byte_len()) are clamped to
+    /// the byte length of the `RopeSlice`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("👋\r\naargh!\r\n🐻‍❄️");
+    /// let s = r.line_slice(1..);
+    ///
+    /// assert_eq!(s.floor_grapheme_boundary(7), 6); // between '\r' and '\n'
+    /// assert_eq!(s.floor_grapheme_boundary(12), 8); // inside '🐻‍❄️'
+    /// assert_eq!(s.floor_grapheme_boundary(100), s.byte_len());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn floor_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        let byte_offset = self.floor_char_boundary(byte_offset);
+
+        if is_grapheme_boundary(*self, byte_offset) {
+            byte_offset
+        } else {
+            prev_grapheme_boundary(*self, byte_offset)
+        }
+    }
+
     /// Returns an iterator over the extended grapheme clusters of this
     /// `RopeSlice`.
     ///
@@ -352,7 +594,7 @@ impl<'a> RopeSlice<'a> {
             panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
         }
 
-        is_grapheme_boundary(self.chunks(), self.byte_len(), byte_offset)
+        is_grapheme_boundary(*self, byte_offset)
     }
 
     /// Returns the line at `line_index`, without its line terminator.
@@ -535,6 +777,180 @@ impl<'a> RopeSlice<'a> {
         Lines::from(self)
     }
 
+    /// Returns the byte offset of the first grapheme cluster boundary after
+    /// `byte_offset`, or [`byte_len()`](Self::byte_len()) if there isn't one.
+    ///
+    /// The byte offset doesn't have to lie on a grapheme cluster or [`char`]
+    /// boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("👋\r\naargh!\r\n🐻‍❄️");
+    /// let s = r.line_slice(1..);
+    ///
+    /// assert_eq!(s.next_grapheme_boundary(5), 6);
+    /// assert_eq!(s.next_grapheme_boundary(6), 8);
+    /// assert_eq!(s.next_grapheme_boundary(8), 21);
+    /// assert_eq!(s.next_grapheme_boundary(21), 21);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn next_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        if self.is_char_boundary(byte_offset) {
+            next_grapheme_boundary(*self, byte_offset)
+        } else {
+            self.ceil_grapheme_boundary(byte_offset)
+        }
+    }
+
+    /// Returns the byte offset of the first word boundary after `byte_offset`,
+    /// or [`byte_len()`](Self::byte_len()) if there isn't one.
+    ///
+    /// This only segments the chunks around the byte offset, so it runs in
+    /// logarithmic time unless the segments around it span many chunks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar\r\nbaz");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.next_word_boundary(0), 3);
+    /// assert_eq!(s.next_word_boundary(3), 4);
+    /// assert_eq!(s.next_word_boundary(5), 7);
+    /// assert_eq!(s.next_word_boundary(7), 9);
+    /// assert_eq!(s.next_word_boundary(12), 12);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn next_word_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        next_word_boundary(*self, self.floor_char_boundary(byte_offset))
+    }
+
+    /// Returns the number of paragraphs in the `RopeSlice`, where a paragraph is a
+    /// maximal run of lines containing at least one non-whitespace character.
+    ///
+    /// See [`word_count()`](Self::word_count()) for the time complexity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Lorem ipsum\n\ndolor sit\n\namet\n");
+    /// assert_eq!(r.byte_slice(6..21).paragraph_count(), 2);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "text-stats")))]
+    #[cfg(feature = "text-stats")]
+    #[inline]
+    pub fn paragraph_count(&self) -> usize {
+        self.tree_slice.text_stats().paragraphs()
+    }
+
+    /// Returns the byte offset of the last grapheme cluster boundary before
+    /// `byte_offset`, or zero if there isn't one.
+    ///
+    /// The byte offset doesn't have to lie on a grapheme cluster or [`char`]
+    /// boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("👋\r\naargh!\r\n🐻‍❄️");
+    /// let s = r.line_slice(1..);
+    ///
+    /// assert_eq!(s.prev_grapheme_boundary(21), 8);
+    /// assert_eq!(s.prev_grapheme_boundary(8), 6);
+    /// assert_eq!(s.prev_grapheme_boundary(6), 5);
+    /// assert_eq!(s.prev_grapheme_boundary(0), 0);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn prev_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        if self.is_char_boundary(byte_offset) {
+            prev_grapheme_boundary(*self, byte_offset)
+        } else {
+            self.floor_grapheme_boundary(byte_offset)
+        }
+    }
+
+    /// Returns the byte offset of the last word boundary before `byte_offset`,
+    /// or zero if there isn't one.
+    ///
+    /// See [`next_word_boundary()`](Self::next_word_boundary()) for the time
+    /// complexity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar\r\nbaz");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.prev_word_boundary(12), 9);
+    /// assert_eq!(s.prev_word_boundary(9), 7);
+    /// assert_eq!(s.prev_word_boundary(5), 4);
+    /// assert_eq!(s.prev_word_boundary(3), 0);
+    /// assert_eq!(s.prev_word_boundary(0), 0);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn prev_word_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        prev_word_boundary(*self, self.ceil_char_boundary(byte_offset))
+    }
+
     /// Returns an iterator over the lines of this `RopeSlice`, including the
     /// line terminators.
     ///
@@ -573,6 +989,61 @@ impl<'a> RopeSlice<'a> {
         RawLines::from(self)
     }
 
+    /// Returns an iterator over the sentences of this `RopeSlice`, as defined by
+    /// the [Unicode Standard Annex #29][uax29].
+    ///
+    /// Concatenating all the sentences gives back the whole text.
+    ///
+    /// The text is segmented one chunk at a time, looking into the following
+    /// chunks only as far as needed to find where the last sentences of the
+    /// chunk end. A sentence is only collected into a `String` if it spans
+    /// several chunks, so the iterator never holds more text than a few
+    /// chunks and a few of the longest sentences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hi there. How are you?\nFine.");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let mut sentences = s.sentences();
+    ///
+    /// assert_eq!(Some("Hi there. "), sentences.next().as_deref());
+    /// assert_eq!(Some("How are you?\n"), sentences.next().as_deref());
+    /// assert_eq!(Some("Fine."), sentences.next().as_deref());
+    /// assert_eq!(None, sentences.next());
+    /// ```
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Sentence_Boundaries
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn sentences(&self) -> crate::iter::Sentences<'a> {
+        crate::iter::Sentences::from(self)
+    }
+
+    /// Returns `true` if the `RopeSlice` starts with the given string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar baz");
+    /// let s = r.byte_slice(4..);
+    ///
+    /// assert!(s.starts_with("bar"));
+    /// assert!(s.starts_with(""));
+    /// assert!(!s.starts_with("foo"));
+    /// ```
+    #[inline]
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.byte_len() >= prefix.len()
+            && chunks_start_with(self.chunks(), prefix.as_bytes())
+    }
+
     /// Removes the last char from the range spanned by this slice.
     ///
     /// # Panics
@@ -729,6 +1200,106 @@ impl<'a> RopeSlice<'a> {
 
         self.tree_slice.slice(Utf16Metric(start)..Utf16Metric(end)).into()
     }
+
+    /// Returns an iterator over the segments of this `RopeSlice` separated by
+    /// word boundaries, as defined by the [Unicode Standard Annex
+    /// #29][uax29].
+    ///
+    /// Unlike [`words()`](Self::words()) this also yields the whitespace and
+    /// punctuation between the words, so concatenating all the segments
+    /// gives back the whole text.
+    ///
+    /// The text is segmented one chunk at a time, looking into the following
+    /// chunks only as far as needed to find where the last segments of the
+    /// chunk end. A segment is only collected into a `String` if it spans
+    /// several chunks, so the iterator never holds more text than a few
+    /// chunks and a few of the longest segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello, world!");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let mut bounds = s.word_bounds();
+    ///
+    /// assert_eq!(Some("Hello"), bounds.next().as_deref());
+    /// assert_eq!(Some(","), bounds.next().as_deref());
+    /// assert_eq!(Some(" "), bounds.next().as_deref());
+    /// assert_eq!(Some("world"), bounds.next().as_deref());
+    /// assert_eq!(Some("!"), bounds.next().as_deref());
+    /// assert_eq!(None, bounds.next());
+    /// ```
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn word_bounds(&self) -> crate::iter::WordBounds<'a> {
+        crate::iter::WordBounds::from(self)
+    }
+
+    /// Returns the number of words in the `RopeSlice`, where a word is a maximal
+    /// run of non-whitespace characters, just like the ones yielded by
+    /// [`str::split_whitespace()`].
+    ///
+    /// Only the first and last chunks of the `RopeSlice` are scanned, all the
+    /// chunks in between use the word counts cached in the `Rope`, so this
+    /// runs in logarithmic time once those have been computed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Lorem ipsum dolor");
+    /// assert_eq!(r.byte_slice(3..14).word_count(), 3);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "text-stats")))]
+    #[cfg(feature = "text-stats")]
+    #[inline]
+    pub fn word_count(&self) -> usize {
+        self.tree_slice.text_stats().words()
+    }
+
+    /// Returns an iterator over the words of this `RopeSlice`, as defined by the
+    /// [Unicode Standard Annex #29][uax29].
+    ///
+    /// Segments that don't contain any alphanumeric character, like
+    /// whitespace and punctuation, are skipped.
+    ///
+    /// The text is segmented one chunk at a time, looking into the following
+    /// chunks only as far as needed to find where the last segments of the
+    /// chunk end. A segment is only collected into a `String` if it spans
+    /// several chunks, so the iterator never holds more text than a few
+    /// chunks and a few of the longest segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello, world!\nit's me.");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let mut words = s.words();
+    ///
+    /// assert_eq!(Some("Hello"), words.next().as_deref());
+    /// assert_eq!(Some("world"), words.next().as_deref());
+    /// assert_eq!(Some("it's"), words.next().as_deref());
+    /// assert_eq!(Some("me"), words.next().as_deref());
+    /// assert_eq!(None, words.next());
+    /// ```
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn words(&self) -> crate::iter::Words<'a> {
+        crate::iter::Words::from(self)
+    }
 }
 
 impl<'a> From<TreeSlice<'a, { Rope::arity() }, RopeChunk>> for RopeSlice<'a> {
@@ -836,3 +1407,31 @@ impl core::cmp::PartialEq<RopeSlice<'_>> for alloc::borrow::Cow<'_, str> {
 }
 
 impl core::cmp::Eq for RopeSlice<'_> {}
+
+impl core::hash::Hash for RopeSlice<'_> {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        hash_chunks(self.chunks(), state)
+    }
+}
+
+impl core::cmp::Ord for RopeSlice<'_> {
+    #[inline]
+    fn cmp(&self, rhs: &Self) -> core::cmp::Ordering {
+        chunks_cmp_chunks(self.chunks(), rhs.chunks())
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSlice<'_>> for RopeSlice<'_> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSlice<'_>) -> Option<core::cmp::Ordering> {
+        Some(chunks_cmp_chunks(self.chunks(), rhs.chunks()))
+    }
+}
+
+impl core::cmp::PartialOrd<Rope> for RopeSlice<'_> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Rope) -> Option<core::cmp::Ordering> {
+        Some(chunks_cmp_chunks(self.chunks(), rhs.chunks()))
+    }
+}
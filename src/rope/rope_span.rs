@@ -0,0 +1,739 @@
+use alloc::string::String;
+use core::ops::RangeBounds;
+
+use super::iterators::{Bytes, Chars, Chunks, Lines, RawLines};
+use super::metrics::{ByteMetric, ChunkSummary};
+use super::rope::RopeChunk;
+use super::utils::debug_chunks;
+use super::{Rope, RopeSlice};
+use crate::tree::Tree;
+
+/// An owned, immutable slice of a [`Rope`].
+///
+/// A `RopeSpan` is the owned counterpart of a [`RopeSlice`]: instead of
+/// borrowing the `Rope` it was created from, it keeps the nodes of that
+/// `Rope` alive by holding a reference-counted pointer to them. This means
+/// it's `Send + Sync + 'static`, so it can be sent to other threads or stored
+/// in long-lived data structures without having to copy its text into a new
+/// `Rope`.
+///
+/// Creating a `RopeSpan` from a `Rope` or a `RopeSlice` is `O(1)`, and the
+/// original `Rope` can keep being edited in the meantime without affecting
+/// the contents of the span.
+///
+/// A `RopeSpan` has all the methods of a `RopeSlice`, and most of them work
+/// by first creating a `RopeSlice` over its contents, which takes
+/// `O(log n)`. If you need to call a lot of
+/// them in a row it can be faster to get the slice once via
+/// [`as_slice()`](Self::as_slice()) and use that instead.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::{Rope, RopeSpan};
+/// #
+/// let mut r = Rope::from("Hello, world!");
+///
+/// let span = RopeSpan::from(r.byte_slice(7..12));
+///
+/// r.replace(7..12, "Earth");
+///
+/// let handle = std::thread::spawn(move || span.to_string());
+///
+/// assert_eq!(handle.join().unwrap(), "world");
+/// assert_eq!(r, "Hello, Earth!");
+/// ```
+#[derive(Clone)]
+pub struct RopeSpan {
+    /// The deepest node containing the whole span, i.e. the root of the
+    /// `TreeSlice` the span was created from.
+    pub(super) root: Tree<{ Rope::arity() }, RopeChunk>,
+
+    /// The summary of the subtree under [`root`](Self::root) up to the start
+    /// of the span.
+    pub(super) offset: ChunkSummary,
+
+    /// The total summary of the span.
+    pub(super) summary: ChunkSummary,
+}
+
+impl RopeSpan {
+    #[doc(hidden)]
+    pub fn assert_invariants(&self) {
+        self.root.assert_invariants();
+        self.as_slice().assert_invariants();
+        assert_eq!(self.summary, *self.as_slice().tree_slice.summary());
+    }
+
+    /// Returns a [`RopeSlice`] over the contents of the `RopeSpan`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeSpan};
+    /// #
+    /// let span = RopeSpan::from(Rope::from("foo\nbar"));
+    ///
+    /// let slice = span.as_slice();
+    /// assert_eq!(slice.line(1), "bar");
+    /// ```
+    #[inline]
+    pub fn as_slice(&self) -> RopeSlice<'_> {
+        let start = self.offset.bytes();
+        let end = start + self.summary.bytes();
+        self.root.slice(ByteMetric(start)..ByteMetric(end)).into()
+    }
+
+    /// Returns the byte at `byte_index`.
+    ///
+    /// See [`RopeSlice::byte()`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte index is out of bounds (i.e. greater than or equal
+    /// to [`byte_len()`](Self::byte_len())).
+    #[track_caller]
+    #[inline]
+    pub fn byte(&self, byte_index: usize) -> u8 {
+        self.as_slice().byte(byte_index)
+    }
+
+    /// Returns the length of the `RopeSpan` in bytes.
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        self.summary.bytes()
+    }
+
+    /// Returns the byte offset of the start of the given line.
+    ///
+    /// See [`RopeSlice::byte_of_line()`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line offset is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_line(&self, line_offset: usize) -> usize {
+        self.as_slice().byte_of_line(line_offset)
+    }
+
+    /// Returns the byte offset corresponding to the given UTF-16 code unit
+    /// offset.
+    ///
+    /// See [`RopeSlice::byte_of_utf16_code_unit()`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the UTF-16 code unit offset is out of bounds (i.e. greater
+    /// than [`utf16_len()`](Self::utf16_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_utf16_code_unit(&self, utf16_offset: usize) -> usize {
+        self.as_slice().byte_of_utf16_code_unit(utf16_offset)
+    }
+
+    /// Returns a sub-slice of the `RopeSpan` in the specified byte range,
+    /// where the start and end of the range are interpreted as offsets.
+    ///
+    /// See [`RopeSlice::byte_slice()`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    #[track_caller]
+    #[inline]
+    pub fn byte_slice<R>(&self, byte_range: R) -> RopeSlice<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.as_slice().byte_slice(byte_range)
+    }
+
+    /// Returns an iterator over the bytes of this `RopeSpan`.
+    #[inline]
+    pub fn bytes(&self) -> Bytes<'_> {
+        self.as_slice().bytes()
+    }
+
+    /// Returns the smallest byte offset greater than or equal to `byte_offset`
+    /// that lies on a [`char`] boundary.
+    ///
+    /// See [`RopeSlice::ceil_char_boundary()`] for more details.
+    #[inline]
+    pub fn ceil_char_boundary(&self, byte_offset: usize) -> usize {
+        self.as_slice().ceil_char_boundary(byte_offset)
+    }
+
+    /// Returns the smallest byte offset greater than or equal to `byte_offset`
+    /// that lies on a grapheme cluster boundary.
+    ///
+    /// See [`RopeSlice::ceil_grapheme_boundary()`] for more details.
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn ceil_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        self.as_slice().ceil_grapheme_boundary(byte_offset)
+    }
+
+    /// Returns the number of `char`s in the `RopeSpan`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "text-stats")))]
+    #[cfg(feature = "text-stats")]
+    #[inline]
+    pub fn char_count(&self) -> usize {
+        self.summary.chars()
+    }
+
+    /// Returns an iterator over the [`char`]s of this `RopeSpan`.
+    #[inline]
+    pub fn chars(&self) -> Chars<'_> {
+        self.as_slice().chars()
+    }
+
+    /// Returns an iterator over the chunks of this `RopeSpan`.
+    #[inline]
+    pub fn chunks(&self) -> Chunks<'_> {
+        self.as_slice().chunks()
+    }
+
+    /// Returns a 64-bit hash of the text of the `RopeSpan`.
+    ///
+    /// See [`Rope::content_hash()`] for more details.
+    #[cfg_attr(docsrs, doc(cfg(feature = "content-hash")))]
+    #[cfg(feature = "content-hash")]
+    #[inline]
+    pub fn content_hash(&self) -> u64 {
+        self.as_slice().content_hash()
+    }
+
+    /// Returns `true` if the `RopeSpan` ends with the given string.
+    #[inline]
+    pub fn ends_with(&self, suffix: &str) -> bool {
+        self.as_slice().ends_with(suffix)
+    }
+
+    /// Returns `true` if the `RopeSpan` and the given string are equal when
+    /// ignoring the case of ASCII letters.
+    ///
+    /// See [`RopeSlice::eq_ignore_ascii_case()`] for more details.
+    #[inline]
+    pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        self.as_slice().eq_ignore_ascii_case(other)
+    }
+
+    /// Returns `true` if the `RopeSpan` and the given string are canonically
+    /// equivalent, i.e. if they're equal after being normalized.
+    ///
+    /// See [`RopeSlice::eq_normalized()`] for more details.
+    #[cfg_attr(docsrs, doc(cfg(feature = "normalization")))]
+    #[cfg(feature = "normalization")]
+    #[inline]
+    pub fn eq_normalized(&self, other: &str) -> bool {
+        self.as_slice().eq_normalized(other)
+    }
+
+    /// Returns the largest byte offset less than or equal to `byte_offset`
+    /// that lies on a [`char`] boundary.
+    ///
+    /// See [`RopeSlice::floor_char_boundary()`] for more details.
+    #[inline]
+    pub fn floor_char_boundary(&self, byte_offset: usize) -> usize {
+        self.as_slice().floor_char_boundary(byte_offset)
+    }
+
+    /// Returns the largest byte offset less than or equal to `byte_offset`
+    /// that lies on a grapheme cluster boundary.
+    ///
+    /// See [`RopeSlice::floor_grapheme_boundary()`] for more details.
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn floor_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        self.as_slice().floor_grapheme_boundary(byte_offset)
+    }
+
+    /// Returns an iterator over the extended grapheme clusters of this
+    /// `RopeSpan`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn graphemes(&self) -> crate::iter::Graphemes<'_> {
+        self.as_slice().graphemes()
+    }
+
+    /// Returns `true` if the given byte offset lies on a [`char`] boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    #[track_caller]
+    #[inline]
+    pub fn is_char_boundary(&self, byte_offset: usize) -> bool {
+        self.as_slice().is_char_boundary(byte_offset)
+    }
+
+    /// Returns `true` if the `RopeSpan`'s byte length is zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.byte_len() == 0
+    }
+
+    /// Returns `true` if the given byte offset lies on a grapheme cluster
+    /// boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn is_grapheme_boundary(&self, byte_offset: usize) -> bool {
+        self.as_slice().is_grapheme_boundary(byte_offset)
+    }
+
+    /// Returns the line at `line_index`, without its line terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line index is out of bounds (i.e. greater than or equal
+    /// to [`line_len()`](Self::line_len())).
+    #[track_caller]
+    #[inline]
+    pub fn line(&self, line_index: usize) -> RopeSlice<'_> {
+        self.as_slice().line(line_index)
+    }
+
+    /// Returns the number of lines in the `RopeSpan`.
+    ///
+    /// See [`RopeSlice::line_len()`] for more details.
+    #[inline]
+    pub fn line_len(&self) -> usize {
+        self.as_slice().line_len()
+    }
+
+    /// Returns the line offset of the given byte.
+    ///
+    /// See [`RopeSlice::line_of_byte()`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    #[track_caller]
+    #[inline]
+    pub fn line_of_byte(&self, byte_offset: usize) -> usize {
+        self.as_slice().line_of_byte(byte_offset)
+    }
+
+    /// Returns a sub-slice of the `RopeSpan` in the specified line range,
+    /// where the start and end of the range are interpreted as offsets.
+    ///
+    /// See [`RopeSlice::line_slice()`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`line_len()`](Self::line_len())).
+    #[track_caller]
+    #[inline]
+    pub fn line_slice<R>(&self, line_range: R) -> RopeSlice<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.as_slice().line_slice(line_range)
+    }
+
+    /// Returns an iterator over the lines of this `RopeSpan`, not including
+    /// the line terminators.
+    #[inline]
+    pub fn lines(&self) -> Lines<'_> {
+        self.as_slice().lines()
+    }
+
+    /// Returns the byte offset of the first grapheme cluster boundary after
+    /// `byte_offset`, or [`byte_len()`](Self::byte_len()) if there isn't one.
+    ///
+    /// See [`RopeSlice::next_grapheme_boundary()`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn next_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        self.as_slice().next_grapheme_boundary(byte_offset)
+    }
+
+    /// Returns the byte offset of the first word boundary after `byte_offset`,
+    /// or [`byte_len()`](Self::byte_len()) if there isn't one.
+    ///
+    /// See [`RopeSlice::next_word_boundary()`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn next_word_boundary(&self, byte_offset: usize) -> usize {
+        self.as_slice().next_word_boundary(byte_offset)
+    }
+
+    /// Returns the number of paragraphs in the `RopeSpan`.
+    ///
+    /// See [`RopeSlice::paragraph_count()`] for more details.
+    #[cfg_attr(docsrs, doc(cfg(feature = "text-stats")))]
+    #[cfg(feature = "text-stats")]
+    #[inline]
+    pub fn paragraph_count(&self) -> usize {
+        self.as_slice().paragraph_count()
+    }
+
+    /// Returns the byte offset of the last grapheme cluster boundary before
+    /// `byte_offset`, or zero if there isn't one.
+    ///
+    /// See [`RopeSlice::prev_grapheme_boundary()`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn prev_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        self.as_slice().prev_grapheme_boundary(byte_offset)
+    }
+
+    /// Returns the byte offset of the last word boundary before `byte_offset`,
+    /// or zero if there isn't one.
+    ///
+    /// See [`RopeSlice::prev_word_boundary()`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn prev_word_boundary(&self, byte_offset: usize) -> usize {
+        self.as_slice().prev_word_boundary(byte_offset)
+    }
+
+    /// Returns an iterator over the lines of this `RopeSpan`, including the
+    /// line terminators.
+    #[inline]
+    pub fn raw_lines(&self) -> RawLines<'_> {
+        self.as_slice().raw_lines()
+    }
+
+    /// Returns an iterator over the sentences of this `RopeSpan`.
+    ///
+    /// See [`RopeSlice::sentences()`] for more details.
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn sentences(&self) -> crate::iter::Sentences<'_> {
+        self.as_slice().sentences()
+    }
+
+    /// Returns `true` if the `RopeSpan` starts with the given string.
+    #[inline]
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.as_slice().starts_with(prefix)
+    }
+
+    /// Returns the number of UTF-16 code units the `RopeSpan` would have if
+    /// it stored its text as UTF-16 instead of UTF-8.
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[inline]
+    pub fn utf16_len(&self) -> usize {
+        self.summary.utf16_code_units()
+    }
+
+    /// Returns the UTF-16 code unit offset corresponding to the given byte
+    /// offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn utf16_code_unit_of_byte(&self, byte_offset: usize) -> usize {
+        self.as_slice().utf16_code_unit_of_byte(byte_offset)
+    }
+
+    /// Returns a sub-slice of the `RopeSpan` in the specified UTF-16 code
+    /// unit range, where the start and end of the range are interpreted as
+    /// offsets.
+    ///
+    /// See [`RopeSlice::utf16_slice()`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`utf16_len()`](Self::utf16_len())).
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn utf16_slice<R>(&self, utf16_range: R) -> RopeSlice<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.as_slice().utf16_slice(utf16_range)
+    }
+
+    /// Returns an iterator over the word boundaries of this `RopeSpan`.
+    ///
+    /// See [`RopeSlice::word_bounds()`] for more details.
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn word_bounds(&self) -> crate::iter::WordBounds<'_> {
+        self.as_slice().word_bounds()
+    }
+
+    /// Returns the number of words in the `RopeSpan`.
+    ///
+    /// See [`RopeSlice::word_count()`] for more details.
+    #[cfg_attr(docsrs, doc(cfg(feature = "text-stats")))]
+    #[cfg(feature = "text-stats")]
+    #[inline]
+    pub fn word_count(&self) -> usize {
+        self.as_slice().word_count()
+    }
+
+    /// Returns an iterator over the words of this `RopeSpan`.
+    ///
+    /// See [`RopeSlice::words()`] for more details.
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn words(&self) -> crate::iter::Words<'_> {
+        self.as_slice().words()
+    }
+}
+
+impl Default for RopeSpan {
+    #[inline]
+    fn default() -> Self {
+        Self::from(Rope::new())
+    }
+}
+
+impl From<Rope> for RopeSpan {
+    #[inline]
+    fn from(rope: Rope) -> Self {
+        Self {
+            summary: *rope.tree.summary(),
+            offset: ChunkSummary::new(),
+            root: rope.tree,
+        }
+    }
+}
+
+impl From<&Rope> for RopeSpan {
+    #[inline]
+    fn from(rope: &Rope) -> Self {
+        Self::from(rope.clone())
+    }
+}
+
+impl From<RopeSlice<'_>> for RopeSpan {
+    #[inline]
+    fn from(rope_slice: RopeSlice<'_>) -> Self {
+        let (root, offset) = rope_slice.tree_slice.root_tree();
+        Self { root, offset, summary: *rope_slice.tree_slice.summary() }
+    }
+}
+
+impl From<&RopeSpan> for Rope {
+    #[inline]
+    fn from(rope_span: &RopeSpan) -> Rope {
+        Rope::from(rope_span.as_slice())
+    }
+}
+
+impl From<RopeSpan> for Rope {
+    #[inline]
+    fn from(rope_span: RopeSpan) -> Rope {
+        Rope::from(&rope_span)
+    }
+}
+
+impl core::fmt::Debug for RopeSpan {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("RopeSpan(\"")?;
+        debug_chunks(self.chunks(), f)?;
+        f.write_str("\")")
+    }
+}
+
+impl core::fmt::Display for RopeSpan {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.as_slice(), f)
+    }
+}
+
+impl core::cmp::PartialEq<RopeSpan> for RopeSpan {
+    #[inline]
+    fn eq(&self, rhs: &RopeSpan) -> bool {
+        self.as_slice() == rhs.as_slice()
+    }
+}
+
+impl core::cmp::PartialEq<RopeSlice<'_>> for RopeSpan {
+    #[inline]
+    fn eq(&self, rhs: &RopeSlice<'_>) -> bool {
+        self.as_slice() == *rhs
+    }
+}
+
+impl core::cmp::PartialEq<RopeSpan> for RopeSlice<'_> {
+    #[inline]
+    fn eq(&self, rhs: &RopeSpan) -> bool {
+        rhs == self
+    }
+}
+
+impl core::cmp::PartialEq<Rope> for RopeSpan {
+    #[inline]
+    fn eq(&self, rhs: &Rope) -> bool {
+        self.as_slice() == *rhs
+    }
+}
+
+impl core::cmp::PartialEq<RopeSpan> for Rope {
+    #[inline]
+    fn eq(&self, rhs: &RopeSpan) -> bool {
+        rhs == self
+    }
+}
+
+impl core::cmp::PartialEq<str> for RopeSpan {
+    #[inline]
+    fn eq(&self, rhs: &str) -> bool {
+        self.as_slice() == *rhs
+    }
+}
+
+impl core::cmp::PartialEq<RopeSpan> for str {
+    #[inline]
+    fn eq(&self, rhs: &RopeSpan) -> bool {
+        rhs == self
+    }
+}
+
+impl core::cmp::PartialEq<&str> for RopeSpan {
+    #[inline]
+    fn eq(&self, rhs: &&str) -> bool {
+        self == *rhs
+    }
+}
+
+impl core::cmp::PartialEq<RopeSpan> for &str {
+    #[inline]
+    fn eq(&self, rhs: &RopeSpan) -> bool {
+        rhs == self
+    }
+}
+
+impl core::cmp::PartialEq<String> for RopeSpan {
+    #[inline]
+    fn eq(&self, rhs: &String) -> bool {
+        self == &**rhs
+    }
+}
+
+impl core::cmp::PartialEq<RopeSpan> for String {
+    #[inline]
+    fn eq(&self, rhs: &RopeSpan) -> bool {
+        rhs == self
+    }
+}
+
+impl core::cmp::PartialEq<alloc::borrow::Cow<'_, str>> for RopeSpan {
+    #[inline]
+    fn eq(&self, rhs: &alloc::borrow::Cow<'_, str>) -> bool {
+        self == &**rhs
+    }
+}
+
+impl core::cmp::PartialEq<RopeSpan> for alloc::borrow::Cow<'_, str> {
+    #[inline]
+    fn eq(&self, rhs: &RopeSpan) -> bool {
+        rhs == self
+    }
+}
+
+impl core::cmp::Eq for RopeSpan {}
+
+impl core::hash::Hash for RopeSpan {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl core::cmp::Ord for RopeSpan {
+    #[inline]
+    fn cmp(&self, rhs: &Self) -> core::cmp::Ordering {
+        self.as_slice().cmp(&rhs.as_slice())
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSpan> for RopeSpan {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSpan) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSlice<'_>> for RopeSpan {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSlice<'_>) -> Option<core::cmp::Ordering> {
+        self.as_slice().partial_cmp(rhs)
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSpan> for RopeSlice<'_> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSpan) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(&rhs.as_slice())
+    }
+}
+
+impl core::cmp::PartialOrd<Rope> for RopeSpan {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Rope) -> Option<core::cmp::Ordering> {
+        self.as_slice().partial_cmp(rhs)
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSpan> for Rope {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSpan) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(&rhs.as_slice())
+    }
+}
@@ -0,0 +1,369 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::error::check_char_boundary;
+use super::utils::panic_messages as panic;
+use super::{Rope, RopeBuilder};
+
+/// A selection in a [`Rope`], made of an anchor and a head byte offset.
+///
+/// The anchor is where the selection was started and the head is where the
+/// cursor currently is, so the head can come before the anchor. A selection
+/// whose anchor and head are equal is a cursor, and is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Selection {
+    /// The byte offset where the selection was started.
+    pub anchor: usize,
+
+    /// The byte offset of the cursor.
+    pub head: usize,
+}
+
+impl Selection {
+    /// Returns an empty selection at the given byte offset.
+    #[inline]
+    pub const fn cursor(byte_offset: usize) -> Self {
+        Self { anchor: byte_offset, head: byte_offset }
+    }
+
+    /// Returns the larger of the anchor and the head.
+    #[inline]
+    pub fn end(&self) -> usize {
+        self.anchor.max(self.head)
+    }
+
+    /// Returns whether the head comes before the anchor.
+    #[inline]
+    pub fn is_backward(&self) -> bool {
+        self.head < self.anchor
+    }
+
+    /// Returns whether the selection is a cursor, i.e. whether its anchor and
+    /// head are equal.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.anchor == self.head
+    }
+
+    /// Returns a new selection with the given anchor and head.
+    #[inline]
+    pub const fn new(anchor: usize, head: usize) -> Self {
+        Self { anchor, head }
+    }
+
+    /// Returns the byte range covered by the selection.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.start()..self.end()
+    }
+
+    /// Returns the smaller of the anchor and the head.
+    #[inline]
+    pub fn start(&self) -> usize {
+        self.anchor.min(self.head)
+    }
+
+    /// Updates the selection to account for the text in `byte_range` having
+    /// been replaced with `text_len` bytes of new text.
+    ///
+    /// Offsets before or at the start of the range don't move, offsets after
+    /// or at the end of it are shifted by the change in length, and offsets
+    /// inside it are moved to the end of the new text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the byte range is greater than its end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Selection;
+    /// #
+    /// let mut selection = Selection::new(2, 8);
+    ///
+    /// // Replace the 6..10 byte range with 1 byte.
+    /// selection.transform(6..10, 1);
+    /// assert_eq!(selection, Selection::new(2, 7));
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn transform(&mut self, byte_range: Range<usize>, text_len: usize) {
+        let Range { start, end } = byte_range;
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        let transform_offset = |offset: usize| {
+            if offset <= start {
+                offset
+            } else if offset >= end {
+                offset - (end - start) + text_len
+            } else {
+                start + text_len
+            }
+        };
+
+        self.anchor = transform_offset(self.anchor);
+        self.head = transform_offset(self.head);
+    }
+}
+
+/// A set of [`Selection`]s, e.g. the cursors of a multi-cursor editor.
+///
+/// The selections are kept sorted by their start offset, and selections that
+/// overlap or that start at the same offset are merged into one.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::{Rope, Selection, SelectionSet};
+/// #
+/// let mut r = Rope::from("foo\nbar\nbaz\n");
+///
+/// let mut selections =
+///     [0, 4, 8].into_iter().map(Selection::cursor).collect::<SelectionSet>();
+///
+/// selections.insert(&mut r, "- ");
+/// assert_eq!(r, "- foo\n- bar\n- baz\n");
+///
+/// // Selections need to be transformed through edits made elsewhere to keep
+/// // pointing at the same text.
+/// r.insert(0, "# List\n");
+/// selections.transform(0..0, 7);
+///
+/// selections.insert(&mut r, "> ");
+/// assert_eq!(r, "# List\n- > foo\n- > bar\n- > baz\n");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelectionSet {
+    /// The selections, sorted by their start offset and without overlaps.
+    selections: Vec<Selection>,
+}
+
+impl From<Selection> for SelectionSet {
+    #[inline]
+    fn from(selection: Selection) -> Self {
+        Self { selections: alloc::vec![selection] }
+    }
+}
+
+impl FromIterator<Selection> for SelectionSet {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Selection>,
+    {
+        let mut set = Self { selections: iter.into_iter().collect() };
+        set.normalize();
+        set
+    }
+}
+
+impl<'a> IntoIterator for &'a SelectionSet {
+    type Item = &'a Selection;
+    type IntoIter = core::slice::Iter<'a, Selection>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl SelectionSet {
+    /// Returns the selections as a slice, sorted by their start offset.
+    #[inline]
+    pub fn as_slice(&self) -> &[Selection] {
+        &self.selections
+    }
+
+    /// Inserts the same text at every selection in the `Rope`, replacing the
+    /// selected text like typing would, and turns every selection into a
+    /// cursor placed after the text it inserted.
+    ///
+    /// The text from the start of the first selection to the end of the last
+    /// one is rebuilt in a single pass and then replaced in the `Rope` with a
+    /// single edit, so this takes time proportional to the length of that
+    /// text no matter how many selections there are.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the end of the last selection is out of bounds (i.e. greater
+    /// than [`Rope::byte_len()`]) or if any of the selections' offsets don't
+    /// lie on a code point boundary. All the offsets are checked before the
+    /// `Rope` is edited, so it's left untouched if this panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, Selection, SelectionSet};
+    /// #
+    /// let mut r = Rope::from("let a = 1;\nlet b = 2;\n");
+    ///
+    /// let mut selections = SelectionSet::from(Selection::new(4, 5));
+    /// selections.push(Selection::new(16, 15));
+    ///
+    /// selections.insert(&mut r, "foo");
+    /// assert_eq!(r, "let foo = 1;\nlet foo = 2;\n");
+    /// assert_eq!(
+    ///     selections.as_slice(),
+    ///     [Selection::cursor(7), Selection::cursor(20)]
+    /// );
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn insert<T>(&mut self, rope: &mut Rope, text: T)
+    where
+        T: AsRef<str>,
+    {
+        let text = text.as_ref();
+
+        let (Some(first), Some(last)) =
+            (self.selections.first(), self.selections.last())
+        else {
+            return;
+        };
+
+        let (start, end) = (first.start(), last.end());
+
+        for selection in &self.selections {
+            for offset in [selection.start(), selection.end()] {
+                if let Err(err) =
+                    check_char_boundary(offset, rope.byte_len(), |idx| {
+                        rope.byte(idx)
+                    })
+                {
+                    panic!("{err}");
+                }
+            }
+        }
+
+        let mut builder = RopeBuilder::new();
+
+        let mut offset = start;
+
+        for selection in &self.selections {
+            for chunk in rope.byte_slice(offset..selection.start()).chunks() {
+                builder.append(chunk);
+            }
+
+            builder.append(text);
+
+            offset = selection.end();
+        }
+
+        let edited = builder.build();
+        rope.replace_with_rope(start..end, edited.byte_slice(..));
+
+        let mut deleted = 0;
+
+        let mut inserted = 0;
+
+        for selection in &mut self.selections {
+            let (start, end) = (selection.start(), selection.end());
+            inserted += text.len();
+            *selection = Selection::cursor(start - deleted + inserted);
+            deleted += end - start;
+        }
+
+        // Inserting an empty string can leave several cursors at the same
+        // offset.
+        if text.is_empty() {
+            self.normalize();
+        }
+    }
+
+    /// Returns whether the set doesn't contain any selections.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.selections.is_empty()
+    }
+
+    /// Returns an iterator over the selections, sorted by their start offset.
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, Selection> {
+        self.selections.iter()
+    }
+
+    /// Returns the number of selections in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.selections.len()
+    }
+
+    /// Returns an empty `SelectionSet`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sorts the selections and merges the ones that overlap or start at the
+    /// same offset.
+    #[inline]
+    fn normalize(&mut self) {
+        self.selections.sort_unstable_by_key(|sel| (sel.start(), sel.end()));
+
+        // `dedup_by()` passes the next selection first and the previous one,
+        // which is the one that's kept, second.
+        self.selections.dedup_by(|next, prev| {
+            let overlaps =
+                next.start() < prev.end() || next.start() == prev.start();
+
+            if overlaps {
+                let (start, end) = (prev.start(), prev.end().max(next.end()));
+
+                // The merged selection keeps the direction of the first one.
+                *prev = if prev.is_backward() {
+                    Selection::new(end, start)
+                } else {
+                    Selection::new(start, end)
+                };
+            }
+
+            overlaps
+        });
+    }
+
+    /// Adds a selection to the set, merging it with the selections it
+    /// overlaps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Selection, SelectionSet};
+    /// #
+    /// let mut selections = SelectionSet::from(Selection::new(2, 6));
+    ///
+    /// selections.push(Selection::cursor(0));
+    /// selections.push(Selection::new(8, 4));
+    ///
+    /// assert_eq!(
+    ///     selections.as_slice(),
+    ///     [Selection::cursor(0), Selection::new(2, 8)]
+    /// );
+    /// ```
+    #[inline]
+    pub fn push(&mut self, selection: Selection) {
+        self.selections.push(selection);
+        self.normalize();
+    }
+
+    /// Updates every selection to account for the text in `byte_range`
+    /// having been replaced with `text_len` bytes of new text, e.g. by a call
+    /// to [`Rope::replace()`], merging the selections that end up overlapping.
+    ///
+    /// See [`Selection::transform()`] for how each offset is moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the byte range is greater than its end.
+    #[track_caller]
+    #[inline]
+    pub fn transform(&mut self, byte_range: Range<usize>, text_len: usize) {
+        for selection in &mut self.selections {
+            selection.transform(byte_range.clone(), text_len);
+        }
+
+        self.normalize();
+    }
+}
@@ -0,0 +1,37 @@
+/// Statistics about the internal structure and memory usage of a
+/// [`Rope`](crate::Rope), returned by [`Rope::stats()`](crate::Rope::stats).
+///
+/// These can be useful to profile the memory footprint of an application
+/// holding many `Rope`s, or to decide when it's worth compacting a `Rope`
+/// that went through a lot of edits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct RopeStats {
+    /// The number of leaves (i.e. chunks of text) in the `Rope`.
+    pub leaf_count: usize,
+
+    /// The depth of the `Rope`'s B-tree, i.e. the number of internal nodes
+    /// between the root and any of the leaves. A `Rope` that fits in a single
+    /// chunk has a depth of zero.
+    pub depth: usize,
+
+    /// The total number of bytes allocated on the heap for the `Rope`,
+    /// including both the text chunks and the internal nodes of the tree.
+    ///
+    /// Memory that's shared with other `Rope`s (see
+    /// [`shared_nodes`](Self::shared_nodes)) is counted as well.
+    pub allocated_bytes: usize,
+
+    /// The number of bytes allocated for the text chunks that don't currently
+    /// hold any text.
+    pub gap_bytes: usize,
+
+    /// The average fill ratio of the leaves, i.e. the number of bytes of text
+    /// divided by the total capacity of all the chunks. This is a number
+    /// between `0.0` and `1.0`.
+    pub fill_ratio: f64,
+
+    /// The number of nodes (both internal nodes and leaves) that are shared
+    /// with at least one other `Rope`, for example because it was cloned.
+    pub shared_nodes: usize,
+}
@@ -0,0 +1,107 @@
+//! This module contains the built-in transforms re-exported in
+//! [`crate::transforms`].
+
+use alloc::borrow::Cow;
+
+#[cfg(feature = "normalization")]
+use unicode_normalization::{is_nfc, is_nfd, UnicodeNormalization};
+
+/// Converts the text to lowercase, like [`str::to_lowercase()`].
+///
+/// # Examples
+///
+/// ```
+/// # use crop::Rope;
+/// #
+/// let mut r = Rope::from("FOO BAR BAZ");
+///
+/// r.map_range(4..7, crop::transforms::to_lowercase);
+/// assert_eq!(r, "FOO bar BAZ");
+/// ```
+#[inline]
+pub fn to_lowercase(s: &str) -> Cow<'_, str> {
+    let is_lowercase = s.chars().all(|ch| {
+        let mut lower = ch.to_lowercase();
+        lower.next() == Some(ch) && lower.next().is_none()
+    });
+
+    if is_lowercase {
+        Cow::Borrowed(s)
+    } else {
+        Cow::Owned(s.to_lowercase())
+    }
+}
+
+/// Converts the text to uppercase, like [`str::to_uppercase()`].
+///
+/// # Examples
+///
+/// ```
+/// # use crop::Rope;
+/// #
+/// let mut r = Rope::from("straße");
+///
+/// r.map_range(.., crop::transforms::to_uppercase);
+/// assert_eq!(r, "STRASSE");
+/// ```
+#[inline]
+pub fn to_uppercase(s: &str) -> Cow<'_, str> {
+    let is_uppercase = s.chars().all(|ch| {
+        let mut upper = ch.to_uppercase();
+        upper.next() == Some(ch) && upper.next().is_none()
+    });
+
+    if is_uppercase {
+        Cow::Borrowed(s)
+    } else {
+        Cow::Owned(s.to_uppercase())
+    }
+}
+
+/// Converts the text to Unicode Normalization Form C (canonical
+/// composition).
+///
+/// # Examples
+///
+/// ```
+/// # use crop::Rope;
+/// #
+/// let mut r = Rope::from("cafe\u{301}");
+///
+/// r.map_range(.., crop::transforms::to_nfc);
+/// assert_eq!(r, "caf\u{e9}");
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "normalization")))]
+#[cfg(feature = "normalization")]
+#[inline]
+pub fn to_nfc(s: &str) -> Cow<'_, str> {
+    if is_nfc(s) {
+        Cow::Borrowed(s)
+    } else {
+        Cow::Owned(s.nfc().collect())
+    }
+}
+
+/// Converts the text to Unicode Normalization Form D (canonical
+/// decomposition).
+///
+/// # Examples
+///
+/// ```
+/// # use crop::Rope;
+/// #
+/// let mut r = Rope::from("caf\u{e9}");
+///
+/// r.map_range(.., crop::transforms::to_nfd);
+/// assert_eq!(r, "cafe\u{301}");
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "normalization")))]
+#[cfg(feature = "normalization")]
+#[inline]
+pub fn to_nfd(s: &str) -> Cow<'_, str> {
+    if is_nfd(s) {
+        Cow::Borrowed(s)
+    } else {
+        Cow::Owned(s.nfd().collect())
+    }
+}
@@ -1,21 +1,45 @@
 //! This module contains utility functions on strings and code to be shared
 //! between `Rope`s and `RopeSlice`s, `RopeChunk`s and `ChunkSlice`s.
 
+#[cfg(feature = "graphemes")]
+use alloc::borrow::Cow;
+#[cfg(feature = "graphemes")]
+use alloc::string::ToString;
+#[cfg(feature = "graphemes")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::hash::Hasher;
+#[cfg(feature = "graphemes")]
+use core::ops::Range;
+
+#[cfg(feature = "graphemes")]
+use unicode_segmentation::{
+    GraphemeCursor,
+    GraphemeIncomplete,
+    UnicodeSegmentation,
+};
+
+use super::gap_buffer::Contents;
 use super::iterators::Chunks;
+#[cfg(feature = "graphemes")]
+use super::RopeSlice;
 
-/// Adjusts the candidate byte offset to make sure it's a char boundary for
-/// `s`. Offsets past the end of the string will be clipped to the length of
-/// the string.
+/// Adjusts the candidate byte offset to make sure it's a boundary of `s`,
+/// i.e. a char boundary if `s` is a `str`. Offsets past the end of the string
+/// will be clipped to the length of the string.
 ///
-/// If the initial candidate is not on a char boundary we can either go left or
+/// If the initial candidate is not on a boundary we can either go left or
 /// right until it is. The direction is chosen based on the value of
 /// `WITH_RIGHT_BIAS`: true => go right, false => go left.
 ///
 /// In every case the adjusted split point will be within ± 3 bytes from the
 /// initial candidate.
 #[inline]
-pub(super) fn adjust_split_point<const WITH_RIGHT_BIAS: bool>(
-    s: &str,
+pub(super) fn adjust_split_point<
+    const WITH_RIGHT_BIAS: bool,
+    T: ?Sized + Contents,
+>(
+    s: &T,
     candidate: usize,
 ) -> usize {
     if candidate >= s.len() {
@@ -25,11 +49,11 @@ pub(super) fn adjust_split_point<const WITH_RIGHT_BIAS: bool>(
     let mut offset = candidate;
 
     if WITH_RIGHT_BIAS {
-        while !s.is_char_boundary(offset) {
+        while !s.is_boundary(offset) {
             offset += 1;
         }
     } else {
-        while !s.is_char_boundary(offset) {
+        while !s.is_boundary(offset) {
             offset -= 1;
         }
     }
@@ -37,19 +61,95 @@ pub(super) fn adjust_split_point<const WITH_RIGHT_BIAS: bool>(
     offset
 }
 
+/// Lexicographically compares the chunks yielded by iterating over two
+/// [`Chunks`] as if they were two contiguous strings.
+///
+/// This is used in the `Ord` and `PartialOrd` implementations of `Rope`s and
+/// `RopeSlice`s, and it returns the same ordering as comparing the two `str`s
+/// obtained by concatenating the chunks.
+#[inline]
+pub(super) fn chunks_cmp_chunks(
+    mut lhs: Chunks<'_>,
+    mut rhs: Chunks<'_>,
+) -> Ordering {
+    let mut left_chunk: &[u8] = &[];
+    let mut right_chunk: &[u8] = &[];
+
+    loop {
+        while left_chunk.is_empty() {
+            match lhs.next() {
+                Some(chunk) => left_chunk = chunk.as_bytes(),
+                None => break,
+            }
+        }
+
+        while right_chunk.is_empty() {
+            match rhs.next() {
+                Some(chunk) => right_chunk = chunk.as_bytes(),
+                None => break,
+            }
+        }
+
+        match (left_chunk.is_empty(), right_chunk.is_empty()) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => return Ordering::Less,
+            (false, true) => return Ordering::Greater,
+            (false, false) => {},
+        }
+
+        let len = left_chunk.len().min(right_chunk.len());
+
+        match left_chunk[..len].cmp(&right_chunk[..len]) {
+            Ordering::Equal => {
+                left_chunk = &left_chunk[len..];
+                right_chunk = &right_chunk[len..];
+            },
+
+            ordering => return ordering,
+        }
+    }
+}
+
+/// Returns whether the string obtained by concatenating the chunks yielded by
+/// a [`Chunks`] ends with the given bytes, iterating over the chunks from the
+/// back.
+#[inline]
+pub(super) fn chunks_end_with(chunks: Chunks<'_>, mut suffix: &[u8]) -> bool {
+    for chunk in chunks.rev() {
+        if suffix.is_empty() {
+            return true;
+        }
+
+        let chunk = chunk.as_bytes();
+        let len = chunk.len().min(suffix.len());
+
+        if chunk[chunk.len() - len..] != suffix[suffix.len() - len..] {
+            return false;
+        }
+
+        suffix = &suffix[..suffix.len() - len];
+    }
+
+    suffix.is_empty()
+}
+
 /// Checks equality between the chunks yielded by iterating over two
-/// [`Chunks`].
+/// [`Chunks`], or over the chunks of two `ByteRope`s or `ByteSlice`s.
 ///
 /// This is used in the `PartialEq` implementation between `Rope`s and
 /// `RopeSlice`s. It's assumed that if we get this far both chunks yield the
 /// same number of bytes.
 #[inline]
-pub(super) fn chunks_eq_chunks(
-    mut lhs: Chunks<'_>,
-    mut rhs: Chunks<'_>,
-) -> bool {
-    let mut left_chunk = lhs.next().unwrap_or("").as_bytes();
-    let mut right_chunk = rhs.next().unwrap_or("").as_bytes();
+pub(super) fn chunks_eq_chunks<'a, 'b, L, R>(
+    mut lhs: impl Iterator<Item = &'a L>,
+    mut rhs: impl Iterator<Item = &'b R>,
+) -> bool
+where
+    L: AsRef<[u8]> + ?Sized + 'a,
+    R: AsRef<[u8]> + ?Sized + 'b,
+{
+    let mut left_chunk = lhs.next().map_or(&[][..], AsRef::as_ref);
+    let mut right_chunk = rhs.next().map_or(&[][..], AsRef::as_ref);
 
     loop {
         if left_chunk.len() < right_chunk.len() {
@@ -68,7 +168,7 @@ pub(super) fn chunks_eq_chunks(
 
         if left_chunk.is_empty() {
             match lhs.next() {
-                Some(chunk) => left_chunk = chunk.as_bytes(),
+                Some(chunk) => left_chunk = chunk.as_ref(),
 
                 // This works because both chunks are assumed to yield the same
                 // number of bytes, so if one iterator is done then so is the
@@ -79,7 +179,7 @@ pub(super) fn chunks_eq_chunks(
 
         if right_chunk.is_empty() {
             match rhs.next() {
-                Some(chunk) => right_chunk = chunk.as_bytes(),
+                Some(chunk) => right_chunk = chunk.as_ref(),
 
                 // Same as above.
                 _ => return true,
@@ -107,6 +207,117 @@ pub(super) fn chunks_eq_str(chunks: Chunks<'_>, s: &str) -> bool {
     true
 }
 
+/// Same as [`chunks_eq_str`], except ASCII letters are compared ignoring
+/// their case.
+#[inline]
+pub(super) fn chunks_eq_str_ignore_ascii_case(
+    chunks: Chunks<'_>,
+    s: &str,
+) -> bool {
+    let s = s.as_bytes();
+    let mut checked = 0;
+    for chunk in chunks {
+        let chunk = chunk.as_bytes();
+        if !chunk.eq_ignore_ascii_case(&s[checked..(checked + chunk.len())]) {
+            return false;
+        }
+        checked += chunk.len();
+    }
+    true
+}
+
+/// Returns whether the string obtained by concatenating the chunks yielded by
+/// a [`Chunks`] starts with the given bytes.
+#[inline]
+pub(super) fn chunks_start_with(
+    chunks: Chunks<'_>,
+    mut prefix: &[u8],
+) -> bool {
+    for chunk in chunks {
+        if prefix.is_empty() {
+            return true;
+        }
+
+        let chunk = chunk.as_bytes();
+        let len = chunk.len().min(prefix.len());
+
+        if chunk[..len] != prefix[..len] {
+            return false;
+        }
+
+        prefix = &prefix[len..];
+    }
+
+    prefix.is_empty()
+}
+
+/// Returns `1` if the byte is an opening bracket, `-1` if it's a closing one
+/// and `0` otherwise.
+///
+/// Brackets are ASCII characters so they can never be part of a multi-byte
+/// character, and we can look at single bytes without decoding them.
+#[cfg(feature = "bracket-depth")]
+#[inline]
+pub(super) fn bracket_delta(byte: u8) -> isize {
+    match byte {
+        b'(' | b'[' | b'{' => 1,
+        b')' | b']' | b'}' => -1,
+        _ => 0,
+    }
+}
+
+/// Returns the closing bracket matching the given opening one, or the opening
+/// bracket matching the given closing one.
+#[cfg(feature = "bracket-depth")]
+#[inline]
+pub(super) fn matching_bracket_byte(byte: u8) -> Option<u8> {
+    match byte {
+        b'(' => Some(b')'),
+        b'[' => Some(b']'),
+        b'{' => Some(b'}'),
+        b')' => Some(b'('),
+        b']' => Some(b'['),
+        b'}' => Some(b'{'),
+        _ => None,
+    }
+}
+
+/// Converts a column range into its start and end, where an unbounded end is
+/// treated as `usize::MAX`.
+///
+/// # Panics
+///
+/// Panics if the start is greater than the end.
+#[track_caller]
+#[inline]
+pub(super) fn column_range_to_start_end<R>(column_range: R) -> (usize, usize)
+where
+    R: core::ops::RangeBounds<usize>,
+{
+    let (start, end) =
+        crate::range_bounds_to_start_end(column_range, 0, usize::MAX);
+
+    if start > end {
+        panic_messages::column_start_after_end(start, end);
+    }
+
+    (start, end)
+}
+
+/// Returns the contents of the slice as a single `&str` if it's made of a
+/// single chunk, or collects them into a `String` otherwise.
+#[cfg(feature = "graphemes")]
+#[inline]
+pub(super) fn contiguous_str(slice: RopeSlice<'_>) -> Cow<'_, str> {
+    let mut chunks = slice.chunks();
+
+    match (chunks.next(), chunks.next()) {
+        (None, _) => Cow::Borrowed(""),
+        (Some(chunk), None) => Cow::Borrowed(chunk),
+        _ => Cow::Owned(slice.to_string()),
+    }
+}
+
 /// Iterates over the string slices yielded by [`Chunks`], writing the debug
 /// output of each chunk to a formatter.
 #[inline]
@@ -145,52 +356,291 @@ pub(super) fn debug_no_quotes(
     f.write_str(&s[written..])
 }
 
-/// Returns whether `byte_offset` is a grapheme boundary in the string
-/// constructed by concatenating the chunks yielded by `chunks`.
+/// Returns whether the character is neither cased nor case-ignorable, as
+/// defined by the Unicode Standard, so that changing the case of the text on
+/// either side of it never depends on the text on the other side. That's the
+/// case for whitespace, control characters, ASCII digits and most ASCII
+/// punctuation.
+///
+/// For example, [`str::to_lowercase()`] maps a `'Σ'` to a final `'ς'` or to a
+/// `'σ'` by looking at the letters around it, skipping over any apostrophe,
+/// period or combining mark in between.
+#[cfg(feature = "graphemes")]
+#[inline]
+fn ends_case_context(ch: char) -> bool {
+    ch.is_whitespace()
+        || (ch.is_ascii()
+            && !ch.is_ascii_alphabetic()
+            && !matches!(ch, '\'' | '.' | ':' | '^' | '`'))
+}
+
+/// Returns the new end of a window that doesn't have enough context after
+/// its segments, doubling its length without going past `max`.
+#[cfg(feature = "graphemes")]
+#[inline]
+pub(super) fn grow_end(
+    slice: RopeSlice<'_>,
+    window: &Range<usize>,
+    max: usize,
+) -> usize {
+    debug_assert!(window.end < max);
+    let end = window.end + window.len().max(1);
+    slice.ceil_char_boundary(end.min(max))
+}
+
+/// Returns the new start of a window that doesn't have enough context before
+/// its segments, doubling its length without going before `min`.
+#[cfg(feature = "graphemes")]
+#[inline]
+pub(super) fn grow_start(
+    slice: RopeSlice<'_>,
+    window: &Range<usize>,
+    min: usize,
+) -> usize {
+    debug_assert!(window.start > min);
+    let start = window.start.saturating_sub(window.len().max(1));
+    slice.floor_char_boundary(start.max(min))
+}
+
+/// Feeds the chunks yielded by a [`Chunks`] to the hasher in the same way a
+/// `str` containing their concatenation would be.
+///
+/// `Hasher`s are free to return different hashes if the same bytes are split
+/// differently across `write()` calls, so instead of writing each chunk
+/// directly we write the bytes in fixed-size blocks. This makes the hash
+/// independent of how the text is split into chunks for every `Hasher`, and
+/// equal to the hash of the corresponding `str` for the ones that don't
+/// depend on the `write()` boundaries (like the default `Hasher` used by
+/// `HashMap`s).
+#[inline]
+pub(super) fn hash_chunks<H: Hasher>(chunks: Chunks<'_>, state: &mut H) {
+    const BLOCK_SIZE: usize = 256;
+
+    let mut block = [0u8; BLOCK_SIZE];
+
+    let mut block_len = 0;
+
+    for chunk in chunks {
+        let mut bytes = chunk.as_bytes();
+
+        while !bytes.is_empty() {
+            if block_len == 0 && bytes.len() >= BLOCK_SIZE {
+                let (full_block, rest) = bytes.split_at(BLOCK_SIZE);
+                state.write(full_block);
+                bytes = rest;
+                continue;
+            }
+
+            let len = (BLOCK_SIZE - block_len).min(bytes.len());
+
+            block[block_len..block_len + len].copy_from_slice(&bytes[..len]);
+
+            block_len += len;
+
+            bytes = &bytes[len..];
+
+            if block_len == BLOCK_SIZE {
+                state.write(&block);
+                block_len = 0;
+            }
+        }
+    }
+
+    state.write(&block[..block_len]);
+
+    // This is what `str`'s `Hash` implementation does to make the hash
+    // prefix-free. `0xff` can never appear in valid UTF-8.
+    state.write_u8(0xff);
+}
+
+/// Returns whether `byte_offset` is a grapheme boundary in the given slice.
+///
+/// The chunks around the offset are found by slicing the `RopeSlice` instead
+/// of iterating over all of its chunks, so this runs in `O(log n)` as long as
+/// the grapheme cluster containing the offset doesn't span too many chunks.
 #[cfg(feature = "graphemes")]
 #[inline]
 pub(super) fn is_grapheme_boundary(
-    mut chunks: Chunks<'_>,
-    byte_len: usize,
+    slice: RopeSlice<'_>,
     byte_offset: usize,
 ) -> bool {
-    use unicode_segmentation::{GraphemeCursor, GraphemeIncomplete};
+    debug_assert!(byte_offset <= slice.byte_len());
 
-    debug_assert!(byte_offset <= byte_len);
+    if byte_offset == 0 || byte_offset == slice.byte_len() {
+        return true;
+    }
+
+    if !slice.is_char_boundary(byte_offset) {
+        return false;
+    }
+
+    let mut cursor = GraphemeCursor::new(byte_offset, slice.byte_len(), true);
 
-    let mut cursor = GraphemeCursor::new(0, byte_len, true);
-    cursor.set_cursor(byte_offset);
+    let chunk = slice.byte_slice(byte_offset..).chunks().next().unwrap();
 
-    let mut bytes_left = byte_len;
+    loop {
+        match cursor.is_boundary(chunk, byte_offset) {
+            Ok(is_boundary) => return is_boundary,
+
+            Err(GraphemeIncomplete::PreContext(offset)) => {
+                provide_context(&mut cursor, slice, offset)
+            },
 
-    // Iterate from the back until we reach the chunk containing the given byte
-    // index.
-    //
-    // TODO: we need something like `Rope{Slice}::chunks_{from,up_to}_byte()`
-    // if we want to make this fast.
-    let chunk = loop {
-        let chunk = chunks.next_back().unwrap();
-        bytes_left -= chunk.len();
-        if bytes_left <= byte_offset {
-            break chunk;
+            _ => unreachable!(),
         }
-    };
+    }
+}
 
-    if !chunk.is_char_boundary(byte_offset - bytes_left) {
-        return false;
+/// Returns the byte offset of the end of the chunk containing `byte_offset`,
+/// without going past `max`.
+#[cfg(feature = "graphemes")]
+#[inline]
+pub(super) fn next_chunk_end(
+    slice: RopeSlice<'_>,
+    byte_offset: usize,
+    max: usize,
+) -> usize {
+    let chunk = slice.byte_slice(byte_offset..max).chunks().next();
+    byte_offset + chunk.map_or(0, str::len)
+}
+
+/// Returns the byte offset of the first grapheme boundary after
+/// `byte_offset`, or the byte length of the slice if there's none.
+///
+/// The offset must lie on a char boundary.
+#[cfg(feature = "graphemes")]
+#[inline]
+pub(super) fn next_grapheme_boundary(
+    slice: RopeSlice<'_>,
+    byte_offset: usize,
+) -> usize {
+    debug_assert!(slice.is_char_boundary(byte_offset));
+
+    let mut cursor = GraphemeCursor::new(byte_offset, slice.byte_len(), true);
+
+    let mut chunks = slice.byte_slice(byte_offset..).chunks();
+
+    let mut chunk = chunks.next().unwrap_or_default();
+
+    let mut chunk_start = byte_offset;
+
+    loop {
+        match cursor.next_boundary(chunk, chunk_start) {
+            Ok(boundary) => return boundary.unwrap_or(slice.byte_len()),
+
+            Err(GraphemeIncomplete::NextChunk) => {
+                chunk_start += chunk.len();
+                chunk = chunks.next().unwrap();
+            },
+
+            Err(GraphemeIncomplete::PreContext(offset)) => {
+                provide_context(&mut cursor, slice, offset)
+            },
+
+            _ => unreachable!(),
+        }
     }
+}
+
+/// Returns the byte offset of the first word boundary after `byte_offset`,
+/// or the byte length of the slice if there's none.
+///
+/// Only a window of a few chunks around the offset is segmented, and it's
+/// only grown if the boundary falls too close to one of its edges.
+#[cfg(feature = "graphemes")]
+#[inline]
+pub(super) fn next_word_boundary(
+    slice: RopeSlice<'_>,
+    byte_offset: usize,
+) -> usize {
+    debug_assert!(byte_offset <= slice.byte_len());
+
+    if byte_offset == slice.byte_len() {
+        return byte_offset;
+    }
+
+    let bounds = 0..slice.byte_len();
 
-    let chunk_start = bytes_left;
+    let mut window = prev_chunk_start(slice, 0, byte_offset)
+        ..next_chunk_end(slice, byte_offset, slice.byte_len());
 
     loop {
-        match cursor.is_boundary(chunk, chunk_start) {
-            Ok(is_boundary) => return is_boundary,
+        let segmented = SegmentedWindow::new(
+            slice,
+            window.clone(),
+            bounds.clone(),
+            word_boundaries,
+        );
+
+        let boundary = segmented
+            .boundaries
+            .iter()
+            .copied()
+            .find(|&boundary| boundary > byte_offset)
+            .unwrap();
+
+        let has_context_before = segmented.has_context_before(byte_offset + 1);
+
+        let has_context_after = segmented.has_context_after(boundary);
+
+        if has_context_before && has_context_after {
+            return boundary;
+        }
+
+        if !has_context_before {
+            window.start = grow_start(slice, &window, bounds.start);
+        }
+
+        if !has_context_after {
+            window.end = grow_end(slice, &window, bounds.end);
+        }
+    }
+}
+
+/// Returns the byte offset of the start of the chunk containing the byte
+/// before `byte_offset`, without going before `min`.
+#[cfg(feature = "graphemes")]
+#[inline]
+pub(super) fn prev_chunk_start(
+    slice: RopeSlice<'_>,
+    min: usize,
+    byte_offset: usize,
+) -> usize {
+    let chunk = slice.byte_slice(min..byte_offset).chunks().next_back();
+    byte_offset - chunk.map_or(0, str::len)
+}
+
+/// Returns the byte offset of the last grapheme boundary before
+/// `byte_offset`, or zero if there's none.
+///
+/// The offset must lie on a char boundary.
+#[cfg(feature = "graphemes")]
+#[inline]
+pub(super) fn prev_grapheme_boundary(
+    slice: RopeSlice<'_>,
+    byte_offset: usize,
+) -> usize {
+    debug_assert!(slice.is_char_boundary(byte_offset));
+
+    let mut cursor = GraphemeCursor::new(byte_offset, slice.byte_len(), true);
+
+    let mut chunks = slice.byte_slice(..byte_offset).chunks();
+
+    let mut chunk = chunks.next_back().unwrap_or_default();
+
+    let mut chunk_start = byte_offset - chunk.len();
+
+    loop {
+        match cursor.prev_boundary(chunk, chunk_start) {
+            Ok(boundary) => return boundary.unwrap_or(0),
+
+            Err(GraphemeIncomplete::PrevChunk) => {
+                chunk = chunks.next_back().unwrap();
+                chunk_start -= chunk.len();
+            },
 
             Err(GraphemeIncomplete::PreContext(offset)) => {
-                debug_assert_eq!(offset, bytes_left);
-                let prev = chunks.next_back().unwrap();
-                bytes_left -= prev.len();
-                cursor.provide_context(prev, bytes_left);
+                provide_context(&mut cursor, slice, offset)
             },
 
             _ => unreachable!(),
@@ -198,15 +648,237 @@ pub(super) fn is_grapheme_boundary(
     }
 }
 
+/// Returns the byte offset of the last word boundary before `byte_offset`, or
+/// zero if there's none.
+///
+/// See [`next_word_boundary()`] for how much of the slice is segmented.
+#[cfg(feature = "graphemes")]
 #[inline]
-pub(super) fn split_adjusted<const WITH_RIGHT_BIAS: bool>(
-    s: &str,
+pub(super) fn prev_word_boundary(
+    slice: RopeSlice<'_>,
+    byte_offset: usize,
+) -> usize {
+    debug_assert!(byte_offset <= slice.byte_len());
+
+    if byte_offset == 0 {
+        return 0;
+    }
+
+    let bounds = 0..slice.byte_len();
+
+    let mut window = prev_chunk_start(slice, 0, byte_offset)
+        ..next_chunk_end(slice, byte_offset, slice.byte_len());
+
+    loop {
+        let segmented = SegmentedWindow::new(
+            slice,
+            window.clone(),
+            bounds.clone(),
+            word_boundaries,
+        );
+
+        let boundary = segmented
+            .boundaries
+            .iter()
+            .copied()
+            .rfind(|&boundary| boundary < byte_offset)
+            .unwrap();
+
+        let has_context_before = segmented.has_context_before(boundary);
+
+        let has_context_after = segmented.has_context_after(byte_offset - 1);
+
+        if has_context_before && has_context_after {
+            return boundary;
+        }
+
+        if !has_context_before {
+            window.start = grow_start(slice, &window, bounds.start);
+        }
+
+        if !has_context_after {
+            window.end = grow_end(slice, &window, bounds.end);
+        }
+    }
+}
+
+/// Returns the byte offset in `slice` of the last point in `chunk`, which
+/// starts at `chunk_start`, where [`Rope::map_range()`](super::Rope::map_range())
+/// can end a piece, or `None` if there's none.
+///
+/// A piece can only end on a grapheme boundary right after a character that
+/// [ends the case context](ends_case_context()) of the text before it.
+#[cfg(feature = "graphemes")]
+#[inline]
+pub(super) fn last_piece_end(
+    slice: RopeSlice<'_>,
+    chunk: &str,
+    chunk_start: usize,
+) -> Option<usize> {
+    chunk
+        .char_indices()
+        .rev()
+        .filter(|&(_, ch)| ends_case_context(ch))
+        .map(|(offset, ch)| chunk_start + offset + ch.len_utf8())
+        .find(|&offset| slice.is_grapheme_boundary(offset))
+}
+
+/// Feeds the cursor the chunk of `slice` ending at `byte_offset`.
+#[cfg(feature = "graphemes")]
+#[inline]
+fn provide_context(
+    cursor: &mut GraphemeCursor,
+    slice: RopeSlice<'_>,
+    byte_offset: usize,
+) {
+    let chunk = slice.byte_slice(..byte_offset).chunks().next_back().unwrap();
+    cursor.provide_context(chunk, byte_offset - chunk.len());
+}
+
+/// Returns the byte offsets of the sentence boundaries of the string,
+/// including its start and end.
+#[cfg(feature = "graphemes")]
+#[inline]
+pub(super) fn sentence_boundaries(s: &str) -> Vec<usize> {
+    s.split_sentence_bound_indices()
+        .map(|(offset, _)| offset)
+        .chain(core::iter::once(s.len()))
+        .collect()
+}
+
+#[inline]
+pub(super) fn split_adjusted<
+    const WITH_RIGHT_BIAS: bool,
+    T: ?Sized + Contents,
+>(
+    s: &T,
     candidate: usize,
-) -> (&str, &str) {
-    let split_point = adjust_split_point::<WITH_RIGHT_BIAS>(s, candidate);
+) -> (&T, &T) {
+    let split_point = adjust_split_point::<WITH_RIGHT_BIAS, T>(s, candidate);
     (&s[..split_point], &s[split_point..])
 }
 
+/// Returns the byte offsets of the word boundaries of the string, including
+/// its start and end.
+#[cfg(feature = "graphemes")]
+#[inline]
+pub(super) fn word_boundaries(s: &str) -> Vec<usize> {
+    s.split_word_bound_indices()
+        .map(|(offset, _)| offset)
+        .chain(core::iter::once(s.len()))
+        .collect()
+}
+
+/// A function returning the byte offsets of the boundaries of the segments of
+/// a string, including its start and end.
+#[cfg(feature = "graphemes")]
+pub(super) type Segmenter = fn(&str) -> Vec<usize>;
+
+/// A window of a `RopeSlice` segmented as if it was the whole text.
+///
+/// Word and sentence boundaries can't be found by looking at the chunks of
+/// a `RopeSlice` one at a time like grapheme boundaries, but whether there's
+/// a boundary between two characters only depends on a couple of segments
+/// around it. So if a window is cut in the middle of the slice, its
+/// segmentation is wrong near the cut but matches the one of the whole slice
+/// once we're at least two segments away from it.
+#[cfg(feature = "graphemes")]
+pub(super) struct SegmentedWindow<'a> {
+    /// The text of the window.
+    pub(super) text: Cow<'a, str>,
+
+    /// The byte offsets in the slice of the segment boundaries of the window,
+    /// including its start and end.
+    pub(super) boundaries: Vec<usize>,
+
+    /// The first byte offset whose segmentation doesn't depend on the text
+    /// before the window, or `None` if there's none.
+    exact_from: Option<usize>,
+
+    /// The last byte offset whose segmentation doesn't depend on the text
+    /// after the window, or `None` if there's none.
+    exact_up_to: Option<usize>,
+}
+
+#[cfg(feature = "graphemes")]
+impl<'a> SegmentedWindow<'a> {
+    /// Segments the `window` range of the slice. The start and end of
+    /// `bounds` are known to be boundaries, so the segmentation is always
+    /// right next to them.
+    #[inline]
+    pub(super) fn new(
+        slice: RopeSlice<'a>,
+        window: Range<usize>,
+        bounds: Range<usize>,
+        segmenter: Segmenter,
+    ) -> Self {
+        debug_assert!(bounds.start <= window.start);
+        debug_assert!(window.end <= bounds.end);
+
+        let text = contiguous_str(slice.byte_slice(window.clone()));
+
+        let boundaries = segmenter(&text)
+            .into_iter()
+            .map(|offset| window.start + offset)
+            .collect::<Vec<_>>();
+
+        let last = boundaries.len() - 1;
+
+        let exact_from = if window.start == bounds.start {
+            Some(window.start)
+        } else if last >= 2 && is_cut_safe(slice, window.start) {
+            Some(boundaries[1] + 1)
+        } else {
+            None
+        };
+
+        let exact_up_to = if window.end == bounds.end {
+            Some(window.end)
+        } else if last >= 2 {
+            Some(boundaries[last - 1] - 1)
+        } else {
+            None
+        };
+
+        Self { text, boundaries, exact_from, exact_up_to }
+    }
+
+    /// Returns whether the window has enough context before `byte_offset`
+    /// for it and every offset after it to be segmented like in the whole
+    /// slice.
+    #[inline]
+    pub(super) fn has_context_before(&self, byte_offset: usize) -> bool {
+        self.exact_from.map_or(false, |from| from <= byte_offset)
+    }
+
+    /// Returns whether the window has enough context after `byte_offset` for
+    /// it and every offset before it to be segmented like in the whole slice.
+    #[inline]
+    pub(super) fn has_context_after(&self, byte_offset: usize) -> bool {
+        self.exact_up_to.map_or(false, |up_to| byte_offset <= up_to)
+    }
+}
+
+/// Returns whether a window can start at `byte_offset` without changing how
+/// the segments after its first two are split.
+///
+/// That's not the case if it cuts a grapheme cluster, since the rules skip
+/// over extending characters, or a run of regional indicators, which are
+/// paired up starting from the first one.
+#[cfg(feature = "graphemes")]
+#[inline]
+fn is_cut_safe(slice: RopeSlice<'_>, byte_offset: usize) -> bool {
+    let is_regional_indicator =
+        |ch: char| ('\u{1F1E6}'..='\u{1F1FF}').contains(&ch);
+
+    is_grapheme_boundary(slice, byte_offset)
+        && !slice
+            .byte_slice(..byte_offset)
+            .chars()
+            .next_back()
+            .map_or(false, is_regional_indicator)
+}
+
 pub mod panic_messages {
     #[track_caller]
     #[cold]
@@ -284,6 +956,21 @@ pub mod panic_messages {
         );
     }
 
+    #[track_caller]
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn column_start_after_end(
+        column_start: usize,
+        column_end: usize,
+    ) -> ! {
+        debug_assert!(column_start > column_end);
+
+        panic!(
+            "column start after end: the start is {column_start} but the end \
+             is {column_end}"
+        );
+    }
+
     #[track_caller]
     #[cold]
     #[inline(never)]
@@ -329,6 +1016,22 @@ pub mod panic_messages {
         );
     }
 
+    #[cfg(feature = "crdt")]
+    #[track_caller]
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn position_out_of_bounds(
+        position: usize,
+        item_len: usize,
+    ) -> ! {
+        debug_assert!(position > item_len);
+
+        panic!(
+            "position out of bounds: the position is {position} but the \
+             length is {item_len}"
+        );
+    }
+
     #[cfg(feature = "utf16-metric")]
     #[track_caller]
     #[cold]
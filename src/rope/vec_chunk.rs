@@ -0,0 +1,189 @@
+//! This module contains the leaf type of the trees that store plain elements
+//! instead of text, i.e. the ones of the `crdt` module's replicas.
+
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::ops::RangeBounds;
+
+use crate::range_bounds_to_start_end;
+use crate::tree::{BalancedLeaf, Leaf, Metric, ReplaceableLeaf, Summarize};
+
+/// A leaf storing up to `MAX_LEN` elements in a `Vec`.
+///
+/// The summary and the base metric depend on the elements, so they're
+/// implemented on the concrete chunk types, while everything else is shared.
+#[derive(Debug, Clone)]
+pub(super) struct VecChunk<T, const MAX_LEN: usize> {
+    elems: Vec<T>,
+}
+
+impl<T, const MAX_LEN: usize> Default for VecChunk<T, MAX_LEN> {
+    #[inline]
+    fn default() -> Self {
+        Self { elems: Vec::new() }
+    }
+}
+
+impl<T: Copy, const MAX_LEN: usize> VecChunk<T, MAX_LEN> {
+    #[inline]
+    pub(super) fn elems(&self) -> &[T] {
+        &self.elems
+    }
+
+    #[inline]
+    pub(super) const fn min_len() -> usize {
+        MAX_LEN / 4
+    }
+
+    /// Splits the elements into as few chunks as possible, all with roughly
+    /// the same length so that none of them is underfilled. An empty slice is
+    /// split into a single empty chunk.
+    #[inline]
+    pub(super) fn split(
+        elems: &[T],
+    ) -> impl ExactSizeIterator<Item = Self> + '_ {
+        let chunks = ((elems.len() + MAX_LEN - 1) / MAX_LEN).max(1);
+
+        (0..chunks).map(move |idx| {
+            let start = elems.len() * idx / chunks;
+            let end = elems.len() * (idx + 1) / chunks;
+            Self { elems: elems[start..end].to_vec() }
+        })
+    }
+}
+
+impl<T: Copy, const MAX_LEN: usize> From<&[T]> for VecChunk<T, MAX_LEN> {
+    #[inline]
+    fn from(elems: &[T]) -> Self {
+        Self { elems: elems.to_vec() }
+    }
+}
+
+impl<T, const MAX_LEN: usize> BalancedLeaf for VecChunk<T, MAX_LEN>
+where
+    T: Copy + Debug,
+    Self: Leaf + for<'a> From<Self::Slice<'a>>,
+{
+    #[inline]
+    fn is_underfilled(&self, _: &Self::Summary) -> bool {
+        self.elems.len() < Self::min_len()
+    }
+
+    #[inline]
+    fn balance_leaves(
+        (left, left_summary): (&mut Self, &mut Self::Summary),
+        (right, right_summary): (&mut Self, &mut Self::Summary),
+    ) {
+        // The two leaves can be combined in a single chunk.
+        if left.elems.len() + right.elems.len() <= MAX_LEN {
+            left.elems.append(&mut right.elems);
+        }
+        // The left side is underfilled => take elements from the right side.
+        else if left.elems.len() < Self::min_len() {
+            let missing = Self::min_len() - left.elems.len();
+            left.elems.extend(right.elems.drain(..missing));
+        }
+        // The right side is underfilled => take elements from the left side.
+        else if right.elems.len() < Self::min_len() {
+            let missing = Self::min_len() - right.elems.len();
+            let moved = left.elems.drain(left.elems.len() - missing..);
+            right.elems.splice(..0, moved);
+        }
+
+        *left_summary = left.summarize();
+        *right_summary = right.summarize();
+    }
+}
+
+/// `M` has to measure the number of elements, which is what its conversion
+/// into a `usize` returns.
+impl<T, M, const MAX_LEN: usize> ReplaceableLeaf<M> for VecChunk<T, MAX_LEN>
+where
+    T: Copy + Debug + 'static,
+    M: Metric<Self::Summary> + Into<usize>,
+    Self: BalancedLeaf,
+{
+    type Replacement<'a> = &'a [T];
+
+    type ExtraLeaves = alloc::vec::IntoIter<Self>;
+
+    #[inline]
+    fn replace<R>(
+        &mut self,
+        summary: &mut Self::Summary,
+        range: R,
+        replacement: &[T],
+    ) -> Option<Self::ExtraLeaves>
+    where
+        R: RangeBounds<M>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(range, 0, self.elems.len());
+
+        debug_assert!(start <= end);
+        debug_assert!(end <= self.elems.len());
+
+        self.elems.splice(start..end, replacement.iter().copied());
+
+        if self.elems.len() <= MAX_LEN {
+            *summary = self.summarize();
+            return None;
+        }
+
+        let elems = core::mem::take(&mut self.elems);
+
+        let mut chunks = Self::split(&elems);
+
+        *self = chunks.next().unwrap();
+
+        *summary = self.summarize();
+
+        Some(chunks.collect::<Vec<_>>().into_iter())
+    }
+
+    #[inline]
+    fn remove_up_to(&mut self, summary: &mut Self::Summary, up_to: M) {
+        self.elems.drain(..up_to.into());
+        *summary = self.summarize();
+    }
+}
+
+/// Implements the arithmetic operators required by
+/// [`Metric`](crate::tree::Metric) for a newtype around an integer.
+macro_rules! impl_metric_ops {
+    ($metric:ident) => {
+        impl core::ops::Add for $metric {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, other: Self) -> Self {
+                Self(self.0 + other.0)
+            }
+        }
+
+        impl core::ops::Sub for $metric {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, other: Self) -> Self {
+                Self(self.0 - other.0)
+            }
+        }
+
+        impl core::ops::AddAssign for $metric {
+            #[inline]
+            fn add_assign(&mut self, other: Self) {
+                self.0 += other.0
+            }
+        }
+
+        impl core::ops::SubAssign for $metric {
+            #[inline]
+            fn sub_assign(&mut self, other: Self) {
+                self.0 -= other.0
+            }
+        }
+    };
+}
+
+pub(super) use impl_metric_ops;
@@ -0,0 +1,146 @@
+//! This module contains the cache used by internal nodes to store data about
+//! their subtree that can't be kept in their summaries, like its rolling
+//! hash, the nesting of its brackets or its word counts.
+//!
+//! That data depends on the order of the node's children, so unlike a
+//! summary it can't be updated by subtracting the data of a removed child.
+//! Instead it's computed from the children the first time it's requested, and
+//! the cache is invalidated every time the node is mutated.
+//!
+//! All three are monoids over the leaves of a `Tree`, so they're computed by
+//! the same fold, generic over the [`CachedFold`] trait.
+
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+use super::{Inode, Leaf};
+
+/// Data about a sequence of leaves that can be computed by concatenating the
+/// data of its parts, and that internal nodes cache for their subtree.
+///
+/// Concatenating has to be associative and [`empty()`](Self::empty()) its
+/// identity, so that the data of a subtree doesn't depend on how its leaves
+/// are grouped.
+pub(super) trait CachedFold<L: Leaf>: Copy {
+    /// Returns the data of an empty sequence.
+    fn empty() -> Self;
+
+    /// Returns the data of `self`'s sequence followed by `rhs`'s.
+    fn concat(self, rhs: Self) -> Self;
+
+    /// Computes the data of a single leaf slice.
+    fn of_leaf(slice: L::Slice<'_>) -> Self;
+
+    /// Returns the cache where the inode stores the data of its subtree.
+    fn cache<const N: usize>(inode: &Inode<N, L>) -> &LazyCache<Self>;
+}
+
+/// A lazily computed value stored in an internal node.
+///
+/// Nodes can be shared between `Tree`s living on different threads, so the
+/// value can be set through a shared reference. It's stored on the heap
+/// behind an atomic pointer, which makes setting it a single
+/// compare-and-swap no matter how big the value is.
+pub(super) struct LazyCache<T> {
+    /// The cached value, or null if the cache is empty.
+    value: AtomicPtr<T>,
+
+    /// Makes the cache `Send` and `Sync` only if `T` is.
+    _marker: PhantomData<Box<T>>,
+}
+
+impl<T> Default for LazyCache<T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            value: AtomicPtr::new(core::ptr::null_mut()),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Copy> Clone for LazyCache<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let new = Self::default();
+
+        if let Some(value) = self.get() {
+            new.set(value);
+        }
+
+        new
+    }
+}
+
+impl<T> Drop for LazyCache<T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.invalidate();
+    }
+}
+
+impl<T: Copy> LazyCache<T> {
+    #[inline]
+    pub(super) fn get(&self) -> Option<T> {
+        // Pairs with the `Release` exchange in `set()`, so if we see the
+        // pointer we also see the value it points to.
+        let value = self.value.load(Ordering::Acquire);
+
+        // SAFETY: a non-null pointer was leaked from a `Box` in `set()`, and
+        // it's only freed by `invalidate()`, which takes `&mut self`.
+        unsafe { value.as_ref() }.copied()
+    }
+
+    /// Caches the value, unless another thread got there first, in which
+    /// case the value it cached is the same one.
+    #[inline]
+    pub(super) fn set(&self, value: T) {
+        let new = Box::into_raw(Box::new(value));
+
+        let swapped = self.value.compare_exchange(
+            core::ptr::null_mut(),
+            new,
+            Ordering::Release,
+            Ordering::Relaxed,
+        );
+
+        if swapped.is_err() {
+            // SAFETY: the pointer was leaked from a `Box` above and it was
+            // never shared.
+            drop(unsafe { Box::from_raw(new) });
+        }
+    }
+}
+
+impl<T> LazyCache<T> {
+    #[inline]
+    pub(super) fn invalidate(&mut self) {
+        let value =
+            core::mem::replace(self.value.get_mut(), core::ptr::null_mut());
+
+        if !value.is_null() {
+            // SAFETY: a non-null pointer was leaked from a `Box` in `set()`,
+            // and nobody else can be reading it since we have `&mut self`.
+            drop(unsafe { Box::from_raw(value) });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_starts_empty() {
+        let mut cache = LazyCache::default();
+        assert_eq!(cache.get(), None);
+
+        cache.set((3, -1));
+        cache.set((3, -1));
+        assert_eq!(cache.clone().get(), Some((3, -1)));
+
+        cache.invalidate();
+        assert_eq!(cache.get(), None);
+    }
+}
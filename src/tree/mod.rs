@@ -1,7 +1,19 @@
+#[cfg(any(
+    feature = "bracket-depth",
+    feature = "content-hash",
+    feature = "text-stats"
+))]
+mod lazy_cache;
 mod leaves;
+#[cfg(feature = "bracket-depth")]
+mod nesting;
 mod node;
 mod node_internal;
 mod node_leaf;
+#[cfg(feature = "content-hash")]
+mod rolling_hash;
+#[cfg(feature = "text-stats")]
+mod text_stats;
 mod tiny_arc;
 mod traits;
 mod tree;
@@ -10,10 +22,22 @@ mod tree_slice;
 mod units;
 
 use iter_chain::ExactChain;
+#[cfg(any(
+    feature = "bracket-depth",
+    feature = "content-hash",
+    feature = "text-stats"
+))]
+use lazy_cache::CachedFold;
 pub use leaves::Leaves;
+#[cfg(feature = "bracket-depth")]
+pub use nesting::Nesting;
 use node::Node;
 use node_internal::Inode;
 use node_leaf::Lnode;
+#[cfg(feature = "content-hash")]
+pub use rolling_hash::RollingHash;
+#[cfg(feature = "text-stats")]
+pub use text_stats::TextStats;
 use tiny_arc::Arc;
 pub use traits::*;
 pub use tree::Tree;
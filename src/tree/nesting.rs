@@ -0,0 +1,70 @@
+//! This module contains the [`Nesting`] of a sequence of opening and closing
+//! delimiters, used to find unmatched delimiters in `Tree`s.
+
+/// How a sequence of opening and closing delimiters changes the nesting depth.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Nesting {
+    /// The number of opening delimiters minus the number of closing ones.
+    pub net: isize,
+
+    /// The minimum depth reached by any prefix of the sequence, starting from
+    /// zero. It's always less than or equal to zero since the empty prefix is
+    /// included.
+    pub min_depth: isize,
+}
+
+impl Nesting {
+    /// Returns the nesting of the concatenation of `self` and `rhs`.
+    #[inline]
+    pub fn concat(self, rhs: Self) -> Self {
+        Self {
+            net: self.net + rhs.net,
+            min_depth: self.min_depth.min(self.net + rhs.min_depth),
+        }
+    }
+
+    /// The nesting of a sequence without any delimiters.
+    #[inline]
+    pub const fn empty() -> Self {
+        Self { net: 0, min_depth: 0 }
+    }
+
+    /// The minimum depth reached by any suffix of the sequence when reading
+    /// it backwards, where closing delimiters increase the depth and opening
+    /// ones decrease it.
+    #[inline]
+    pub fn min_depth_backward(&self) -> isize {
+        self.min_depth - self.net
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nesting_of(s: &str) -> Nesting {
+        s.bytes().fold(Nesting::empty(), |nesting, byte| {
+            let delta = match byte {
+                b'(' => 1,
+                b')' => -1,
+                _ => return nesting,
+            };
+            nesting.concat(Nesting { net: delta, min_depth: delta.min(0) })
+        })
+    }
+
+    #[test]
+    fn concat_is_independent_of_split() {
+        let s = ")(()))((()(";
+
+        let whole = nesting_of(s);
+
+        assert_eq!(whole, Nesting { net: 1, min_depth: -2 });
+        assert_eq!(whole.min_depth_backward(), -3);
+
+        for split in 0..=s.len() {
+            let (left, right) = s.split_at(split);
+            assert_eq!(whole, nesting_of(left).concat(nesting_of(right)));
+        }
+    }
+}
@@ -1,4 +1,10 @@
 use super::traits::{BalancedLeaf, Leaf, Metric, SlicingMetric};
+#[cfg(any(
+    feature = "bracket-depth",
+    feature = "content-hash",
+    feature = "text-stats"
+))]
+use super::CachedFold;
 use super::{Arc, Inode, Lnode};
 
 #[derive(Clone)]
@@ -149,6 +155,77 @@ impl<const N: usize, L: Leaf> Node<N, L> {
         }
     }
 
+    /// Returns the `F` of the contents of this node.
+    #[cfg(any(
+        feature = "bracket-depth",
+        feature = "content-hash",
+        feature = "text-stats"
+    ))]
+    #[inline]
+    pub(super) fn fold<F: CachedFold<L>>(&self) -> F {
+        match self {
+            Node::Internal(inode) => inode.fold(),
+            Node::Leaf(leaf) => F::of_leaf(leaf.as_slice()),
+        }
+    }
+
+    /// Returns the `F` of the leaves in the `L::BaseMetric` range
+    /// `start..end` of this node's subtree, using the values cached in the
+    /// inodes fully contained in the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range doesn't start and end on leaf boundaries.
+    #[cfg(any(feature = "content-hash", feature = "text-stats"))]
+    #[inline]
+    pub(super) fn fold_between<F: CachedFold<L>>(
+        &self,
+        start: L::BaseMetric,
+        end: L::BaseMetric,
+    ) -> F {
+        let zero = L::BaseMetric::zero();
+
+        if start == zero && end == self.base_measure() {
+            return self.fold();
+        }
+
+        let mut value = F::empty();
+
+        if start == end {
+            return value;
+        }
+
+        // The range starts and ends on leaf boundaries, so if we get here
+        // this can't be a leaf.
+        let inode = self.get_internal();
+
+        let mut offset = zero;
+
+        for child in inode.children() {
+            let child_end = offset + child.base_measure();
+
+            if child_end > start && offset < end {
+                let from = if start > offset { start - offset } else { zero };
+
+                let to = if end < child_end {
+                    end - offset
+                } else {
+                    child.base_measure()
+                };
+
+                value = value.concat(child.fold_between(from, to));
+            }
+
+            if child_end >= end {
+                break;
+            }
+
+            offset = child_end;
+        }
+
+        value
+    }
+
     #[inline]
     pub(super) fn get_internal(&self) -> &Inode<N, L> {
         match self {
@@ -165,6 +242,27 @@ impl<const N: usize, L: Leaf> Node<N, L> {
         }
     }
 
+    /// Returns the number of bytes allocated on the heap for the nodes in the
+    /// subtree rooted at `node`, including the allocation of `node` itself.
+    ///
+    /// Note that this doesn't include any heap memory owned by the values
+    /// stored in the leaves.
+    #[inline]
+    pub(super) fn heap_size(node: &Arc<Self>) -> usize {
+        let mut size = Arc::<Self>::allocation_size();
+
+        if let Node::Internal(inode) = &**node {
+            size +=
+                inode.children_capacity() * core::mem::size_of::<Arc<Self>>();
+
+            for child in inode.children() {
+                size += Self::heap_size(child);
+            }
+        }
+
+        size
+    }
+
     #[inline]
     pub(super) fn is_empty(&self) -> bool {
         match self {
@@ -261,6 +359,147 @@ impl<const N: usize, L: Leaf> Node<N, L> {
         }
     }
 
+    /// Returns the number of nodes in the subtree rooted at `node` that are
+    /// also reachable from another `Arc`, i.e. either the node's own `Arc`
+    /// or the `Arc` of one of its ancestors has a strong count > 1.
+    ///
+    /// `ancestor_is_shared` should be `false` when calling this on a root.
+    #[inline]
+    pub(super) fn shared_count(
+        node: &Arc<Self>,
+        ancestor_is_shared: bool,
+    ) -> usize {
+        let is_shared = ancestor_is_shared || Arc::strong_count(node) > 1;
+
+        match &**node {
+            Node::Internal(inode) => {
+                (is_shared as usize)
+                    + inode
+                        .children()
+                        .iter()
+                        .map(|child| Self::shared_count(child, is_shared))
+                        .sum::<usize>()
+            },
+
+            Node::Leaf(_) => is_shared as usize,
+        }
+    }
+
+    /// Returns the offset of the first closing delimiter at or after `from`
+    /// that makes the depth negative, starting from the given depth.
+    ///
+    /// Children whose cached nesting shows they can't contain the delimiter
+    /// are skipped without looking at their leaves.
+    #[cfg(feature = "bracket-depth")]
+    #[inline]
+    pub(super) fn find_unmatched_close(
+        &self,
+        from: L::BaseMetric,
+        depth: &mut isize,
+    ) -> Option<L::BaseMetric>
+    where
+        L: super::NestingLeaf,
+    {
+        let inode = match self {
+            Node::Internal(inode) => inode,
+            Node::Leaf(leaf) => {
+                return L::find_unmatched_close(leaf.as_slice(), from, depth)
+            },
+        };
+
+        let zero = L::BaseMetric::zero();
+
+        let mut offset = zero;
+
+        for child in inode.children() {
+            let child_end = offset + child.base_measure();
+
+            if child_end <= from {
+                offset = child_end;
+                continue;
+            }
+
+            if from > offset {
+                if let Some(found) =
+                    child.find_unmatched_close(from - offset, depth)
+                {
+                    return Some(offset + found);
+                }
+            } else {
+                let nesting = child.fold::<super::Nesting>();
+
+                if *depth + nesting.min_depth < 0 {
+                    return child
+                        .find_unmatched_close(zero, depth)
+                        .map(|found| offset + found);
+                }
+
+                *depth += nesting.net;
+            }
+
+            offset = child_end;
+        }
+
+        None
+    }
+
+    /// Returns the offset of the last opening delimiter before `to` that makes
+    /// the depth negative when scanning backward, starting from the given
+    /// depth.
+    ///
+    /// Children whose cached nesting shows they can't contain the delimiter
+    /// are skipped without looking at their leaves.
+    #[cfg(feature = "bracket-depth")]
+    #[inline]
+    pub(super) fn find_unmatched_open(
+        &self,
+        to: L::BaseMetric,
+        depth: &mut isize,
+    ) -> Option<L::BaseMetric>
+    where
+        L: super::NestingLeaf,
+    {
+        let inode = match self {
+            Node::Internal(inode) => inode,
+            Node::Leaf(leaf) => {
+                return L::find_unmatched_open(leaf.as_slice(), to, depth)
+            },
+        };
+
+        let mut offset = self.base_measure();
+
+        for child in inode.children().iter().rev() {
+            let child_start = offset - child.base_measure();
+
+            if child_start >= to {
+                offset = child_start;
+                continue;
+            }
+
+            if to < offset {
+                if let Some(found) =
+                    child.find_unmatched_open(to - child_start, depth)
+                {
+                    return Some(child_start + found);
+                }
+            } else {
+                let nesting = child.fold::<super::Nesting>();
+
+                if *depth + nesting.min_depth_backward() < 0 {
+                    return child
+                        .find_unmatched_open(child.base_measure(), depth)
+                        .map(|found| child_start + found);
+                }
+
+                *depth -= nesting.net;
+            }
+
+            offset = child_start;
+        }
+
+        None
+    }
+
     #[inline]
     pub(super) fn summary(&self) -> &L::Summary {
         match self {
@@ -1,5 +1,13 @@
+use alloc::string::String;
+use alloc::vec::Vec;
 use core::ops::{Range, RangeBounds};
 
+#[cfg(any(
+    feature = "bracket-depth",
+    feature = "content-hash",
+    feature = "text-stats"
+))]
+use super::lazy_cache::{CachedFold, LazyCache};
 use super::traits::*;
 use super::{Arc, ExactChain, Node};
 use crate::range_bounds_to_start_end;
@@ -10,6 +18,12 @@ pub(super) struct Inode<const N: usize, L: Leaf> {
     summary: L::Summary,
     depth: usize,
     leaf_count: usize,
+    #[cfg(feature = "content-hash")]
+    hash_cache: LazyCache<super::RollingHash>,
+    #[cfg(feature = "bracket-depth")]
+    nesting_cache: LazyCache<super::Nesting>,
+    #[cfg(feature = "text-stats")]
+    stats_cache: LazyCache<super::TextStats>,
 }
 
 impl<const N: usize, L: Leaf> core::fmt::Debug for Inode<N, L> {
@@ -42,6 +56,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
     where
         L: BalancedLeaf + Clone,
     {
+        self.invalidate_caches();
+
         debug_assert!(node.depth() < self.depth());
 
         if self.depth() > node.depth() + 1 {
@@ -136,6 +152,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
     /// Panics if `other` is at a different depth.
     #[inline]
     pub(super) fn balance(&mut self, other: &mut Self) {
+        self.invalidate_caches();
+
         debug_assert_eq!(self.depth(), other.depth());
 
         if !self.is_underfilled() && !other.is_underfilled() {
@@ -189,6 +207,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
     where
         L: BalancedLeaf + Clone,
     {
+        self.invalidate_caches();
+
         debug_assert!(self.len() > 1);
 
         if !self.child(child_idx).is_underfilled() {
@@ -218,6 +238,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
     where
         L: BalancedLeaf + Clone,
     {
+        self.invalidate_caches();
+
         self.balance_first_child_with_second();
 
         let first_is_underfilled = self.with_child_mut(0, |first| {
@@ -245,6 +267,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
     where
         L: BalancedLeaf + Clone,
     {
+        self.invalidate_caches();
+
         self.balance_last_child_with_penultimate();
 
         let last_is_underfilled =
@@ -281,6 +305,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
     where
         L: BalancedLeaf + Clone,
     {
+        self.invalidate_caches();
+
         debug_assert!(self.len() >= 2);
 
         // Check for early returns.
@@ -295,6 +321,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
                 // Move the second child's children to the first child, then
                 // remove the second child.
                 if first.len() + second.len() <= Self::max_children() {
+                    first.invalidate_caches();
+                    second.invalidate_caches();
                     first.children.append(&mut second.children);
                     first.leaf_count += second.leaf_count;
                     first.summary += second.summary();
@@ -349,6 +377,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
     where
         L: BalancedLeaf + Clone,
     {
+        self.invalidate_caches();
+
         debug_assert!(self.len() >= 2);
 
         // Check for early returns.
@@ -365,6 +395,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
                 // Move the last child's children to the penultimate child,
                 // then remove the last child.
                 if penultimate.len() + last.len() <= Self::max_children() {
+                    penultimate.invalidate_caches();
+                    last.invalidate_caches();
                     penultimate.children.append(&mut last.children);
                     penultimate.leaf_count += last.leaf_count;
                     penultimate.summary += last.summary();
@@ -405,6 +437,14 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         self.measure::<L::BaseMetric>()
     }
 
+    /// Returns the rolling hash of this inode's subtree if it's already been
+    /// computed.
+    #[cfg(feature = "content-hash")]
+    #[inline]
+    pub(super) fn cached_rolling_hash(&self) -> Option<super::RollingHash> {
+        self.hash_cache.get()
+    }
+
     #[inline]
     pub(super) fn child(&self, child_idx: usize) -> &Arc<Node<N, L>> {
         &self.children[child_idx]
@@ -415,6 +455,12 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         &self.children
     }
 
+    /// The number of children this inode can hold without reallocating.
+    #[inline]
+    pub(super) fn children_capacity(&self) -> usize {
+        self.children.capacity()
+    }
+
     /// Returns the index of the child at the given measure together
     /// with the combined `M`-offset of the other children up to but not
     /// including that child.
@@ -507,6 +553,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
     where
         R: RangeBounds<usize>,
     {
+        self.invalidate_caches();
+
         let (start, end) = range_bounds_to_start_end(idx_range, 0, self.len());
 
         debug_assert!(start <= end);
@@ -527,6 +575,12 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
             depth: 1,
             leaf_count: 0,
             summary: Default::default(),
+            #[cfg(feature = "content-hash")]
+            hash_cache: LazyCache::default(),
+            #[cfg(feature = "bracket-depth")]
+            nesting_cache: LazyCache::default(),
+            #[cfg(feature = "text-stats")]
+            stats_cache: LazyCache::default(),
         }
     }
 
@@ -544,6 +598,29 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
     ///
     /// Panics if `children` yields zero nodes, more than `max_children` nodes
     /// or nodes at different depths.
+    /// Returns the `F` of this inode's subtree, computing and caching it if it
+    /// wasn't already cached.
+    #[cfg(any(
+        feature = "bracket-depth",
+        feature = "content-hash",
+        feature = "text-stats"
+    ))]
+    #[inline]
+    pub(super) fn fold<F: CachedFold<L>>(&self) -> F {
+        if let Some(value) = F::cache(self).get() {
+            return value;
+        }
+
+        let value = self
+            .children
+            .iter()
+            .fold(F::empty(), |value, child| value.concat(child.fold()));
+
+        F::cache(self).set(value);
+
+        value
+    }
+
     #[inline]
     pub(super) fn from_children<I>(children: I) -> Self
     where
@@ -564,7 +641,18 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
             summary += child.summary();
         }
 
-        Self { children, depth, leaf_count, summary }
+        Self {
+            children,
+            depth,
+            leaf_count,
+            summary,
+            #[cfg(feature = "content-hash")]
+            hash_cache: LazyCache::default(),
+            #[cfg(feature = "bracket-depth")]
+            nesting_cache: LazyCache::default(),
+            #[cfg(feature = "text-stats")]
+            stats_cache: LazyCache::default(),
+        }
     }
 
     /// Constructs a new inode from an arbitrarily long sequence of nodes.
@@ -627,6 +715,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         child_offset: usize,
         child: Arc<Node<N, L>>,
     ) {
+        self.invalidate_caches();
+
         if self.is_empty() {
             self.depth = child.depth() + 1;
         }
@@ -656,6 +746,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
     ) where
         L: BalancedLeaf + Clone,
     {
+        self.invalidate_caches();
+
         debug_assert!(!self.is_empty());
         debug_assert!(child_offset <= self.len());
         debug_assert!(self.depth() >= 2);
@@ -713,6 +805,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         I: IntoIterator<Item = Arc<Node<N, L>>>,
         I::IntoIter: ExactSizeIterator,
     {
+        self.invalidate_caches();
+
         let mut children = children.into_iter();
 
         if self.len() + children.len() <= Self::max_children() {
@@ -767,6 +861,18 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         ))
     }
 
+    /// Invalidates the lazily computed caches of this inode. This has to be
+    /// called every time the inode is mutated.
+    #[inline]
+    fn invalidate_caches(&mut self) {
+        #[cfg(feature = "content-hash")]
+        self.hash_cache.invalidate();
+        #[cfg(feature = "bracket-depth")]
+        self.nesting_cache.invalidate();
+        #[cfg(feature = "text-stats")]
+        self.stats_cache.invalidate();
+    }
+
     #[inline]
     pub(super) fn is_empty(&self) -> bool {
         self.len() == 0
@@ -825,6 +931,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
     where
         L: BalancedLeaf + Clone,
     {
+        self.invalidate_caches();
+
         debug_assert!(node.depth() < self.depth());
 
         if self.depth() > node.depth() + 1 {
@@ -875,6 +983,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
     /// than `self.depth() - 1` if the inode already contained some children.
     #[inline]
     pub(super) fn push(&mut self, child: Arc<Node<N, L>>) {
+        self.invalidate_caches();
+
         if self.is_empty() {
             self.depth = child.depth() + 1;
         }
@@ -894,6 +1004,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
     /// Panics if `child_idx` is greater or equal to the length of this inode.
     #[inline]
     pub(super) fn remove(&mut self, child_idx: usize) -> Arc<Node<N, L>> {
+        self.invalidate_caches();
+
         debug_assert!(child_idx < self.len());
         let child = self.children.remove(child_idx);
         self.leaf_count -= child.leaf_count();
@@ -918,6 +1030,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         child_idx: usize,
         new_child: Arc<Node<N, L>>,
     ) {
+        self.invalidate_caches();
+
         debug_assert!(child_idx < self.len());
         debug_assert_eq!(new_child.depth() + 1, self.depth());
 
@@ -943,6 +1057,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         first_idx: usize,
         second_idx: usize,
     ) -> (&mut Arc<Node<N, L>>, &mut Arc<Node<N, L>>) {
+        self.invalidate_caches();
+
         debug_assert!(first_idx < second_idx);
         debug_assert!(second_idx < self.len());
 
@@ -964,6 +1080,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
     where
         F: FnOnce(&mut Arc<Node<N, L>>) -> T,
     {
+        self.invalidate_caches();
+
         let child = &mut self.children[child_idx];
 
         self.summary -= child.summary();
@@ -978,6 +1096,75 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
     }
 }
 
+#[cfg(feature = "bracket-depth")]
+impl<L: NestingLeaf> CachedFold<L> for super::Nesting {
+    #[inline]
+    fn empty() -> Self {
+        Self::empty()
+    }
+
+    #[inline]
+    fn concat(self, rhs: Self) -> Self {
+        self.concat(rhs)
+    }
+
+    #[inline]
+    fn of_leaf(slice: L::Slice<'_>) -> Self {
+        L::nesting(slice)
+    }
+
+    #[inline]
+    fn cache<const N: usize>(inode: &Inode<N, L>) -> &LazyCache<Self> {
+        &inode.nesting_cache
+    }
+}
+
+#[cfg(feature = "content-hash")]
+impl<L: HashableLeaf> CachedFold<L> for super::RollingHash {
+    #[inline]
+    fn empty() -> Self {
+        Self::empty()
+    }
+
+    #[inline]
+    fn concat(self, rhs: Self) -> Self {
+        self.concat(rhs)
+    }
+
+    #[inline]
+    fn of_leaf(slice: L::Slice<'_>) -> Self {
+        L::rolling_hash(slice)
+    }
+
+    #[inline]
+    fn cache<const N: usize>(inode: &Inode<N, L>) -> &LazyCache<Self> {
+        &inode.hash_cache
+    }
+}
+
+#[cfg(feature = "text-stats")]
+impl<L: StatsLeaf> CachedFold<L> for super::TextStats {
+    #[inline]
+    fn empty() -> Self {
+        Self::empty()
+    }
+
+    #[inline]
+    fn concat(self, rhs: Self) -> Self {
+        self.concat(rhs)
+    }
+
+    #[inline]
+    fn of_leaf(slice: L::Slice<'_>) -> Self {
+        L::text_stats(slice)
+    }
+
+    #[inline]
+    fn cache<const N: usize>(inode: &Inode<N, L>) -> &LazyCache<Self> {
+        &inode.stats_cache
+    }
+}
+
 /// Takes an iterator of `n` nodes (with `n >= min_children`) at depth `d`
 /// and gives back inodes of depth `d + 1` that are all guaranteed to have
 /// between `min_children` and `max_children` children.
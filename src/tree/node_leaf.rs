@@ -1,6 +1,12 @@
 use core::ops::RangeBounds;
 
-use super::traits::{BalancedLeaf, Leaf, Metric, ReplaceableLeaf};
+use super::traits::{
+    BalancedLeaf,
+    CompactableLeaf,
+    Leaf,
+    Metric,
+    ReplaceableLeaf,
+};
 
 #[derive(Clone, Default)]
 pub(super) struct Lnode<L: Leaf> {
@@ -62,6 +68,17 @@ impl<L: Leaf> Lnode<L> {
         self.measure::<L::BaseMetric>()
     }
 
+    #[inline]
+    pub(super) fn fill(&mut self, other: &mut Self)
+    where
+        L: CompactableLeaf,
+    {
+        L::fill_leaves(
+            (&mut self.value, &mut self.summary),
+            (&mut other.value, &mut other.summary),
+        )
+    }
+
     #[inline]
     pub(super) fn is_underfilled(&self) -> bool
     where
@@ -0,0 +1,141 @@
+//! This module contains the polynomial rolling hash used to compute content
+//! hashes of `Tree`s and `TreeSlice`s.
+//!
+//! Given a string of bytes `b_0, .., b_{n - 1}`, its hash is
+//!
+//! ```text
+//! (b_0 + 1) * B^(n - 1) + (b_1 + 1) * B^(n - 2) + .. + (b_{n - 1} + 1)
+//! ```
+//!
+//! modulo the Mersenne prime `2^61 - 1`. Together with `B^n` this is enough
+//! to compute the hash of the concatenation of two strings from their hashes,
+//! which makes the hash of a tree independent of how its contents are split
+//! across the leaves.
+//!
+//! Note that this can't be stored in the summaries of the nodes because those
+//! have to support removing a child from anywhere in an internal node by
+//! simply subtracting its summary, and the hash of a child depends on its
+//! position.
+
+/// `2^61 - 1`.
+const MODULUS: u64 = (1 << 61) - 1;
+
+/// An arbitrary base smaller than the modulus.
+const BASE: u64 = 0x0d6e_8fea_cb3e_9c47;
+
+/// The rolling hash of a string of bytes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RollingHash {
+    /// The hash of the bytes.
+    hash: u64,
+
+    /// `BASE` raised to the number of bytes that were hashed.
+    base_pow: u64,
+}
+
+impl Default for RollingHash {
+    #[inline]
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl RollingHash {
+    /// Returns the hash of the concatenation of `self` and `rhs`.
+    #[inline]
+    pub fn concat(self, rhs: Self) -> Self {
+        Self {
+            hash: add_mod(mul_mod(self.hash, rhs.base_pow), rhs.hash),
+            base_pow: mul_mod(self.base_pow, rhs.base_pow),
+        }
+    }
+
+    /// The hash of the empty string.
+    #[inline]
+    pub const fn empty() -> Self {
+        Self { hash: 0, base_pow: 1 }
+    }
+
+    /// Returns the hash of the bytes.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self::empty().push_bytes(bytes)
+    }
+
+    /// Returns the hash of the concatenation of `self` and `bytes`.
+    #[inline]
+    pub fn push_bytes(mut self, bytes: &[u8]) -> Self {
+        for &byte in bytes {
+            self.hash = add_mod(mul_mod(self.hash, BASE), byte as u64 + 1);
+            self.base_pow = mul_mod(self.base_pow, BASE);
+        }
+        self
+    }
+
+    /// Mixes the hash and the length into a well distributed `u64`.
+    #[inline]
+    pub fn finish(&self) -> u64 {
+        // The finalizer of SplitMix64.
+        let mut z = self.hash ^ self.base_pow.rotate_left(32);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[inline]
+fn add_mod(a: u64, b: u64) -> u64 {
+    debug_assert!(a < MODULUS && b <= MODULUS);
+    let sum = a + b;
+    if sum >= MODULUS {
+        sum - MODULUS
+    } else {
+        sum
+    }
+}
+
+#[inline]
+fn mul_mod(a: u64, b: u64) -> u64 {
+    let product = a as u128 * b as u128;
+    let folded = (product as u64 & MODULUS) + (product >> 61) as u64;
+    let folded = (folded & MODULUS) + (folded >> 61);
+    if folded >= MODULUS {
+        folded - MODULUS
+    } else {
+        folded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concat_is_independent_of_split() {
+        let bytes = "Löwe 老虎 Léopard\r\n\0\0".as_bytes();
+
+        let whole = RollingHash::from_bytes(bytes);
+
+        for split in 0..=bytes.len() {
+            let (left, right) = bytes.split_at(split);
+            let concat = RollingHash::from_bytes(left)
+                .concat(RollingHash::from_bytes(right));
+            assert_eq!(whole, concat);
+        }
+    }
+
+    #[test]
+    fn leading_zeros_change_the_hash() {
+        let a = RollingHash::from_bytes(b"a");
+        let b = RollingHash::from_bytes(b"\0a");
+        assert_ne!(a.hash, b.hash);
+        assert_ne!(a.finish(), b.finish());
+    }
+
+    #[test]
+    fn mul_mod_reduces() {
+        assert_eq!(mul_mod(MODULUS - 1, MODULUS - 1), 1);
+        assert_eq!(mul_mod(MODULUS - 1, 2), MODULUS - 2);
+        assert_eq!(mul_mod(0, BASE), 0);
+    }
+}
@@ -0,0 +1,165 @@
+//! This module contains the [`TextStats`] of a piece of text, used to count
+//! the words and paragraphs of `Tree`s.
+//!
+//! Words are maximal runs of non-whitespace characters, and paragraphs are
+//! maximal runs of lines containing at least one non-whitespace character.
+//! Counting them is done by an automaton reading the text one character at a
+//! time, and the stats of a piece of text store, for every state the
+//! automaton can be in when it starts reading it, the state it ends in and how
+//! many words and paragraphs it counted along the way. This is the
+//! boundary data needed to get the stats of the concatenation of two pieces,
+//! e.g. when a word is split across two leaves.
+
+/// Inside a word.
+const IN_WORD: u8 = 0;
+
+/// After a word, on a line that contains at least one word.
+const AFTER_WORD: u8 = 1;
+
+/// On a line without any words yet, after a blank line or at the start of
+/// the text.
+const AFTER_BLANK_LINE: u8 = 2;
+
+/// On a line without any words yet, after a line with some words.
+const AFTER_TEXT_LINE: u8 = 3;
+
+const STATES: usize = 4;
+
+/// The word and paragraph counts of a piece of text, together with what's
+/// needed to combine them with the ones of the text around it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TextStats {
+    /// The state the automaton ends in for every state it starts in.
+    end: [u8; STATES],
+
+    /// The number of words started for every state the automaton starts in.
+    words: [usize; STATES],
+
+    /// The number of paragraphs started for every state the automaton starts
+    /// in.
+    paragraphs: [usize; STATES],
+}
+
+impl TextStats {
+    /// Returns the stats of the concatenation of `self` and `rhs`.
+    #[inline]
+    pub fn concat(self, rhs: Self) -> Self {
+        let mut stats = self;
+
+        for state in 0..STATES {
+            let mid = self.end[state] as usize;
+            stats.end[state] = rhs.end[mid];
+            stats.words[state] += rhs.words[mid];
+            stats.paragraphs[state] += rhs.paragraphs[mid];
+        }
+
+        stats
+    }
+
+    /// The stats of the empty string.
+    #[inline]
+    pub const fn empty() -> Self {
+        Self {
+            end: [IN_WORD, AFTER_WORD, AFTER_BLANK_LINE, AFTER_TEXT_LINE],
+            words: [0; STATES],
+            paragraphs: [0; STATES],
+        }
+    }
+
+    /// Returns the stats of the given string.
+    #[inline]
+    pub fn from_text(s: &str) -> Self {
+        let mut stats = Self::empty();
+
+        let mut chars = s.chars();
+
+        // The automaton always ends up in the same state after reading a
+        // non-whitespace character, so we only need to run it for every
+        // starting state until then.
+        while stats.end.iter().any(|&end| end != stats.end[0]) {
+            let Some(ch) = chars.next() else { return stats };
+
+            for state in 0..STATES {
+                let (words, paragraphs) = step(&mut stats.end[state], ch);
+                stats.words[state] += words;
+                stats.paragraphs[state] += paragraphs;
+            }
+        }
+
+        let (mut end, mut words, mut paragraphs) = (stats.end[0], 0, 0);
+
+        for ch in chars {
+            let (w, p) = step(&mut end, ch);
+            words += w;
+            paragraphs += p;
+        }
+
+        for state in 0..STATES {
+            stats.end[state] = end;
+            stats.words[state] += words;
+            stats.paragraphs[state] += paragraphs;
+        }
+
+        stats
+    }
+
+    /// The number of paragraphs in the text.
+    #[inline]
+    pub fn paragraphs(&self) -> usize {
+        self.paragraphs[AFTER_BLANK_LINE as usize]
+    }
+
+    /// The number of words in the text.
+    #[inline]
+    pub fn words(&self) -> usize {
+        self.words[AFTER_BLANK_LINE as usize]
+    }
+}
+
+/// Moves the automaton to the state it's in after reading `ch`, returning
+/// the number of words and paragraphs that `ch` started.
+#[inline]
+fn step(state: &mut u8, ch: char) -> (usize, usize) {
+    if ch == '\n' {
+        *state = match *state {
+            IN_WORD | AFTER_WORD => AFTER_TEXT_LINE,
+            _ => AFTER_BLANK_LINE,
+        };
+        (0, 0)
+    } else if ch.is_whitespace() {
+        if *state == IN_WORD {
+            *state = AFTER_WORD;
+        }
+        (0, 0)
+    } else {
+        let started = (
+            (*state != IN_WORD) as usize,
+            (*state == AFTER_BLANK_LINE) as usize,
+        );
+        *state = IN_WORD;
+        started
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concat_is_independent_of_split() {
+        let s = "  foo bar\n\n\r\nbaz\n qux\n \nquux  ";
+
+        let whole = TextStats::from_text(s);
+
+        assert_eq!(whole.words(), 5);
+        assert_eq!(whole.paragraphs(), 3);
+
+        for split in 0..=s.len() {
+            let (left, right) = s.split_at(split);
+            assert_eq!(
+                whole,
+                TextStats::from_text(left).concat(TextStats::from_text(right))
+            );
+        }
+    }
+}
@@ -4,6 +4,7 @@
 //!
 //! [rclite]: https://github.com/fereidani/rclite
 
+use alloc::boxed::Box;
 use core::mem::MaybeUninit;
 use core::ptr::{addr_of_mut, NonNull};
 use core::sync::atomic;
@@ -69,6 +70,18 @@ impl<T> Arc<T> {
     pub(super) fn ptr_eq(this: &Self, other: &Self) -> bool {
         this.ptr == other.ptr
     }
+
+    /// Returns the number of bytes allocated on the heap by `Arc::new()`.
+    #[inline]
+    pub(super) const fn allocation_size() -> usize {
+        core::mem::size_of::<ArcInner<T>>()
+    }
+
+    /// Returns the number of `Arc`s pointing to the same value as `this`.
+    #[inline]
+    pub(super) fn strong_count(this: &Self) -> usize {
+        this.inner().counter.load(atomic::Ordering::Relaxed)
+    }
 }
 
 impl<T: Clone> Arc<T> {
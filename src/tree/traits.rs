@@ -30,6 +30,57 @@ pub trait Leaf: Summarize + BaseMeasured + AsSlice {}
 
 impl<T: Summarize + BaseMeasured + AsSlice> Leaf for T {}
 
+/// Leaves whose contents can be hashed with a
+/// [`RollingHash`](super::RollingHash), allowing internal nodes to cache the
+/// hash of their subtree.
+///
+/// The hash of a slice must only depend on its contents and not on how they're
+/// laid out in memory, so that the hash of a `Tree` doesn't depend on how its
+/// contents are split across its leaves.
+#[cfg(feature = "content-hash")]
+pub trait HashableLeaf: Leaf {
+    fn rolling_hash(slice: Self::Slice<'_>) -> super::RollingHash;
+}
+
+/// Leaves whose contents contain opening and closing delimiters, allowing
+/// internal nodes to cache the [`Nesting`](super::Nesting) of their subtree
+/// and `Tree`s to find unmatched delimiters in logarithmic time.
+#[cfg(feature = "bracket-depth")]
+pub trait NestingLeaf: Leaf {
+    fn nesting(slice: Self::Slice<'_>) -> super::Nesting;
+
+    /// Scans the slice forward starting at `from` and with the given initial
+    /// depth, returning the offset of the first closing delimiter that makes
+    /// the depth negative. If there isn't one `depth` is updated to the depth
+    /// at the end of the slice.
+    fn find_unmatched_close(
+        slice: Self::Slice<'_>,
+        from: Self::BaseMetric,
+        depth: &mut isize,
+    ) -> Option<Self::BaseMetric>;
+
+    /// Scans the slice backward starting at `to` and with the given initial
+    /// depth, returning the offset of the last opening delimiter that makes
+    /// the depth negative. If there isn't one `depth` is updated to the depth
+    /// at the start of the slice.
+    fn find_unmatched_open(
+        slice: Self::Slice<'_>,
+        to: Self::BaseMetric,
+        depth: &mut isize,
+    ) -> Option<Self::BaseMetric>;
+}
+
+/// Leaves containing text, allowing internal nodes to cache the
+/// [`TextStats`](super::TextStats) of their subtree and `Tree`s to count their
+/// words and paragraphs in logarithmic time.
+///
+/// Like for `HashableLeaf`s, the stats of a slice must only depend on its
+/// contents.
+#[cfg(feature = "text-stats")]
+pub trait StatsLeaf: Leaf {
+    fn text_stats(slice: Self::Slice<'_>) -> super::TextStats;
+}
+
 pub trait BalancedLeaf: Leaf + for<'a> From<Self::Slice<'a>> {
     /// Returns whether the leaf node is too small to be on its own and should
     /// be rebalanced with another leaf.
@@ -45,6 +96,19 @@ pub trait BalancedLeaf: Leaf + for<'a> From<Self::Slice<'a>> {
     );
 }
 
+/// Leaves whose contents can be moved to another leaf a bit at a time,
+/// allowing `Tree`s to be compacted.
+pub trait CompactableLeaf: BalancedLeaf {
+    /// Moves as much of the start of the `right` leaf to the end of the `left`
+    /// one as the latter can fit.
+    ///
+    /// The `right` leaf is left empty if all of it fits in the `left` one.
+    fn fill_leaves(
+        left: (&mut Self, &mut Self::Summary),
+        right: (&mut Self, &mut Self::Summary),
+    );
+}
+
 pub trait ReplaceableLeaf<M: Metric<Self::Summary>>: BalancedLeaf {
     type Replacement<'a>;
 
@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use core::ops::Range;
 
 use super::*;
@@ -68,6 +69,76 @@ impl<const ARITY: usize, L: BalancedLeaf + Clone> From<TreeSlice<'_, ARITY, L>>
 }
 
 impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
+    /// Appends another `Tree` to the end of this one.
+    ///
+    /// This runs in `O(log n)` and doesn't copy any leaves, except for the
+    /// ones at the seam between the two `Tree`s that may need to be balanced.
+    /// All the other nodes of `other` end up being shared with this `Tree`.
+    #[inline]
+    pub fn append(&mut self, other: Self)
+    where
+        L: BalancedLeaf + Clone + Default,
+    {
+        if other.root.is_empty() {
+            return;
+        }
+
+        if self.root.is_empty() {
+            *self = other;
+            return;
+        }
+
+        let mut left = core::mem::take(&mut self.root);
+
+        let mut right = other.root;
+
+        self.root = match left.depth().cmp(&right.depth()) {
+            core::cmp::Ordering::Greater => {
+                // The left root is deeper so it's also an inode.
+                let inode = Arc::make_mut(&mut left).get_internal_mut();
+
+                match inode.append_at_depth(right) {
+                    Some(extra) => {
+                        let extra = Arc::new(Node::Internal(extra));
+                        Arc::new(Node::Internal(Inode::from_children([
+                            left, extra,
+                        ])))
+                    },
+
+                    None => left,
+                }
+            },
+
+            core::cmp::Ordering::Less => {
+                // The right root is deeper so it's also an inode.
+                let inode = Arc::make_mut(&mut right).get_internal_mut();
+
+                match inode.prepend_at_depth(left) {
+                    Some(extra) => {
+                        let extra = Arc::new(Node::Internal(extra));
+                        Arc::new(Node::Internal(Inode::from_children([
+                            extra, right,
+                        ])))
+                    },
+
+                    None => right,
+                }
+            },
+
+            core::cmp::Ordering::Equal => {
+                Arc::make_mut(&mut left).balance(Arc::make_mut(&mut right));
+
+                if right.is_empty() {
+                    left
+                } else {
+                    Arc::new(Node::Internal(Inode::from_children([
+                        left, right,
+                    ])))
+                }
+            },
+        };
+    }
+
     #[doc(hidden)]
     pub fn assert_invariants(&self) {
         match &*self.root {
@@ -103,6 +174,83 @@ impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
         self.root.convert_measure(up_to)
     }
 
+    /// Returns the [`RollingHash`] of this `Tree` if it's already been computed
+    /// and cached in its root.
+    #[cfg(feature = "content-hash")]
+    #[inline]
+    pub fn cached_rolling_hash(&self) -> Option<RollingHash> {
+        match &*self.root {
+            Node::Internal(root) => root.cached_rolling_hash(),
+            Node::Leaf(_) => None,
+        }
+    }
+
+    /// Re-packs the leaves of the `Tree` so that they're as full as possible,
+    /// then rebuilds the internal nodes on top of them.
+    ///
+    /// The `is_dense` closure is called with the summary and the leaf count
+    /// of the internal nodes to decide whether their subtree is already
+    /// compact enough. Those subtrees are left untouched, and their nodes are
+    /// shared with the new `Tree` instead of being copied.
+    #[inline]
+    pub fn compact<F>(&mut self, is_dense: F)
+    where
+        F: Fn(&L::Summary, usize) -> bool,
+        L: CompactableLeaf + Clone + Default,
+    {
+        let Node::Internal(root) = &*self.root else { return };
+
+        if is_dense(root.summary(), root.leaf_count()) {
+            return;
+        }
+
+        *self = tree_compact::compact(&self.root, is_dense);
+    }
+
+    /// Returns the depth of the `Tree`, i.e. the number of edges between the
+    /// root and any of the leaves. A `Tree` whose root is a leaf has a depth
+    /// of zero.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.root.depth()
+    }
+
+    /// Returns the offset of the first closing delimiter at or after `from`
+    /// which isn't matched by an opening delimiter between `from` and itself.
+    ///
+    /// The [`Nesting`] of every internal node is computed once and then cached
+    /// until the node is mutated, so this runs in logarithmic time as long as
+    /// the caches on the path to `from` are warm.
+    #[cfg(feature = "bracket-depth")]
+    #[inline]
+    pub fn find_unmatched_close(
+        &self,
+        from: L::BaseMetric,
+    ) -> Option<L::BaseMetric>
+    where
+        L: NestingLeaf,
+    {
+        debug_assert!(from <= self.base_measure());
+        self.root.find_unmatched_close(from, &mut 0)
+    }
+
+    /// Returns the offset of the last opening delimiter before `to` which
+    /// isn't matched by a closing delimiter between itself and `to`.
+    ///
+    /// See [`find_unmatched_close()`](Self::find_unmatched_close()) for more.
+    #[cfg(feature = "bracket-depth")]
+    #[inline]
+    pub fn find_unmatched_open(
+        &self,
+        to: L::BaseMetric,
+    ) -> Option<L::BaseMetric>
+    where
+        L: NestingLeaf,
+    {
+        debug_assert!(to <= self.base_measure());
+        self.root.find_unmatched_open(to, &mut 0)
+    }
+
     /// Creates a new `Tree` from a sequence of leaves.
     ///
     /// If the iterator doesn't yield any items the `Tree` will contain a
@@ -134,6 +282,16 @@ impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
         Self { root: Arc::new(Node::Internal(root)) }
     }
 
+    /// Returns the number of bytes allocated on the heap for the nodes of this
+    /// `Tree`, including the ones shared with other `Tree`s.
+    ///
+    /// Note that this doesn't include any heap memory owned by the leaves
+    /// themselves, which should be accounted for by the caller.
+    #[inline]
+    pub fn heap_size(&self) -> usize {
+        Node::heap_size(&self.root)
+    }
+
     /// Returns the leaf containing the `measure`-th unit of the `M`-metric,
     /// plus the `M`-measure of all the leaves before it.
     #[inline]
@@ -167,6 +325,13 @@ impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
         M::measure(self.summary())
     }
 
+    /// Returns whether the two `Tree`s share the same root, in which case
+    /// their contents are equal.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.root, &other.root)
+    }
+
     /// Replaces a range of the `Tree` with the given replacement.
     #[track_caller]
     #[inline]
@@ -191,11 +356,33 @@ impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
         }
     }
 
+    /// Returns the [`RollingHash`] of the contents of this `Tree`.
+    ///
+    /// This is computed once for every internal node and then cached until
+    /// the node is mutated, so calling this after an edit only has to re-hash
+    /// the nodes on the paths to the leaves that changed.
+    #[cfg(feature = "content-hash")]
+    #[inline]
+    pub fn rolling_hash(&self) -> RollingHash
+    where
+        L: HashableLeaf,
+    {
+        self.root.fold()
+    }
+
     #[inline]
     pub(super) fn root(&self) -> &Arc<Node<ARITY, L>> {
         &self.root
     }
 
+    /// Returns the number of nodes (both internal and leaves) in this `Tree`
+    /// that are shared with at least one other `Tree`, e.g. one obtained by
+    /// cloning this one.
+    #[inline]
+    pub fn shared_node_count(&self) -> usize {
+        Node::shared_count(&self.root, false)
+    }
+
     /// Returns a slice of the `Tree` in the range of the given metric.
     #[track_caller]
     #[inline]
@@ -217,6 +404,19 @@ impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
         self.root.summary()
     }
 
+    /// Returns the [`TextStats`] of the contents of this `Tree`.
+    ///
+    /// Like the [`RollingHash`], these are computed once for every internal
+    /// node and then cached until the node is mutated.
+    #[cfg(feature = "text-stats")]
+    #[inline]
+    pub fn text_stats(&self) -> TextStats
+    where
+        L: StatsLeaf,
+    {
+        self.root.fold()
+    }
+
     /// Returns an iterator over the `M`-units of this `Tree`.
     #[inline]
     pub fn units<M>(&self) -> Units<'_, ARITY, L, M>
@@ -283,10 +483,10 @@ mod from_treeslice {
     /// Returns a `(root, invalid_first, invalid_last)` tuple where:
     ///
     /// - `root` is the internal node obtained by removing all the nodes before
-    /// `slice.before` and after `slice.before + slice.base_measure`,
+    ///   `slice.before` and after `slice.before + slice.base_measure`,
     ///
     /// - `invalid_{first,last}` are the number of invalid nodes contained in
-    /// the subtrees of the first and last child, respectively.
+    ///   the subtrees of the first and last child, respectively.
     ///
     /// Note that all the `Arc`s enclosing the nodes on the left and right side
     /// of the subtree under `root` are guaranteed to have a strong count of 1,
@@ -501,6 +701,113 @@ mod from_treeslice {
     }
 }
 
+mod tree_compact {
+    //! This module handles the logic used to compact `Tree`s.
+
+    use super::*;
+
+    /// Returns a new `Tree` containing the same leaves as the subtree rooted
+    /// at `root`, re-packed using [`CompactableLeaf::fill_leaves`]. All the
+    /// subtrees which `is_dense` are reused as they are.
+    #[inline]
+    pub(super) fn compact<const N: usize, L, F>(
+        root: &Arc<Node<N, L>>,
+        is_dense: F,
+    ) -> Tree<N, L>
+    where
+        F: Fn(&L::Summary, usize) -> bool,
+        L: CompactableLeaf + Clone + Default,
+    {
+        let mut compactor = Compactor {
+            is_dense,
+            compacted: Tree::default(),
+            builder: TreeBuilder::new(),
+            pending: None,
+        };
+
+        compactor.visit(root);
+
+        compactor.flush();
+
+        compactor.compacted
+    }
+
+    struct Compactor<const N: usize, L: Leaf, F> {
+        is_dense: F,
+
+        /// The `Tree` built so far, containing both the dense subtrees and the
+        /// re-packed leaves that came before the current run.
+        compacted: Tree<N, L>,
+
+        /// The builder for the current run of re-packed leaves.
+        builder: TreeBuilder<N, L>,
+
+        /// The last leaf of the current run, which may still be able to take
+        /// the contents of the next leaf.
+        pending: Option<Lnode<L>>,
+    }
+
+    impl<const N: usize, L, F> Compactor<N, L, F>
+    where
+        F: Fn(&L::Summary, usize) -> bool,
+        L: CompactableLeaf + Clone + Default,
+    {
+        /// Builds the current run of leaves and appends it to the compacted
+        /// `Tree`.
+        #[inline]
+        fn flush(&mut self) {
+            if let Some(pending) = self.pending.take() {
+                self.builder.append_lnode(pending);
+            }
+
+            let run = core::mem::take(&mut self.builder).build();
+
+            self.compacted.append(run);
+        }
+
+        #[inline]
+        fn push_leaf(&mut self, mut leaf: Lnode<L>) {
+            let Some(pending) = &mut self.pending else {
+                self.pending = Some(leaf);
+                return;
+            };
+
+            // This moves as much of `leaf` as possible into `pending`. If
+            // there's something left over then `pending` is full and `leaf`
+            // becomes the next one to be filled.
+            pending.fill(&mut leaf);
+
+            if !leaf.is_empty() {
+                let filled = core::mem::replace(pending, leaf);
+                self.builder.append_lnode(filled);
+            }
+        }
+
+        #[inline]
+        fn visit(&mut self, node: &Arc<Node<N, L>>) {
+            match &**node {
+                Node::Internal(inode)
+                    if (self.is_dense)(
+                        inode.summary(),
+                        inode.leaf_count(),
+                    ) =>
+                {
+                    self.flush();
+                    self.compacted.append(Tree { root: Arc::clone(node) });
+                },
+
+                Node::Internal(inode) => {
+                    for child in inode.children() {
+                        self.visit(child);
+                    }
+                },
+
+                Node::Leaf(leaf) => self.push_leaf(leaf.clone()),
+            }
+        }
+    }
+}
+
 mod tree_replace {
     //! This module contains the logic used to implement [`Tree::replace()`].
 
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 use super::traits::{BalancedLeaf, Leaf};
 use super::{Arc, Inode, Lnode, Node, Tree};
 
@@ -40,9 +42,16 @@ impl<const ARITY: usize, L: Leaf> Default for TreeBuilder<ARITY, L> {
 impl<const ARITY: usize, L: Leaf> TreeBuilder<ARITY, L> {
     #[inline]
     pub fn append(&mut self, leaf: L) {
+        self.append_lnode(Lnode::from(leaf));
+    }
+
+    /// Same as [`append`](Self::append()), except it takes a leaf node whose
+    /// summary has already been computed.
+    #[inline]
+    pub(super) fn append_lnode(&mut self, lnode: Lnode<L>) {
         debug_assert!(self.leaves.len() < ARITY);
 
-        self.leaves.push(Arc::new(Node::Leaf(Lnode::from(leaf))));
+        self.leaves.push(Arc::new(Node::Leaf(lnode)));
 
         if self.leaves.len() < ARITY {
             return;
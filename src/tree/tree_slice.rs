@@ -177,6 +177,28 @@ impl<'a, const ARITY: usize, L: Leaf> TreeSlice<'a, ARITY, L> {
         }
     }
 
+    /// Returns the `F` of the contents of this `TreeSlice`, folding the
+    /// values of its first and last leaf slices with the ones cached in the
+    /// internal nodes of the `Tree` for all the leaves in between.
+    #[cfg(any(feature = "content-hash", feature = "text-stats"))]
+    #[inline]
+    fn fold<F: CachedFold<L>>(&self) -> F {
+        if self.leaf_count == 1 {
+            return F::of_leaf(self.start_slice);
+        }
+
+        let start = L::BaseMetric::measure(&self.offset)
+            + L::BaseMetric::measure(&self.start_summary);
+
+        let end = L::BaseMetric::measure(&self.offset)
+            + L::BaseMetric::measure(&self.summary)
+            - L::BaseMetric::measure(&self.end_summary);
+
+        F::of_leaf(self.start_slice)
+            .concat(self.root.fold_between(start, end))
+            .concat(F::of_leaf(self.end_slice))
+    }
+
     #[inline]
     pub fn leaf_count(&self) -> usize {
         self.leaf_count
@@ -195,11 +217,33 @@ impl<'a, const ARITY: usize, L: Leaf> TreeSlice<'a, ARITY, L> {
         M::measure(self.summary())
     }
 
+    /// Returns the [`RollingHash`] of the contents of this `TreeSlice`.
+    ///
+    /// Only the first and last leaf slices are hashed from scratch, all the
+    /// leaves in between use the hashes cached in the internal nodes of the
+    /// `Tree`.
+    #[cfg(feature = "content-hash")]
+    #[inline]
+    pub fn rolling_hash(&self) -> RollingHash
+    where
+        L: HashableLeaf,
+    {
+        self.fold()
+    }
+
     #[inline]
     pub(super) fn root(&self) -> &'a Arc<Node<ARITY, L>> {
         self.root
     }
 
+    /// Returns a [`Tree`] sharing this slice's root, together with the
+    /// summary of all the leaves in that `Tree` that come before the start of
+    /// this slice.
+    #[inline]
+    pub fn root_tree(&self) -> (Tree<ARITY, L>, L::Summary) {
+        (Tree { root: Arc::clone(self.root) }, self.offset.clone())
+    }
+
     #[inline]
     pub fn start_slice(&self) -> L::Slice<'a> {
         self.start_slice
@@ -214,6 +258,20 @@ impl<'a, const ARITY: usize, L: Leaf> TreeSlice<'a, ARITY, L> {
     pub fn summary(&self) -> &L::Summary {
         &self.summary
     }
+
+    /// Returns the [`TextStats`] of the contents of this `TreeSlice`.
+    ///
+    /// Just like the [`rolling_hash()`](Self::rolling_hash()), only the first
+    /// and last leaf slices are scanned, all the leaves in between use the
+    /// stats cached in the internal nodes of the `Tree`.
+    #[cfg(feature = "text-stats")]
+    #[inline]
+    pub fn text_stats(&self) -> TextStats
+    where
+        L: StatsLeaf,
+    {
+        self.fold()
+    }
 }
 
 impl<'a, const ARITY: usize, L: Leaf> TreeSlice<'a, ARITY, L>
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 use super::traits::{DoubleEndedUnitMetric, Leaf, Metric, UnitMetric};
 use super::tree_slice;
 use super::{Arc, Lnode, Node, Tree, TreeSlice};
@@ -294,7 +296,9 @@ where
             is_initialized: false,
             path: Vec::with_capacity(tree_slice.root().depth()),
             leaf_node: tree_slice.root(),
-            yielded_in_leaf: L::Summary::default(),
+            // If the slice is contained in a single leaf its root is that leaf,
+            // and this is already how much of it comes before the slice.
+            yielded_in_leaf: tree_slice.offset.clone(),
             start_slice: L::Slice::default(),
             start_summary: L::Summary::default(),
             first_slice: Some((
@@ -353,8 +357,12 @@ impl<'a, const N: usize, L: Leaf, M: UnitMetric<L>> UnitsForward<'a, N, L, M> {
 
                     match self.first_slice.take() {
                         Some((slice, summary)) => {
-                            self.yielded_in_leaf =
-                                leaf.summary().clone() - &summary;
+                            // Otherwise the first slice extends to the end of
+                            // its leaf.
+                            if !self.path.is_empty() {
+                                self.yielded_in_leaf =
+                                    leaf.summary().clone() - &summary;
+                            }
 
                             self.start_slice = slice;
                             self.start_summary = summary;
@@ -951,6 +959,11 @@ struct UnitsBackward<'a, const N: usize, L: Leaf, M: Metric<L::Summary>> {
     /// [`previous`](Self::previous()).
     end_summary: L::Summary,
 
+    /// The summary of the part of `leaf_node` before `end_slice`. It's only
+    /// non-empty if `end_slice` is part of the first slice in the yielding
+    /// range.
+    end_slice_offset: L::Summary,
+
     /// The first slice in the yielding range and its summary. It's only set if
     /// we're iterating over a `TreeSlice`.
     first_slice: Option<(L::Slice<'a>, L::Summary)>,
@@ -980,6 +993,7 @@ impl<const N: usize, L: Leaf, M: Metric<L::Summary>> Clone
             first_slice: self.first_slice.clone(),
             last_slice: self.last_slice.clone(),
             end_summary: self.end_summary.clone(),
+            end_slice_offset: self.end_slice_offset.clone(),
             ..*self
         }
     }
@@ -999,6 +1013,7 @@ where
             yielded_in_leaf: L::Summary::default(),
             end_slice: L::Slice::default(),
             end_summary: L::Summary::default(),
+            end_slice_offset: L::Summary::default(),
             first_slice: None,
             last_slice: None,
             base_start: L::BaseMetric::zero(),
@@ -1024,6 +1039,9 @@ where
             yielded_in_leaf: L::Summary::default(),
             end_slice: L::Slice::default(),
             end_summary: L::Summary::default(),
+            // If the slice is contained in a single leaf its root is that leaf,
+            // and this is already how much of it comes before the slice.
+            end_slice_offset: tree_slice.offset.clone(),
             first_slice: Some((
                 tree_slice.start_slice,
                 tree_slice.start_summary.clone(),
@@ -1083,8 +1101,15 @@ impl<'a, const N: usize, L: Leaf, M: DoubleEndedUnitMetric<L>>
 
                     match self.last_slice.take() {
                         Some((slice, summary)) => {
-                            self.yielded_in_leaf =
-                                leaf.summary().clone() - &summary;
+                            // Otherwise the last slice starts at the start of
+                            // its leaf.
+                            if !self.path.is_empty() {
+                                self.end_slice_offset = L::Summary::default();
+                            }
+
+                            self.yielded_in_leaf = leaf.summary().clone()
+                                - &summary
+                                - &self.end_slice_offset;
 
                             self.end_slice = slice;
                             self.end_summary = summary;
@@ -1297,7 +1322,7 @@ impl<'a, const N: usize, L: Leaf, M: DoubleEndedUnitMetric<L>>
             return (
                 TreeSlice {
                     root: self.leaf_node,
-                    offset: L::Summary::default(),
+                    offset: self.end_slice_offset.clone(),
                     summary: end_summary.clone(),
                     start_slice: end_slice,
                     start_summary: end_summary.clone(),
@@ -1326,9 +1351,12 @@ impl<'a, const N: usize, L: Leaf, M: DoubleEndedUnitMetric<L>>
 
             if contains_first_slice {
                 let (slice, summary) = self.first_slice.take().unwrap();
+                self.end_slice_offset =
+                    previous_leaf.summary().clone() - &summary;
                 self.end_slice = slice;
                 self.end_summary = summary;
             } else {
+                self.end_slice_offset = L::Summary::default();
                 self.end_slice = previous_leaf.as_slice();
                 self.end_summary = previous_leaf.summary().clone();
             };
@@ -1395,7 +1423,7 @@ impl<'a, const N: usize, L: Leaf, M: DoubleEndedUnitMetric<L>>
             L::BaseMetric::measure(&rest_summary) > L::BaseMetric::zero()
         );
 
-        let offset = rest_summary.clone();
+        let offset = self.end_slice_offset.clone() + &rest_summary;
 
         self.yielded_in_leaf += &advance;
         self.end_slice = rest;
@@ -1587,9 +1615,12 @@ impl<'a, const N: usize, L: Leaf, M: DoubleEndedUnitMetric<L>>
 
             if contains_first_slice {
                 let (slice, summary) = self.first_slice.take().unwrap();
+                self.end_slice_offset =
+                    previous_leaf.summary().clone() - &summary;
                 self.end_slice = slice;
                 self.end_summary = summary;
             } else {
+                self.end_slice_offset = L::Summary::default();
                 self.end_slice = previous_leaf.as_slice();
                 self.end_summary = previous_leaf.summary().clone();
             };
@@ -1645,8 +1676,11 @@ impl<'a, const N: usize, L: Leaf, M: DoubleEndedUnitMetric<L>>
                 > self.base_remaining;
 
             if contains_first_slice {
-                self.first_slice.take().unwrap()
+                let (slice, summary) = self.first_slice.take().unwrap();
+                self.end_slice_offset = leaf.summary().clone() - &summary;
+                (slice, summary)
             } else {
+                self.end_slice_offset = L::Summary::default();
                 (leaf.as_slice(), leaf.summary().clone())
             }
         };
@@ -1749,7 +1783,7 @@ impl<'a, const N: usize, L: Leaf, M: DoubleEndedUnitMetric<L>>
                 M::remainder(self.end_slice, &self.end_summary);
 
             if L::BaseMetric::measure(&summary) > L::BaseMetric::zero() {
-                let offset = rest_summary.clone();
+                let offset = self.end_slice_offset.clone() + &rest_summary;
 
                 self.yielded_in_leaf += &summary;
                 self.end_slice = rest;
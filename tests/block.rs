@@ -0,0 +1,218 @@
+use crop::{ColumnUnit, Rope};
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, MEDIUM, SMALL};
+
+/// Returns the byte range of the chars of `line` starting at a char column in
+/// `start..end`, and how many columns `line` falls short of `start`.
+fn char_span(line: &str, start: usize, end: usize) -> (usize, usize, usize) {
+    let offset_of = |column: usize| {
+        line.char_indices().nth(column).map_or(line.len(), |(idx, _)| idx)
+    };
+    let missing = start.saturating_sub(line.chars().count());
+    (offset_of(start), offset_of(end), missing)
+}
+
+fn block_str(s: &str, start: usize, end: usize) -> Vec<&str> {
+    s.lines()
+        .map(|line| {
+            let (start, end, _) = char_span(line, start, end);
+            &line[start..end]
+        })
+        .collect()
+}
+
+fn replace_block_str(
+    s: &str,
+    start: usize,
+    end: usize,
+    replacements: &[&str],
+) -> String {
+    let mut replacements = replacements.iter();
+
+    s.split_inclusive('\n')
+        .map(|raw_line| {
+            let line = raw_line.trim_end_matches('\n').trim_end_matches('\r');
+            let terminator = &raw_line[line.len()..];
+            let (start, end, missing) = char_span(line, start, end);
+            let replacement = replacements.next().copied().unwrap_or("");
+            let padding = if replacement.is_empty() { 0 } else { missing };
+            format!(
+                "{}{}{replacement}{}{terminator}",
+                &line[..start],
+                " ".repeat(padding),
+                &line[end..]
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn block_slice_bytes() {
+    let r = Rope::from("aé\nbbbb\r\n\nccccc");
+
+    let block = r.block_slice(.., 1..3, ColumnUnit::Bytes).collect::<Vec<_>>();
+
+    // The "é" starts at byte 1, so it's part of the block even though it
+    // ends after byte 3.
+    assert_eq!(block, ["é", "bb", "", "cc"]);
+
+    let block = r.block_slice(.., 2..4, ColumnUnit::Bytes).collect::<Vec<_>>();
+
+    assert_eq!(block, ["", "bb", "", "cc"]);
+}
+
+#[test]
+fn block_slice_chars() {
+    let r = Rope::from("aéb\n🦀🦀🦀🦀\nc\n");
+
+    let block = r.block_slice(.., 1..3, ColumnUnit::Chars).collect::<Vec<_>>();
+
+    assert_eq!(block, ["éb", "🦀🦀", ""]);
+
+    let block =
+        r.block_slice(1..2, 2.., ColumnUnit::Chars).collect::<Vec<_>>();
+
+    assert_eq!(block, ["🦀🦀"]);
+}
+
+#[test]
+fn block_slice_both_ways() {
+    let r = Rope::from(SMALL);
+
+    let forward = r.block_slice(.., 3..20, ColumnUnit::Chars);
+    let backward = forward.clone().rev().collect::<Vec<_>>();
+
+    assert_eq!(forward.len(), r.line_len());
+    assert!(forward.eq(backward.into_iter().rev()));
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn block_slice_random() {
+    let mut rng = rand::thread_rng();
+
+    for s in [SMALL, MEDIUM, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let start = rng.gen_range(0..=20);
+            let end = rng.gen_range(start..=40);
+
+            let block = r.block_slice(.., start..end, ColumnUnit::Chars);
+
+            assert!(block.eq(block_str(s, start, end)));
+        }
+    }
+}
+
+#[cfg(feature = "display-width")]
+#[test]
+fn block_slice_cells() {
+    let r = Rope::from("abcdef\nこんにちは\n");
+
+    let block = r.block_slice(.., 2..6, ColumnUnit::Cells).collect::<Vec<_>>();
+
+    assert_eq!(block, ["cdef", "んに"]);
+
+    // "ん" starts at cell 2, which is before the start of the range.
+    let block = r.block_slice(.., 3..6, ColumnUnit::Cells).collect::<Vec<_>>();
+
+    assert_eq!(block, ["def", "に"]);
+}
+
+#[cfg(feature = "display-width")]
+#[test]
+fn block_slice_cells_zero_width() {
+    // The combining acute accent is zero cells wide, so it starts at the same
+    // column as the character after it, but it belongs to the "e" before it.
+    let r = Rope::from("e\u{301}x\nab\u{301}\u{301}c\n");
+
+    let block = r.block_slice(.., 0..1, ColumnUnit::Cells).collect::<Vec<_>>();
+
+    assert_eq!(block, ["e\u{301}", "a"]);
+
+    let block = r.block_slice(.., 1..2, ColumnUnit::Cells).collect::<Vec<_>>();
+
+    assert_eq!(block, ["x", "b\u{301}\u{301}"]);
+
+    let mut r = r;
+
+    r.replace_block(.., 0..1, ColumnUnit::Cells, ["E", "A"]);
+
+    assert_eq!(r, "Ex\nAb\u{301}\u{301}c\n");
+}
+
+#[test]
+fn replace_block_pads_short_lines() {
+    let mut r = Rope::from("aaaa\nb\r\n\ncccccc");
+
+    r.replace_block(.., 3..5, ColumnUnit::Chars, ["X", "Y", "", "Z"]);
+
+    assert_eq!(r, "aaaX\nb  Y\r\n\ncccZc");
+}
+
+#[test]
+fn replace_block_runs_out_of_lines() {
+    let mut r = Rope::from("aaaa\nbbbb\ncccc\n");
+
+    r.replace_block(.., 1..3, ColumnUnit::Bytes, ["-"]);
+
+    assert_eq!(r, "a-a\nbb\ncc\n");
+
+    r.replace_block(1..2, 1..1, ColumnUnit::Bytes, ["1", "2", "3"]);
+
+    assert_eq!(r, "a-a\nb1b\ncc\n");
+}
+
+#[test]
+fn replace_block_empty_line_range() {
+    let mut r = Rope::from("aaaa\nbbbb\n");
+
+    r.replace_block(1..1, 0..2, ColumnUnit::Chars, ["x"]);
+
+    assert_eq!(r, "aaaa\nbbbb\n");
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn replace_block_random() {
+    let mut rng = rand::thread_rng();
+
+    for s in [SMALL, MEDIUM, CURSED_LIPSUM] {
+        let mut r = Rope::from(s);
+
+        let line_len = s.lines().count();
+
+        let replacements = ["", "x", "🦀🦀", "foo bar"];
+
+        let replacements = (0..line_len)
+            .map(|_| replacements[rng.gen_range(0..replacements.len())])
+            .collect::<Vec<_>>();
+
+        let start = rng.gen_range(0..=20);
+        let end = rng.gen_range(start..=40);
+
+        r.replace_block(.., start..end, ColumnUnit::Chars, &replacements);
+        r.assert_invariants();
+
+        assert_eq!(r, replace_block_str(s, start, end, &replacements));
+    }
+}
+
+#[test]
+#[should_panic]
+fn block_slice_column_start_after_end() {
+    let r = Rope::from("aaaa\nbbbb\n");
+    #[allow(clippy::reversed_empty_ranges)]
+    let _ = r.block_slice(.., 3..1, ColumnUnit::Chars);
+}
+
+#[test]
+#[should_panic]
+fn replace_block_line_out_of_bounds() {
+    let mut r = Rope::from("aaaa\nbbbb\n");
+    r.replace_block(..3, 0..1, ColumnUnit::Chars, ["a"]);
+}
@@ -0,0 +1,236 @@
+#![cfg(feature = "bracket-depth")]
+
+use crop::Rope;
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, MEDIUM};
+
+fn delta(byte: u8) -> isize {
+    match byte {
+        b'(' | b'[' | b'{' => 1,
+        b')' | b']' | b'}' => -1,
+        _ => 0,
+    }
+}
+
+fn pair(open: u8, close: u8) -> bool {
+    matches!((open, close), (b'(', b')') | (b'[', b']') | (b'{', b'}'))
+}
+
+/// Finds the first unmatched closing bracket at or after `from` by scanning
+/// the string.
+fn unmatched_close(s: &str, from: usize) -> Option<usize> {
+    let mut depth = 0;
+    (from..s.len()).find(|&idx| {
+        depth += delta(s.as_bytes()[idx]);
+        depth < 0
+    })
+}
+
+/// Finds the last unmatched opening bracket before `to` by scanning the
+/// string.
+fn unmatched_open(s: &str, to: usize) -> Option<usize> {
+    let mut depth = 0;
+    (0..to).rev().find(|&idx| {
+        depth -= delta(s.as_bytes()[idx]);
+        depth < 0
+    })
+}
+
+fn matching_bracket(s: &str, idx: usize) -> Option<usize> {
+    let byte = s.as_bytes()[idx];
+
+    let found = match delta(byte) {
+        1 => unmatched_close(s, idx + 1)?,
+        -1 => unmatched_open(s, idx)?,
+        _ => return None,
+    };
+
+    let other = s.as_bytes()[found];
+
+    (pair(byte, other) || pair(other, byte)).then_some(found)
+}
+
+fn enclosing_brackets(s: &str, offset: usize) -> Option<(usize, usize)> {
+    let open = unmatched_open(s, offset)?;
+    let close = unmatched_close(s, offset)?;
+    pair(s.as_bytes()[open], s.as_bytes()[close]).then_some((open, close))
+}
+
+fn random_brackets(len: usize) -> String {
+    let mut rng = rand::thread_rng();
+
+    (0..len)
+        .map(|_| match rng.gen_range(0..10) {
+            0 => '(',
+            1 => ')',
+            2 => '[',
+            3 => ']',
+            4 => '{',
+            5 => '}',
+            6 => 'é',
+            7 => '\n',
+            _ => 'a',
+        })
+        .collect()
+}
+
+/// Nests `depth` levels of brackets around a bit of text, so that the
+/// matching brackets end up in different subtrees.
+fn nested(depth: usize) -> String {
+    let mut s = String::new();
+    for i in 0..depth {
+        s.push(['(', '[', '{'][i % 3]);
+        s.push_str("ab ");
+    }
+    for i in (0..depth).rev() {
+        s.push_str(" cd");
+        s.push([')', ']', '}'][i % 3]);
+    }
+    s
+}
+
+fn assert_matches_str(r: &Rope, s: &str) {
+    for idx in 0..s.len() {
+        assert_eq!(matching_bracket(s, idx), r.matching_bracket(idx), "{idx}");
+    }
+
+    for offset in 0..=s.len() {
+        assert_eq!(
+            enclosing_brackets(s, offset),
+            r.enclosing_brackets(offset),
+            "{offset}"
+        );
+    }
+}
+
+#[test]
+fn brackets_empty() {
+    let r = Rope::new();
+    assert_eq!(None, r.enclosing_brackets(0));
+}
+
+#[test]
+fn brackets_nested() {
+    let s = nested(300);
+    let r = Rope::from(s.as_str());
+
+    assert_eq!(Some(s.len() - 1), r.matching_bracket(0));
+    assert_eq!(Some(0), r.matching_bracket(s.len() - 1));
+    assert_matches_str(&r, &s);
+}
+
+#[test]
+fn brackets_mismatched() {
+    let r = Rope::from("(a]b)");
+
+    assert_eq!(None, r.matching_bracket(0));
+    assert_eq!(None, r.matching_bracket(2));
+    assert_eq!(None, r.matching_bracket(4));
+    assert_eq!(None, r.enclosing_brackets(1));
+    assert_eq!(None, r.enclosing_brackets(3));
+}
+
+#[test]
+fn brackets_no_brackets() {
+    let r = Rope::from(CURSED_LIPSUM);
+    assert_matches_str(&r, CURSED_LIPSUM);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn brackets_random() {
+    for len in [10, 100, 1000, 5000] {
+        let s = random_brackets(len);
+        let r = Rope::from(s.as_str());
+        assert_matches_str(&r, &s);
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn brackets_after_edits() {
+    let mut rng = rand::thread_rng();
+
+    let mut s = nested(200);
+    let mut r = Rope::from(s.as_str());
+
+    for _ in 0..100 {
+        // Keep a clone around so that the edited nodes are shared and have
+        // to be cloned, together with their caches, before being mutated.
+        let clone = r.clone();
+
+        let mut start = rng.gen_range(0..=s.len());
+        let mut end = rng.gen_range(start..=(start + 5).min(s.len()));
+
+        while !s.is_char_boundary(start) {
+            start -= 1;
+        }
+
+        while !s.is_char_boundary(end) {
+            end += 1;
+        }
+        let text = random_brackets(rng.gen_range(0..5));
+
+        r.replace(start..end, &text);
+        s.replace_range(start..end, &text);
+
+        for _ in 0..20 {
+            let offset = rng.gen_range(0..=s.len());
+
+            assert_eq!(
+                enclosing_brackets(&s, offset),
+                r.enclosing_brackets(offset),
+                "{offset}"
+            );
+
+            if offset < s.len() {
+                assert_eq!(
+                    matching_bracket(&s, offset),
+                    r.matching_bracket(offset),
+                    "{offset}"
+                );
+            }
+        }
+
+        drop(clone);
+    }
+
+    assert_matches_str(&r, &s);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn brackets_medium() {
+    let mut rng = rand::thread_rng();
+
+    let s = MEDIUM.replace("the", "(the").replace("and", "and)");
+    let r = Rope::from(s.as_str());
+
+    for _ in 0..1000 {
+        let offset = rng.gen_range(0..s.len());
+
+        assert_eq!(matching_bracket(&s, offset), r.matching_bracket(offset));
+
+        assert_eq!(
+            enclosing_brackets(&s, offset),
+            r.enclosing_brackets(offset)
+        );
+    }
+}
+
+#[should_panic]
+#[test]
+fn matching_bracket_out_of_bounds() {
+    let r = Rope::from("()");
+    r.matching_bracket(2);
+}
+
+#[should_panic]
+#[test]
+fn enclosing_brackets_out_of_bounds() {
+    let r = Rope::from("()");
+    r.enclosing_brackets(3);
+}
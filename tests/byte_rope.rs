@@ -0,0 +1,119 @@
+use crop::byte_rope::ByteRope;
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL};
+
+#[test]
+fn byte_rope_empty() {
+    let r = ByteRope::new();
+
+    assert!(r.is_empty());
+    assert_eq!(r.byte_len(), 0);
+    assert_eq!(r.line_len(), 0);
+    assert_eq!(r.chunks().next(), None);
+    assert_eq!(r.bytes().next(), None);
+    assert_eq!(r, &b""[..]);
+
+    r.assert_invariants();
+}
+
+#[test]
+fn byte_rope_invalid_utf8() {
+    // The "🦀" is encoded as `[0xF0, 0x9F, 0xA6, 0x80]`.
+    let mut r = ByteRope::from("🦀".as_bytes());
+
+    r.insert(2, b"\xFF\xFF");
+    assert_eq!(r, &b"\xF0\x9F\xFF\xFF\xA6\x80"[..]);
+
+    r.delete(1..3);
+    assert_eq!(r, &b"\xF0\xFF\xA6\x80"[..]);
+
+    assert_eq!(r.byte_slice(1..2), &b"\xFF"[..]);
+    assert_eq!(r.byte(3), 0x80);
+
+    r.assert_invariants();
+}
+
+#[test]
+fn byte_rope_from_bytes() {
+    for s in ["", "a", SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = ByteRope::from(s.as_bytes());
+
+        r.assert_invariants();
+
+        assert_eq!(r, s.as_bytes());
+        assert_eq!(r.byte_len(), s.len());
+        assert!(r.bytes().eq(s.bytes()));
+        assert!(r.bytes().rev().eq(s.bytes().rev()));
+        assert_eq!(Vec::from(r.clone()), s.as_bytes());
+        assert_eq!(ByteRope::from(r.byte_slice(..)), r);
+    }
+}
+
+#[test]
+fn byte_rope_lines() {
+    for s in ["", "\n", "a\n", "a\nb", SMALL, MEDIUM, CURSED_LIPSUM] {
+        let r = ByteRope::from(s.as_bytes());
+
+        let line_len = s.lines().count();
+
+        assert_eq!(r.line_len(), line_len);
+        assert_eq!(r.byte_slice(..).line_len(), line_len);
+
+        let mut byte_offset = 0;
+
+        for (line_offset, line) in s.split_inclusive('\n').enumerate() {
+            assert_eq!(r.byte_of_line(line_offset), byte_offset);
+            assert_eq!(r.line_of_byte(byte_offset), line_offset);
+            assert_eq!(
+                r.line_slice(line_offset..line_offset + 1),
+                line.as_bytes()
+            );
+            byte_offset += line.len();
+        }
+
+        assert_eq!(r.byte_of_line(line_len), s.len());
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn byte_rope_random_edits() {
+    let mut rng = rand::thread_rng();
+
+    for s in [SMALL, MEDIUM, CURSED_LIPSUM] {
+        let mut r = ByteRope::from(s.as_bytes());
+        let mut v = s.as_bytes().to_vec();
+
+        for _ in 0..100 {
+            let start = rng.gen_range(0..=v.len());
+            let end = rng.gen_range(start..=(start + 20).min(v.len()));
+
+            let replacement = (0..rng.gen_range(0..40))
+                .map(|_| rng.gen::<u8>())
+                .collect::<Vec<_>>();
+
+            r.replace(start..end, &replacement);
+            v.splice(start..end, replacement);
+
+            r.assert_invariants();
+            assert_eq!(r, v);
+
+            let start = rng.gen_range(0..=v.len());
+            let end = rng.gen_range(start..=v.len());
+
+            let slice = r.byte_slice(start..end);
+            assert_eq!(slice, v[start..end]);
+            assert!(slice.bytes().eq(v[start..end].iter().copied()));
+
+            let line_breaks = v.iter().filter(|&&b| b == b'\n').count();
+            assert_eq!(r.line_of_byte(v.len()), line_breaks);
+        }
+
+        r.delete(..);
+        r.assert_invariants();
+        assert!(r.is_empty());
+    }
+}
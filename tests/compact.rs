@@ -0,0 +1,137 @@
+mod common;
+
+use common::{CURSED_LIPSUM, MEDIUM};
+use crop::Rope;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Returns a `Rope` with a lot of partially filled chunks, obtained by
+/// replacing random ranges of `MEDIUM` with `CURSED_LIPSUM`, together with
+/// the `String` it should be equal to.
+fn fragmented() -> (Rope, String) {
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let mut r = Rope::from(MEDIUM);
+    let mut s = String::from(MEDIUM);
+
+    for _ in 0..200 {
+        let len = r.byte_len();
+        let start = rng.gen_range(0..len);
+        let end = (start + rng.gen_range(0..64)).min(len);
+
+        if !(s.is_char_boundary(start) && s.is_char_boundary(end)) {
+            continue;
+        }
+
+        r.replace(start..end, CURSED_LIPSUM);
+        s.replace_range(start..end, CURSED_LIPSUM);
+    }
+
+    (r, s)
+}
+
+#[test]
+fn compact_empty() {
+    let mut r = Rope::new();
+    r.compact();
+    r.assert_invariants();
+    assert_eq!(r, "");
+    assert_eq!(None, r.compact_incremental(0, 1));
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn compact_fragmented() {
+    let (mut r, s) = fragmented();
+
+    let leaves_before = r.stats().leaf_count;
+
+    r.compact();
+    r.assert_invariants();
+
+    assert_eq!(r, s);
+    assert!(r.stats().leaf_count <= leaves_before);
+    assert!(r.stats().fill_ratio >= 0.5);
+
+    // Compacting an already compacted rope is a no-op.
+    let r2 = r.clone();
+    r.compact();
+    r.assert_invariants();
+    assert_eq!(r.stats().shared_nodes, r2.stats().shared_nodes);
+}
+
+/// Tests that compacting fills every chunk, even when they're all already
+/// more than half full.
+#[cfg_attr(miri, ignore)]
+#[test]
+fn compact_fills_chunks() {
+    let mut r = Rope::from("a".repeat(200_000));
+
+    for offset in (0..r.byte_len()).step_by(1000).rev() {
+        r.delete(offset + 560..offset + 1000);
+    }
+
+    assert!(r.stats().fill_ratio > 0.5);
+
+    r.compact();
+    r.assert_invariants();
+
+    assert_eq!(r, "a".repeat(112_000));
+    assert!(r.stats().fill_ratio > 0.99);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn compact_preserves_sharing() {
+    let mut r = Rope::from(MEDIUM.repeat(4));
+
+    // Fragment a small part of the rope.
+    for _ in 0..100 {
+        r.delete(1000..1010);
+        r.insert(500, "a");
+    }
+
+    let clone = r.clone();
+
+    r.compact();
+    r.assert_invariants();
+
+    assert_eq!(r, clone);
+    assert!(r.stats().shared_nodes > 0);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn compact_incremental() {
+    let (r, s) = fragmented();
+
+    for max_chunks in [1, 2, 7, 64, usize::MAX] {
+        let mut r = r.clone();
+        let mut offset = 0;
+        let mut steps = 0;
+
+        while let Some(next) = r.compact_incremental(offset, max_chunks) {
+            assert!(next > offset);
+            offset = next;
+            steps += 1;
+        }
+
+        r.assert_invariants();
+        assert_eq!(r, s);
+        assert!(steps <= r.byte_len());
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn compact_incremental_from_middle() {
+    let (mut r, s) = fragmented();
+
+    let mut offset = r.byte_len() / 2 + 3;
+
+    while let Some(next) = r.compact_incremental(offset, 3) {
+        offset = next;
+    }
+
+    r.assert_invariants();
+    assert_eq!(r, s);
+}
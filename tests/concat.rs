@@ -0,0 +1,164 @@
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+use crop::Rope;
+use rand::Rng;
+
+#[test]
+fn append_empty() {
+    let mut r = Rope::from(TINY);
+    r.append(Rope::new());
+    r.assert_invariants();
+    assert_eq!(r, TINY);
+
+    let mut r = Rope::new();
+    r.append(Rope::from(TINY));
+    r.assert_invariants();
+    assert_eq!(r, TINY);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn append_different_depths() {
+    let texts = ["", "a", "\n", TINY, SMALL, CURSED_LIPSUM, MEDIUM];
+
+    for left in texts {
+        for right in texts {
+            let mut r = Rope::from(left);
+            r.append(Rope::from(right));
+            r.assert_invariants();
+            assert_eq!(r, format!("{left}{right}"));
+
+            let mut r = Rope::from(right);
+            r.prepend(Rope::from(left));
+            r.assert_invariants();
+            assert_eq!(r, format!("{left}{right}"));
+        }
+    }
+}
+
+#[test]
+fn append_trailing_newline() {
+    let mut r = Rope::from("foo\n");
+    r.append(Rope::from("bar"));
+    assert_eq!(r.line_len(), 2);
+
+    r.append(Rope::from("\n"));
+    assert_eq!(r.line_len(), 2);
+
+    r.append(Rope::new());
+    assert_eq!(r.line_len(), 2);
+
+    let mut r = Rope::from("foo\n");
+    r.prepend(Rope::from("bar"));
+    assert_eq!(r.line_len(), 1);
+}
+
+#[test]
+fn add() {
+    let r = Rope::from("Hello") + Rope::from(", ") + Rope::from("world!");
+    r.assert_invariants();
+    assert_eq!(r, "Hello, world!");
+
+    let mut r = Rope::from("Hello");
+    r += Rope::from(" there");
+    r.assert_invariants();
+    assert_eq!(r, "Hello there");
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn append_many() {
+    let mut rng = rand::thread_rng();
+
+    let mut r = Rope::new();
+    let mut s = String::new();
+
+    let mut remaining = MEDIUM;
+
+    while !remaining.is_empty() {
+        let mut len = rng.gen_range(0..=remaining.len().min(5000));
+
+        while !remaining.is_char_boundary(len) {
+            len += 1;
+        }
+
+        let (chunk, rest) = remaining.split_at(len);
+        r += Rope::from(chunk);
+        s.push_str(chunk);
+        remaining = rest;
+    }
+
+    r.assert_invariants();
+    assert_eq!(r, s);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn split_off_random() {
+    let mut rng = rand::thread_rng();
+
+    let r = Rope::from(LARGE);
+
+    for _ in 0..20 {
+        let mut at = rng.gen_range(0..=LARGE.len());
+
+        while !LARGE.is_char_boundary(at) {
+            at += 1;
+        }
+
+        let mut left = r.clone();
+        let right = left.split_off(at);
+
+        left.assert_invariants();
+        right.assert_invariants();
+
+        assert_eq!(left, LARGE[..at]);
+        assert_eq!(right, LARGE[at..]);
+
+        left.append(right);
+        left.assert_invariants();
+        assert_eq!(left, LARGE);
+    }
+}
+
+#[test]
+fn split_off_ends() {
+    let mut r = Rope::from(SMALL);
+    let right = r.split_off(SMALL.len());
+    assert_eq!(r, SMALL);
+    assert!(right.is_empty());
+
+    let right = r.split_off(0);
+    assert!(r.is_empty());
+    assert_eq!(right, SMALL);
+}
+
+#[should_panic]
+#[test]
+fn split_off_out_of_bounds() {
+    let mut r = Rope::from("foo");
+    r.split_off(4);
+}
+
+#[should_panic]
+#[test]
+fn split_off_not_char_boundary() {
+    let mut r = Rope::from("こんにちは");
+    r.split_off(1);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn append_shares_nodes() {
+    let large = Rope::from(LARGE);
+
+    let mut r = Rope::from(TINY);
+    r.append(large.clone());
+    r.assert_invariants();
+
+    assert!(large.stats().shared_nodes > 0);
+
+    let right = r.split_off(TINY.len() + LARGE.len() / 2);
+    assert!(right.stats().shared_nodes > 0);
+}
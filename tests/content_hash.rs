@@ -0,0 +1,157 @@
+#![cfg(feature = "content-hash")]
+
+use crop::{Rope, RopeBuilder, RopeSpan};
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+/// Tests that the content hash only depends on the text and not on how it's
+/// split into chunks.
+#[test]
+fn content_hash_independent_of_chunking() {
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let expected = Rope::from(s).content_hash();
+
+        let mut builder = RopeBuilder::new();
+        for line in s.split_inclusive('\n') {
+            builder.append(line);
+        }
+        assert_eq!(builder.build().content_hash(), expected);
+    }
+
+    for s in [TINY, SMALL, CURSED_LIPSUM] {
+        let mut r = Rope::new();
+        for (idx, ch) in s.char_indices().rev() {
+            r.insert(0, &s[idx..idx + ch.len_utf8()]);
+        }
+        assert_eq!(r.content_hash(), Rope::from(s).content_hash());
+    }
+}
+
+#[test]
+fn content_hash_empty() {
+    let r = Rope::from("abc");
+    let empty = Rope::new().content_hash();
+    assert_eq!(r.byte_slice(1..1).content_hash(), empty);
+    assert_eq!(Rope::from(LARGE).byte_slice(100..100).content_hash(), empty);
+    assert_ne!(r.content_hash(), empty);
+}
+
+#[test]
+fn content_hash_differs() {
+    let r = Rope::from(MEDIUM);
+    let hash = r.content_hash();
+
+    let mut r2 = r.clone();
+    r2.replace(1000..1001, "#");
+    assert_ne!(r2.content_hash(), hash);
+
+    r2.replace(1000..1001, &MEDIUM[1000..1001]);
+    assert_eq!(r2.content_hash(), hash);
+
+    // The original wasn't affected by editing the clone.
+    assert_eq!(r.content_hash(), hash);
+
+    assert_ne!(
+        Rope::from("a").content_hash(),
+        Rope::from("a\0").content_hash()
+    );
+}
+
+/// Tests that the cached hashes are kept up to date by checking the hash of
+/// the rope against the one of a freshly built rope after every edit.
+#[test]
+fn content_hash_after_random_edits() {
+    let mut rng = rand::thread_rng();
+
+    for s in [SMALL, MEDIUM] {
+        let mut r = Rope::from(s);
+        let mut string = String::from(s);
+
+        // Populate the caches.
+        let _ = r.content_hash();
+
+        for _ in 0..50 {
+            let start = rng.gen_range(0..=r.byte_len());
+            let end = rng.gen_range(start..=(start + 100).min(r.byte_len()));
+
+            if !string.is_char_boundary(start) || !string.is_char_boundary(end)
+            {
+                continue;
+            }
+
+            let text =
+                ["", "a", "foo\nbar", "🐸\r\n", &s[..64]][rng.gen_range(0..5)];
+
+            r.replace(start..end, text);
+            string.replace_range(start..end, text);
+
+            assert_eq!(r.content_hash(), Rope::from(&*string).content_hash());
+        }
+
+        r.assert_invariants();
+    }
+}
+
+/// Tests that the content hash of random slices is equal to the one of a rope
+/// with the same text.
+#[test]
+fn content_hash_slices() {
+    let mut rng = rand::thread_rng();
+
+    for s in [CURSED_LIPSUM, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+
+        for _ in 0..100 {
+            let mut start = rng.gen_range(0..=r.byte_len());
+            let mut end = rng.gen_range(start..=r.byte_len());
+
+            while !s.is_char_boundary(start) {
+                start -= 1;
+            }
+
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let slice = r.byte_slice(start..end);
+            let expected = Rope::from(&s[start..end]).content_hash();
+
+            assert_eq!(slice.content_hash(), expected);
+            assert_eq!(RopeSpan::from(slice).content_hash(), expected);
+        }
+    }
+}
+
+#[test]
+fn content_hash_line_slices() {
+    let r = Rope::from(LARGE);
+
+    for (idx, line) in LARGE.lines().enumerate().step_by(7) {
+        assert_eq!(
+            r.line(idx).content_hash(),
+            Rope::from(line).content_hash()
+        );
+    }
+}
+
+#[test]
+fn eq_with_cached_hashes() {
+    let mut r1 = Rope::from(LARGE);
+    let mut r2 = r1.clone();
+
+    assert_eq!(r1, r2);
+
+    r2.insert(1000, "a");
+    r1.insert(1000, "a");
+
+    let _ = (r1.content_hash(), r2.content_hash());
+    assert_eq!(r1, r2);
+
+    r2.replace(2000..2001, "b");
+    let _ = r2.content_hash();
+    assert_ne!(r1, r2);
+    assert_ne!(r2, r1);
+}
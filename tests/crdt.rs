@@ -0,0 +1,247 @@
+#![cfg(feature = "crdt")]
+
+use crop::crdt::{CrdtError, ItemId, Operation, Replica};
+use rand::rngs::ThreadRng;
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, SMALL};
+
+const INSERTIONS: [&str; 5] = ["a", "foo", "🦀", "é\n", "\r\n"];
+
+/// Makes a random local edit to the replica, returning the operation it
+/// created.
+fn random_edit(rng: &mut ThreadRng, replica: &mut Replica) -> Operation {
+    let len = replica.rope().byte_len();
+
+    let offset = replica.rope().floor_char_boundary(rng.gen_range(0..=len));
+
+    if len > 0 && rng.gen_bool(0.3) {
+        let end =
+            replica.rope().ceil_char_boundary(rng.gen_range(offset..=len));
+
+        if let Some(op) = replica.delete(offset..end) {
+            return op;
+        }
+    }
+
+    replica.insert(offset, INSERTIONS[rng.gen_range(0..5)]).unwrap()
+}
+
+#[test]
+fn crdt_local_edits() {
+    let mut replica = Replica::new(0);
+
+    assert!(replica.insert(0, "").is_none());
+    assert!(replica.delete(..).is_none());
+
+    replica.insert(0, "Hello world");
+    replica.insert(5, ",");
+    replica.delete(6..12);
+    replica.insert(6, " 🦀");
+    replica.assert_invariants();
+
+    assert_eq!(replica.rope(), "Hello, 🦀");
+    assert_eq!(replica.item_len(), 14);
+}
+
+#[test]
+fn crdt_concurrent_inserts_at_same_position() {
+    let mut replicas = [Replica::new(1), Replica::new(2), Replica::new(3)];
+
+    let base = replicas[0].insert(0, "ab").unwrap();
+
+    for replica in &mut replicas[1..] {
+        replica.apply(&base).unwrap();
+    }
+
+    let ops = replicas
+        .iter_mut()
+        .zip(["1", "2", "3"])
+        .map(|(replica, text)| replica.insert(1, text).unwrap())
+        .collect::<Vec<_>>();
+
+    for (idx, replica) in replicas.iter_mut().enumerate() {
+        for op in ops.iter().rev().cycle().skip(idx).take(3) {
+            replica.apply(op).unwrap();
+        }
+        replica.assert_invariants();
+    }
+
+    // Concurrent insertions with the same clock are ordered by decreasing
+    // replica id.
+    for replica in &replicas {
+        assert_eq!(replica.rope(), "a321b");
+    }
+}
+
+#[test]
+fn crdt_apply_errors() {
+    let mut alice = Replica::new(1);
+    let mut bob = Replica::new(2);
+
+    let foo = alice.insert(0, "foo").unwrap();
+    let delete = alice.delete(1..2).unwrap();
+
+    let unknown = ItemId { clock: 2, replica: 1 };
+
+    assert_eq!(
+        bob.apply(&delete),
+        Err(CrdtError::UnknownItem { id: unknown })
+    );
+
+    bob.apply(&foo).unwrap();
+
+    let insert = Operation::Insert {
+        id: ItemId { clock: 10, replica: 3 },
+        origin: Some(ItemId { clock: 5, replica: 3 }),
+        text: "bar".into(),
+    };
+
+    assert!(bob.apply(&insert).is_err());
+    assert_eq!(bob.rope(), "foo");
+
+    bob.apply(&delete).unwrap();
+    bob.apply(&delete).unwrap();
+    bob.assert_invariants();
+
+    assert_eq!(bob.rope(), "fo");
+}
+
+#[test]
+fn crdt_positions() {
+    let mut replica = Replica::new(0);
+
+    replica.insert(0, "a🦀bc");
+    replica.delete(1..6);
+
+    assert_eq!(replica.rope(), "ac");
+
+    assert_eq!(replica.position_of_byte(0), 0);
+    assert_eq!(replica.position_of_byte(1), 1);
+    assert_eq!(replica.position_of_byte(2), 4);
+
+    assert_eq!(replica.byte_of_position(0), 0);
+    assert_eq!(replica.byte_of_position(2), 1);
+    assert_eq!(replica.byte_of_position(3), 1);
+    assert_eq!(replica.byte_of_position(4), 2);
+
+    // Text inserted after the "a" goes before the tombstones.
+    replica.insert(1, "d");
+    assert_eq!(replica.byte_of_position(2), 2);
+    assert_eq!(replica.rope(), "adc");
+}
+
+/// Tests that typing in the middle of the text, which keeps inserting
+/// characters right before the same one, doesn't break the lookup of remote
+/// operations once it runs out of room between two characters.
+#[test]
+fn crdt_typing_in_the_middle() {
+    let mut alice = Replica::new(1);
+    let mut bob = Replica::new(2);
+
+    bob.apply(&alice.insert(0, "()").unwrap()).unwrap();
+
+    let mut expected = String::from("()");
+
+    for (idx, ch) in "Hello world 🦀".chars().cycle().take(200).enumerate() {
+        let offset = expected.len() - 1;
+
+        let insert =
+            alice.insert(offset, ch.encode_utf8(&mut [0; 4])).unwrap();
+        expected.insert(offset, ch);
+
+        bob.apply(&insert).unwrap();
+
+        if idx % 20 == 0 {
+            let start = alice.rope().floor_char_boundary(offset / 2);
+            let end = alice.rope().ceil_char_boundary(start + 1);
+            bob.apply(&alice.delete(start..end).unwrap()).unwrap();
+            expected.replace_range(start..end, "");
+        }
+    }
+
+    alice.assert_invariants();
+    bob.assert_invariants();
+
+    assert_eq!(*alice.rope(), expected);
+    assert_eq!(bob.rope(), alice.rope());
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn crdt_local_edits_random() {
+    let mut rng = rand::thread_rng();
+
+    for s in [SMALL, CURSED_LIPSUM] {
+        let mut replica = Replica::new(0);
+        replica.insert(0, s);
+
+        let mut expected = s.to_owned();
+
+        for _ in 0..100 {
+            let len = expected.len();
+            let start =
+                replica.rope().floor_char_boundary(rng.gen_range(0..=len));
+            let end =
+                replica.rope().ceil_char_boundary(rng.gen_range(start..=len));
+
+            replica.delete(start..end);
+            expected.replace_range(start..end, "");
+
+            let text = INSERTIONS[rng.gen_range(0..5)];
+            replica.insert(start, text);
+            expected.insert_str(start, text);
+        }
+
+        replica.assert_invariants();
+        assert_eq!(*replica.rope(), expected);
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn crdt_convergence_random() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..10 {
+        let mut replicas = (0..3).map(Replica::new).collect::<Vec<_>>();
+
+        let mut inboxes = vec![Vec::<Operation>::new(); replicas.len()];
+
+        for _ in 0..200 {
+            let idx = rng.gen_range(0..replicas.len());
+
+            if rng.gen_bool(0.5) {
+                let op = random_edit(&mut rng, &mut replicas[idx]);
+
+                for (other, inbox) in inboxes.iter_mut().enumerate() {
+                    if other != idx {
+                        inbox.push(op.clone());
+                    }
+                }
+            } else if !inboxes[idx].is_empty() {
+                // Deliver a random pending operation, putting it back if it
+                // depends on one that hasn't been delivered yet.
+                let op_idx = rng.gen_range(0..inboxes[idx].len());
+                let op = inboxes[idx].swap_remove(op_idx);
+
+                if replicas[idx].apply(&op).is_err() {
+                    inboxes[idx].push(op);
+                }
+            }
+        }
+
+        for (replica, inbox) in replicas.iter_mut().zip(&mut inboxes) {
+            while !inbox.is_empty() {
+                inbox.retain(|op| replica.apply(op).is_err());
+            }
+            replica.assert_invariants();
+        }
+
+        for replica in &replicas[1..] {
+            assert_eq!(replica.rope(), replicas[0].rope());
+        }
+    }
+}
@@ -0,0 +1,209 @@
+#![cfg(feature = "encoding")]
+
+use std::io::Read;
+
+use crop::encoding::encoding_rs::{
+    Encoding,
+    EUC_JP,
+    ISO_2022_JP,
+    ISO_8859_2,
+    SHIFT_JIS,
+    UTF_16BE,
+    UTF_16LE,
+    UTF_8,
+    WINDOWS_1252,
+};
+use crop::encoding::{EncodingError, TextEncoding};
+use crop::Rope;
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+/// A reader yielding at most one byte per call, to check that multi-byte
+/// sequences and BOMs split across reads are decoded correctly.
+struct ByteByByte<'a>(&'a [u8]);
+
+impl Read for ByteByByte<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let Some((&first, rest)) = self.0.split_first() else { return Ok(0) };
+
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        buf[0] = first;
+        self.0 = rest;
+        Ok(1)
+    }
+}
+
+fn encode(encoding: &'static Encoding, s: &str) -> Vec<u8> {
+    if encoding == UTF_16LE {
+        s.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    } else if encoding == UTF_16BE {
+        s.encode_utf16().flat_map(u16::to_be_bytes).collect()
+    } else {
+        encoding.encode(s).0.into_owned()
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn encoding_round_trip() {
+    let encodings =
+        [UTF_8, UTF_16LE, UTF_16BE, WINDOWS_1252, SHIFT_JIS, EUC_JP];
+
+    for s in [TINY, SMALL, MEDIUM, LARGE, "日本語のテキスト\r\nです。"]
+    {
+        for encoding in encodings {
+            // Skip the encodings that can't represent the text.
+            if encoding.encode(s).2 {
+                continue;
+            }
+
+            let bytes = encode(encoding, s);
+
+            let (r, detected) =
+                Rope::from_reader_with_encoding(&bytes[..], encoding).unwrap();
+
+            assert_eq!(r, s);
+            assert_eq!(detected, TextEncoding::from(encoding));
+
+            let (r, _) =
+                Rope::from_reader_with_encoding(ByteByByte(&bytes), encoding)
+                    .unwrap();
+
+            assert_eq!(r, s);
+
+            let mut encoded = Vec::new();
+            r.write_encoded(&mut encoded, detected).unwrap();
+            assert_eq!(encoded, bytes);
+        }
+    }
+}
+
+#[test]
+fn encoding_utf16_surrogate_pairs() {
+    let s = "🦀 crabs 🦀";
+
+    for encoding in [UTF_16LE, UTF_16BE] {
+        let r = Rope::from(s);
+
+        let mut encoded = Vec::new();
+        r.write_encoded(&mut encoded, encoding).unwrap();
+        assert_eq!(encoded, encode(encoding, s));
+
+        let (r, _) =
+            Rope::from_reader_with_encoding(ByteByByte(&encoded), encoding)
+                .unwrap();
+
+        assert_eq!(r, s);
+    }
+}
+
+#[test]
+fn encoding_bom_detection_and_preservation() {
+    let boms: [(&'static Encoding, &[u8]); 3] = [
+        (UTF_8, b"\xEF\xBB\xBF"),
+        (UTF_16LE, b"\xFF\xFE"),
+        (UTF_16BE, b"\xFE\xFF"),
+    ];
+
+    for (encoding, bom) in boms {
+        for s in ["", "a", CURSED_LIPSUM] {
+            let mut bytes = bom.to_vec();
+            bytes.extend(encode(encoding, s));
+
+            // The BOM wins over the encoding we pass in.
+            let (r, detected) = Rope::from_reader_with_encoding(
+                ByteByByte(&bytes),
+                WINDOWS_1252,
+            )
+            .unwrap();
+
+            assert_eq!(r, s);
+            assert_eq!(detected, TextEncoding { encoding, has_bom: true });
+
+            let mut encoded = Vec::new();
+            r.write_encoded(&mut encoded, detected).unwrap();
+            assert_eq!(encoded, bytes);
+        }
+    }
+
+    // Encodings without a BOM never get one.
+    let encoding = TextEncoding { encoding: WINDOWS_1252, has_bom: true };
+    let mut encoded = Vec::new();
+    Rope::from("abc").write_encoded(&mut encoded, encoding).unwrap();
+    assert_eq!(encoded, b"abc");
+}
+
+#[test]
+fn encoding_malformed_input() {
+    let bytes = b"abc\xFFdef";
+
+    let err = Rope::from_reader_with_encoding(&bytes[..], UTF_8).unwrap_err();
+
+    assert!(matches!(
+        err,
+        EncodingError::Malformed { encoding, byte_offset: 3 } if encoding == UTF_8
+    ));
+
+    // A truncated Shift-JIS sequence at the end of the input.
+    let mut bytes = SHIFT_JIS.encode("日本").0.into_owned();
+    bytes.pop();
+
+    let err = Rope::from_reader_with_encoding(ByteByByte(&bytes), SHIFT_JIS)
+        .unwrap_err();
+
+    assert!(matches!(err, EncodingError::Malformed { byte_offset: 2, .. }));
+}
+
+#[test]
+fn encoding_unmappable_chars() {
+    let s = "Zażółć gęślą jaźń";
+
+    let r = Rope::from(s);
+
+    // All of these are in ISO-8859-2..
+    let mut encoded = Vec::new();
+    r.write_encoded(&mut encoded, ISO_8859_2).unwrap();
+    assert_eq!(encoded, encode(ISO_8859_2, s));
+
+    // ..but not in Windows-1252, which has "ó" but not "ż".
+    let err = r.write_encoded(Vec::new(), WINDOWS_1252).unwrap_err();
+
+    assert!(matches!(
+        err,
+        EncodingError::Unmappable { byte_offset: 2, ch: 'ż', .. }
+    ));
+
+    assert_eq!(
+        err.to_string(),
+        "character 'ż' at byte offset 2 can't be encoded in windows-1252"
+    );
+
+    let s = "ab".repeat(100) + "🦀";
+    let err = Rope::from(&*s).write_encoded(Vec::new(), EUC_JP).unwrap_err();
+
+    assert!(matches!(
+        err,
+        EncodingError::Unmappable { byte_offset: 200, ch: '🦀', .. }
+    ));
+}
+
+#[test]
+fn encoding_stateful_encoder_is_flushed() {
+    let s = "abc日本語";
+
+    let mut encoded = Vec::new();
+    Rope::from(s).write_encoded(&mut encoded, ISO_2022_JP).unwrap();
+
+    // The encoder has to switch back to ASCII at the end of the input.
+    assert_eq!(encoded, encode(ISO_2022_JP, s));
+
+    let (r, _) =
+        Rope::from_reader_with_encoding(&encoded[..], ISO_2022_JP).unwrap();
+
+    assert_eq!(r, s);
+}
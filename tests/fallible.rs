@@ -0,0 +1,322 @@
+use crop::{Rope, RopeError};
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, MEDIUM};
+
+#[test]
+fn try_byte() {
+    let r = Rope::from("abc");
+    assert_eq!(r.try_byte(2), Ok(b'c'));
+    assert_eq!(
+        r.try_byte(3),
+        Err(RopeError::ByteIndexOutOfBounds { byte_index: 3, byte_len: 3 })
+    );
+    assert_eq!(r.byte_slice(1..).try_byte(1), Ok(b'c'));
+    assert!(r.byte_slice(1..).try_byte(2).is_err());
+}
+
+#[test]
+fn try_byte_slice_errors() {
+    let r = Rope::from("a🌏b");
+
+    #[allow(clippy::reversed_empty_ranges)]
+    let res = r.try_byte_slice(3..2);
+    assert_eq!(
+        res,
+        Err(RopeError::ByteStartAfterEnd { byte_start: 3, byte_end: 2 })
+    );
+
+    assert_eq!(
+        r.try_byte_slice(..7),
+        Err(RopeError::ByteOffsetOutOfBounds { byte_offset: 7, byte_len: 6 })
+    );
+
+    for offset in 2..5 {
+        assert_eq!(
+            r.try_byte_slice(offset..),
+            Err(RopeError::ByteOffsetNotCharBoundary {
+                byte_offset: offset,
+                splitting_char: '🌏',
+                char_range: 1..5,
+            })
+        );
+    }
+
+    assert_eq!(r.try_byte_slice(1..5).unwrap(), "🌏");
+    assert_eq!(r.try_byte_slice(..).unwrap(), "a🌏b");
+}
+
+/// Tests that `try_byte_slice` agrees with `byte_slice` on random ranges,
+/// both on `Rope`s and on `RopeSlice`s.
+#[test]
+fn try_inclusive_usize_max() {
+    let r = Rope::from("foo\nbar");
+
+    assert_eq!(
+        r.try_byte_slice(..=usize::MAX),
+        Err(RopeError::ByteOffsetOutOfBounds {
+            byte_offset: usize::MAX,
+            byte_len: 7,
+        })
+    );
+
+    assert_eq!(
+        r.try_line_slice(..=usize::MAX),
+        Err(RopeError::LineOffsetOutOfBounds {
+            line_offset: usize::MAX,
+            line_len: 2,
+        })
+    );
+
+    assert!(r.byte_slice(..).try_byte_slice(..=usize::MAX).is_err());
+    assert!(r.clone().try_delete(..=usize::MAX).is_err());
+}
+
+#[test]
+fn try_byte_slice_random() {
+    let mut rng = rand::thread_rng();
+
+    for s in [CURSED_LIPSUM, MEDIUM] {
+        let r = Rope::from(s);
+        let slice = r.byte_slice(..);
+
+        for _ in 0..100 {
+            let start = rng.gen_range(0..=s.len());
+            let end = rng.gen_range(start..=s.len());
+
+            let is_valid =
+                s.is_char_boundary(start) && s.is_char_boundary(end);
+
+            match r.try_byte_slice(start..end) {
+                Ok(rope_slice) => {
+                    assert!(is_valid);
+                    assert_eq!(rope_slice, s[start..end]);
+                    rope_slice.assert_invariants();
+                },
+
+                Err(RopeError::ByteOffsetNotCharBoundary {
+                    byte_offset,
+                    splitting_char,
+                    char_range,
+                }) => {
+                    assert!(!is_valid);
+                    assert!(byte_offset == start || byte_offset == end);
+                    assert_eq!(
+                        s[char_range].chars().next(),
+                        Some(splitting_char)
+                    );
+                },
+
+                Err(err) => panic!("unexpected error: {err}"),
+            }
+
+            assert_eq!(slice.try_byte_slice(start..end).is_ok(), is_valid);
+        }
+    }
+}
+
+#[test]
+fn try_insert_and_delete() {
+    let mut r = Rope::from("a🌏b");
+
+    assert_eq!(
+        r.try_insert(7, "c"),
+        Err(RopeError::ByteOffsetOutOfBounds { byte_offset: 7, byte_len: 6 })
+    );
+
+    assert!(matches!(
+        r.try_insert(2, "c"),
+        Err(RopeError::ByteOffsetNotCharBoundary { byte_offset: 2, .. })
+    ));
+
+    assert!(matches!(
+        r.try_delete(0..3),
+        Err(RopeError::ByteOffsetNotCharBoundary { byte_offset: 3, .. })
+    ));
+
+    // The rope is left untouched if the operation fails.
+    assert_eq!(r, "a🌏b");
+
+    r.try_insert(5, "c").unwrap();
+    assert_eq!(r, "a🌏cb");
+
+    r.try_delete(1..5).unwrap();
+    assert_eq!(r, "acb");
+
+    r.try_replace(1..2, "🌏").unwrap();
+    assert_eq!(r, "a🌏b");
+
+    r.assert_invariants();
+}
+
+#[test]
+fn try_insert_rope() {
+    let mut r = Rope::from("a🌏b");
+    let other = Rope::from("xyz");
+
+    assert!(r.try_insert_rope(3, other.byte_slice(..)).is_err());
+    assert!(r.try_replace_with_rope(2..9, other.byte_slice(..)).is_err());
+
+    r.try_insert_rope(5, other.byte_slice(..)).unwrap();
+    assert_eq!(r, "a🌏xyzb");
+
+    r.try_replace_with_rope(1..5, other.byte_slice(1..)).unwrap();
+    assert_eq!(r, "ayzxyzb");
+
+    r.assert_invariants();
+}
+
+#[test]
+fn try_line() {
+    let r = Rope::from("foo\nbar\r\nbaz\n");
+
+    assert_eq!(r.try_line(2).unwrap(), "baz");
+    assert_eq!(
+        r.try_line(3),
+        Err(RopeError::LineIndexOutOfBounds { line_index: 3, line_len: 3 })
+    );
+
+    let s = r.byte_slice(4..);
+    assert_eq!(s.try_line(1).unwrap(), "baz");
+    assert_eq!(
+        s.try_line(2),
+        Err(RopeError::LineIndexOutOfBounds { line_index: 2, line_len: 2 })
+    );
+}
+
+#[test]
+fn try_line_slice() {
+    let r = Rope::from("foo\nbar\r\nbaz\n");
+
+    assert_eq!(r.try_line_slice(1..3).unwrap(), "bar\r\nbaz\n");
+    #[allow(clippy::reversed_empty_ranges)]
+    let res = r.try_line_slice(2..1);
+    assert_eq!(
+        res,
+        Err(RopeError::LineStartAfterEnd { line_start: 2, line_end: 1 })
+    );
+    assert_eq!(
+        r.try_line_slice(..4),
+        Err(RopeError::LineOffsetOutOfBounds { line_offset: 4, line_len: 3 })
+    );
+
+    let s = r.byte_slice(4..);
+    assert_eq!(s.try_line_slice(1..).unwrap(), "baz\n");
+    assert!(s.try_line_slice(..3).is_err());
+}
+
+#[test]
+fn try_byte_of_line_and_line_of_byte() {
+    let r = Rope::from("a\n🌏\nb");
+
+    assert_eq!(r.try_byte_of_line(2), Ok(7));
+    assert_eq!(r.try_byte_of_line(3), Ok(8));
+    assert_eq!(
+        r.try_byte_of_line(4),
+        Err(RopeError::LineOffsetOutOfBounds { line_offset: 4, line_len: 3 })
+    );
+
+    assert_eq!(r.try_line_of_byte(6), Ok(1));
+    assert_eq!(r.try_line_of_byte(8), Ok(2));
+    assert!(matches!(
+        r.try_line_of_byte(3),
+        Err(RopeError::ByteOffsetNotCharBoundary { .. })
+    ));
+    assert!(matches!(
+        r.try_line_of_byte(9),
+        Err(RopeError::ByteOffsetOutOfBounds { .. })
+    ));
+}
+
+#[test]
+fn try_is_char_boundary() {
+    let r = Rope::from("a🌏");
+    assert_eq!(r.try_is_char_boundary(2), Ok(false));
+    assert_eq!(r.try_is_char_boundary(5), Ok(true));
+    assert!(r.try_is_char_boundary(6).is_err());
+}
+
+#[test]
+fn try_split_off() {
+    let mut r = Rope::from("a🌏b");
+    assert!(r.try_split_off(2).is_err());
+    assert!(r.try_split_off(7).is_err());
+    assert_eq!(r, "a🌏b");
+
+    let right = r.try_split_off(5).unwrap();
+    assert_eq!(r, "a🌏");
+    assert_eq!(right, "b");
+}
+
+#[test]
+fn error_display() {
+    let r = Rope::from("a🌏");
+
+    assert_eq!(
+        r.try_byte(5).unwrap_err().to_string(),
+        "byte index out of bounds: the index is 5 but the length is 5"
+    );
+
+    assert_eq!(
+        r.try_byte_slice(2..).unwrap_err().to_string(),
+        "byte offset 2 is not a char boundary: it is inside '🌏' (bytes 1..5)"
+    );
+
+    #[allow(clippy::reversed_empty_ranges)]
+    let res = r.try_line_slice(1..0);
+    assert_eq!(
+        res.unwrap_err().to_string(),
+        "line start after end: the start is 1 but the end is 0"
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn error_is_std_error() {
+    let r = Rope::from("a🌏");
+
+    let err: Box<dyn std::error::Error> = Box::new(r.try_line(1).unwrap_err());
+    assert_eq!(
+        err.to_string(),
+        "line index out of bounds: the index is 1 but the length is 1"
+    );
+}
+
+#[cfg(feature = "utf16-metric")]
+#[test]
+fn try_utf16() {
+    let r = Rope::from("a🌏b");
+
+    assert_eq!(r.try_byte_of_utf16_code_unit(3), Ok(5));
+    assert_eq!(
+        r.try_byte_of_utf16_code_unit(5),
+        Err(RopeError::Utf16OffsetOutOfBounds {
+            utf16_offset: 5,
+            utf16_len: 4
+        })
+    );
+
+    assert_eq!(r.try_utf16_code_unit_of_byte(5), Ok(3));
+    assert!(r.try_utf16_code_unit_of_byte(2).is_err());
+
+    assert_eq!(r.try_utf16_slice(1..3).unwrap(), "🌏");
+    #[allow(clippy::reversed_empty_ranges)]
+    let res = r.try_utf16_slice(3..1);
+    assert_eq!(
+        res,
+        Err(RopeError::Utf16StartAfterEnd { utf16_start: 3, utf16_end: 1 })
+    );
+    assert!(r.byte_slice(1..).try_utf16_slice(..4).is_err());
+}
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn try_is_grapheme_boundary() {
+    let r = Rope::from("e\u{301}x");
+    assert_eq!(r.try_is_grapheme_boundary(1), Ok(false));
+    assert_eq!(r.try_is_grapheme_boundary(3), Ok(true));
+    assert!(r.try_is_grapheme_boundary(5).is_err());
+    assert!(r.byte_slice(1..).try_is_grapheme_boundary(5).is_err());
+}
@@ -138,3 +138,100 @@ fn graphemes_is_boundary_out_of_bounds() {
     let r = Rope::from("🇷🇸🇮🇴");
     assert!(r.is_grapheme_boundary(17));
 }
+
+/// Returns the grapheme boundaries of the string, including its start and end.
+#[cfg(feature = "graphemes")]
+fn grapheme_boundaries(s: &str) -> Vec<usize> {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    s.grapheme_indices(true)
+        .map(|(idx, _)| idx)
+        .chain(std::iter::once(s.len()))
+        .collect()
+}
+
+#[cfg(feature = "graphemes")]
+const BOUNDARIES_TEXT: &str = concat!(
+    "a\r\nb🇷🇸🇮🇴c🐻‍❄️\r\n",
+    "e\u{301}\u{302}\u{303}\u{304}\u{305}\u{306}\u{307}\u{308}",
+    "\u{309}\u{30a}\u{30b}\u{30c}\u{30d}\u{30e}\u{30f}!\n",
+    "👨‍👩‍👧‍👦ഷ്ഠ",
+);
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn graphemes_floor_ceil_boundary() {
+    let s = BOUNDARIES_TEXT;
+    let boundaries = grapheme_boundaries(s);
+
+    let r = Rope::from(s);
+
+    for start in [0, 1, 3] {
+        let slice = r.byte_slice(start..);
+        let slice_boundaries = grapheme_boundaries(&s[start..]);
+
+        for idx in 0..=slice.byte_len() + 1 {
+            let floor =
+                *slice_boundaries.iter().rev().find(|&&b| b <= idx).unwrap();
+            let ceil = *slice_boundaries
+                .iter()
+                .find(|&&b| b >= idx)
+                .unwrap_or(&slice.byte_len());
+
+            assert_eq!(floor, slice.floor_grapheme_boundary(idx), "{idx}");
+            assert_eq!(ceil, slice.ceil_grapheme_boundary(idx), "{idx}");
+        }
+    }
+
+    for idx in 0..=s.len() {
+        let floor = *boundaries.iter().rev().find(|&&b| b <= idx).unwrap();
+        let ceil = *boundaries.iter().find(|&&b| b >= idx).unwrap();
+
+        assert_eq!(floor, r.floor_grapheme_boundary(idx), "{idx}");
+        assert_eq!(ceil, r.ceil_grapheme_boundary(idx), "{idx}");
+        assert_eq!(floor == idx, r.is_grapheme_boundary(idx), "{idx}");
+    }
+}
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn graphemes_prev_next_boundary() {
+    let s = BOUNDARIES_TEXT;
+    let boundaries = grapheme_boundaries(s);
+
+    let r = Rope::from(s);
+    let slice = r.byte_slice(..);
+
+    for idx in 0..=s.len() {
+        let prev = *boundaries.iter().rev().find(|&&b| b < idx).unwrap_or(&0);
+        let next = *boundaries.iter().find(|&&b| b > idx).unwrap_or(&s.len());
+
+        assert_eq!(prev, r.prev_grapheme_boundary(idx), "{idx}");
+        assert_eq!(next, r.next_grapheme_boundary(idx), "{idx}");
+        assert_eq!(prev, slice.prev_grapheme_boundary(idx), "{idx}");
+        assert_eq!(next, slice.next_grapheme_boundary(idx), "{idx}");
+    }
+
+    // Walking the whole rope one grapheme at a time should yield the same
+    // boundaries both forwards and backwards.
+    let mut forward = vec![0];
+    while *forward.last().unwrap() < r.byte_len() {
+        forward.push(r.next_grapheme_boundary(*forward.last().unwrap()));
+    }
+    assert_eq!(forward, boundaries);
+
+    let mut backward = vec![r.byte_len()];
+    while *backward.last().unwrap() > 0 {
+        backward.push(r.prev_grapheme_boundary(*backward.last().unwrap()));
+    }
+    backward.reverse();
+    assert_eq!(backward, boundaries);
+}
+
+#[cfg(feature = "graphemes")]
+#[should_panic]
+#[test]
+fn graphemes_next_boundary_out_of_bounds() {
+    let r = Rope::from("🇷🇸🇮🇴");
+    r.next_grapheme_boundary(17);
+}
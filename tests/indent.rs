@@ -0,0 +1,190 @@
+use crop::{Indent, Rope};
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, MEDIUM, SMALL};
+
+/// Splits the string into lines including their terminators.
+fn raw_lines(s: &str) -> Vec<&str> {
+    s.split_inclusive('\n').collect()
+}
+
+fn indent_str(s: &str, start: usize, end: usize, indent: &str) -> String {
+    raw_lines(s)
+        .into_iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            let is_blank = line == "\n" || line == "\r\n";
+            if (start..end).contains(&idx) && !is_blank {
+                format!("{indent}{line}")
+            } else {
+                line.to_owned()
+            }
+        })
+        .collect()
+}
+
+fn dedent_str(
+    s: &str,
+    start: usize,
+    end: usize,
+    width: usize,
+    tab_width: usize,
+) -> String {
+    raw_lines(s)
+        .into_iter()
+        .enumerate()
+        .map(|(idx, mut line)| {
+            if (start..end).contains(&idx) {
+                let mut column = 0;
+                loop {
+                    let next = match line.as_bytes().first() {
+                        Some(b' ') => column + 1,
+                        Some(b'\t') => (column / tab_width + 1) * tab_width,
+                        _ => break,
+                    };
+                    if next > width {
+                        break;
+                    }
+                    column = next;
+                    line = &line[1..];
+                }
+            }
+            line
+        })
+        .collect()
+}
+
+#[test]
+fn indentation_of_line() {
+    let r = Rope::from("foo\n  bar\r\n\t\tbaz\n   \n\n  ");
+
+    assert_eq!(r.indentation_of_line(0), "");
+    assert_eq!(r.indentation_of_line(1), "  ");
+    assert_eq!(r.indentation_of_line(2), "\t\t");
+    assert_eq!(r.indentation_of_line(3), "   ");
+    assert_eq!(r.indentation_of_line(4), "");
+    assert_eq!(r.indentation_of_line(5), "  ");
+}
+
+#[should_panic]
+#[test]
+fn indentation_of_line_out_of_bounds() {
+    let r = Rope::from("foo\nbar\n");
+    r.indentation_of_line(2);
+}
+
+#[test]
+fn indent_lines_skips_blank_lines() {
+    let mut r = Rope::from("a\n\nb\r\n\r\n  \nc");
+    r.indent_lines(.., "\t");
+    r.assert_invariants();
+    assert_eq!(r, "\ta\n\n\tb\r\n\r\n\t  \n\tc");
+}
+
+#[test]
+fn indent_lines_empty_range() {
+    let mut r = Rope::from("a\nb\n");
+    r.indent_lines(1..1, "    ");
+    r.indent_lines(2..2, "    ");
+    assert_eq!(r, "a\nb\n");
+}
+
+#[should_panic]
+#[test]
+fn indent_lines_out_of_bounds() {
+    let mut r = Rope::from("a\nb\n");
+    r.indent_lines(0..3, "    ");
+}
+
+#[test]
+fn dedent_lines_tabs() {
+    let mut r = Rope::from("\tfoo\n  \tbar\n     baz\n\t\tqux\n");
+    r.dedent_lines(.., 4, 4);
+    r.assert_invariants();
+    assert_eq!(r, "foo\nbar\n baz\n\tqux\n");
+
+    // A tab that would only be partially removed is kept.
+    let mut r = Rope::from("  \tfoo\n");
+    r.dedent_lines(.., 2, 8);
+    assert_eq!(r, "\tfoo\n");
+}
+
+#[should_panic]
+#[test]
+fn dedent_lines_zero_tab_width() {
+    let mut r = Rope::from("\tfoo\n");
+    r.dedent_lines(.., 4, 0);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn indent_dedent_random() {
+    let mut rng = rand::thread_rng();
+
+    for s in [SMALL, MEDIUM, CURSED_LIPSUM] {
+        let mut r = Rope::from(s);
+        let mut s = s.to_owned();
+
+        for _ in 0..20 {
+            let start = rng.gen_range(0..=r.line_len());
+            let end = rng.gen_range(start..=r.line_len());
+
+            if rng.gen() {
+                let indent = ["  ", "    ", "\t", " \t "][rng.gen_range(0..4)];
+                r.indent_lines(start..end, indent);
+                s = indent_str(&s, start, end, indent);
+            } else {
+                let width = rng.gen_range(0..10);
+                let tab_width = rng.gen_range(1..9);
+                r.dedent_lines(start..end, width, tab_width);
+                s = dedent_str(&s, start, end, width, tab_width);
+            }
+
+            r.assert_invariants();
+            assert_eq!(r, s);
+        }
+    }
+}
+
+#[test]
+fn indent_then_dedent() {
+    let s = "fn main() {\nlet x = 1;\n\nprintln!(\"{x}\");\n}\n";
+
+    let mut r = Rope::from(s.repeat(500));
+    let line_len = r.line_len();
+
+    r.indent_lines(..line_len, "    ");
+    assert_eq!(r.indentation_of_line(line_len - 1), "    ");
+    assert_eq!(r.indentation_of_line(line_len - 3), "");
+
+    r.dedent_lines(..line_len, 4, 4);
+    r.assert_invariants();
+    assert_eq!(r, s.repeat(500));
+}
+
+#[test]
+fn detect_indent() {
+    let r = Rope::from("a {\n  b {\n    c\n  }\n}\n");
+    assert_eq!(r.detect_indent(), Some(Indent::Spaces(2)));
+
+    let r = Rope::from("a {\n    b {\n        c\n\n        d\n    }\n}\n");
+    assert_eq!(r.detect_indent(), Some(Indent::Spaces(4)));
+
+    let r = Rope::from("a {\n\tb {\n\t\tc\n\t}\n  // d\n}\n");
+    assert_eq!(r.detect_indent(), Some(Indent::Tabs));
+
+    let r = Rope::from("a\nb\n  \n\nc");
+    assert_eq!(r.detect_indent(), None);
+
+    assert_eq!(Rope::new().detect_indent(), None);
+}
+
+#[test]
+fn detect_indent_after_indent_lines() {
+    let mut r = Rope::from("a\nb\nc\nd\n");
+    r.indent_lines(1..2, "   ");
+    r.indent_lines(2..3, "      ");
+    assert_eq!(r.detect_indent(), Some(Indent::Spaces(3)));
+}
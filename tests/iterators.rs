@@ -86,6 +86,7 @@ fn iter_bytes_both_ways() {
     assert_eq!(None, rope_bytes.next_back());
 }
 
+#[allow(clippy::needless_as_bytes)]
 #[test]
 fn iter_bytes_cursed() {
     let s = CURSED_LIPSUM;
@@ -426,6 +427,53 @@ fn iter_lines_forward_backward() {
     assert_eq!(None, backward.next());
 }
 
+#[test]
+fn iter_lines_both_ways() {
+    let r = Rope::from("\na\nb\r\nc\n");
+
+    let mut lines = r.lines();
+    assert_eq!("", lines.next().unwrap());
+    assert_eq!("c", lines.next_back().unwrap());
+    assert_eq!("a", lines.next().unwrap());
+    assert_eq!("b", lines.next_back().unwrap());
+    assert_eq!(0, lines.len());
+    assert_eq!(None, lines.next());
+    assert_eq!(None, lines.next_back());
+
+    let mut raw_lines = r.raw_lines();
+    assert_eq!("\n", raw_lines.next().unwrap());
+    assert_eq!("c\n", raw_lines.next_back().unwrap());
+    assert_eq!("a\n", raw_lines.next().unwrap());
+    assert_eq!("b\r\n", raw_lines.next_back().unwrap());
+    assert_eq!(0, raw_lines.len());
+    assert_eq!(None, raw_lines.next_back());
+    assert_eq!(None, raw_lines.next());
+}
+
+/// Tests that the lines of a `RopeSlice` contained in a single chunk can be
+/// sliced again, when iterating in both directions.
+#[test]
+fn iter_lines_reslice() {
+    let r = Rope::from("aaa\nbbb\nccc\nddd\n");
+    let s = r.byte_slice(2..14);
+
+    let lines = ["a", "bbb", "ccc", "dd"];
+
+    for (line, expected) in s.lines().zip(lines) {
+        assert_eq!(expected, line.byte_slice(..));
+        assert_eq!(&expected[1..], line.byte_slice(1..));
+    }
+
+    for (line, expected) in s.lines().rev().zip(lines.into_iter().rev()) {
+        assert_eq!(expected, line.byte_slice(..));
+        assert_eq!(&expected[1..], line.byte_slice(1..));
+    }
+
+    for (line, expected) in s.raw_lines().rev().zip(["dd", "ccc\n", "bbb\n"]) {
+        assert_eq!(expected, line.byte_slice(..));
+    }
+}
+
 #[cfg_attr(miri, ignore)]
 #[test]
 fn iter_lines_over_random_slices() {
@@ -475,6 +523,59 @@ fn iter_lines_over_random_slices() {
     }
 }
 
+#[test]
+fn iter_lines_in_empty() {
+    let r = Rope::new();
+    assert_eq!(0, r.lines_in(..).count());
+
+    let r = Rope::from("foo\nbar\n");
+    assert_eq!(0, r.lines_in(1..1).count());
+    assert_eq!(0, r.lines_in(2..).rev().count());
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn iter_lines_in_random_ranges() {
+    let mut rng = rand::thread_rng();
+
+    for s in [TINY, SMALL, MEDIUM, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let start = rng.gen_range(0..=r.line_len());
+            let end = rng.gen_range(start..=r.line_len());
+
+            let expected = (start..end)
+                .map(|idx| (idx, r.byte_of_line(idx), r.line(idx)))
+                .collect::<Vec<_>>();
+
+            let lines = r.lines_in(start..end);
+
+            assert_eq!(lines.len(), end - start);
+            assert_eq!(lines.clone().collect::<Vec<_>>(), expected);
+
+            assert!(lines.clone().rev().eq(expected.iter().cloned().rev()));
+
+            // Alternate between the two ends.
+            let mut lines = lines;
+            let (mut front, mut back) = (0, expected.len());
+
+            while front < back {
+                if rng.gen_bool(0.5) {
+                    assert_eq!(lines.next().unwrap(), expected[front]);
+                    front += 1;
+                } else {
+                    back -= 1;
+                    assert_eq!(lines.next_back().unwrap(), expected[back]);
+                }
+            }
+
+            assert_eq!(lines.next(), None);
+            assert_eq!(lines.next_back(), None);
+        }
+    }
+}
+
 #[test]
 fn iter_raw_lines_0() {
     let r = Rope::from(
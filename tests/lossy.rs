@@ -0,0 +1,156 @@
+use crop::{Rope, RopeBuilder};
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, SMALL};
+
+/// Returns the bytes of the string with some of them replaced by bytes that
+/// make the string invalid UTF-8.
+fn corrupt(s: &str, rng: &mut impl Rng) -> Vec<u8> {
+    let mut bytes = s.as_bytes().to_vec();
+
+    let garbage: [&[u8]; 6] =
+        [b"\xFF", b"\x80", b"\xC3", b"\xE2\x82", b"\xF0\x9F\xA6", b"\xED\xA0"];
+
+    for _ in 0..bytes.len() / 50 + 1 {
+        let offset = rng.gen_range(0..=bytes.len());
+        let g = garbage[rng.gen_range(0..garbage.len())];
+        bytes.splice(offset..offset, g.iter().copied());
+    }
+
+    bytes
+}
+
+#[test]
+fn lossy_valid_utf8() {
+    for s in ["", "a", SMALL, CURSED_LIPSUM] {
+        let (r, invalid) = Rope::from_utf8_lossy(s.as_bytes());
+        assert_eq!(r, s);
+        assert!(invalid.is_empty());
+        r.assert_invariants();
+    }
+}
+
+#[test]
+fn lossy_matches_std() {
+    let inputs: [&[u8]; 8] = [
+        b"\xFF",
+        b"foo\xC3",
+        b"\xE2\x82\xACbar\xE2\x82",
+        b"\xF0\x9F\xA6\x80\xF0\x9F\xA6",
+        b"\xED\xA0\x80",
+        b"\xC0\xAF",
+        b"a\xF4\x90\x80\x80b",
+        b"\r\xFF\n",
+    ];
+
+    for bytes in inputs {
+        let (r, invalid) = Rope::from_utf8_lossy(bytes);
+
+        assert_eq!(r, String::from_utf8_lossy(bytes));
+        assert_eq!(invalid.original_bytes(&r), bytes);
+        r.assert_invariants();
+    }
+}
+
+#[test]
+fn lossy_invalid_sequences() {
+    let (r, invalid) = Rope::from_utf8_lossy(b"a\xFF\xFEb\xE2\x82");
+
+    assert_eq!(r, "a\u{FFFD}\u{FFFD}b\u{FFFD}");
+
+    let sequences = invalid
+        .iter()
+        .map(|seq| (seq.byte_offset(), seq.original_range(), seq.bytes()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        sequences,
+        [
+            (1, 1..2, &b"\xFF"[..]),
+            (4, 2..3, &b"\xFE"[..]),
+            (8, 4..6, &b"\xE2\x82"[..]),
+        ]
+    );
+}
+
+#[test]
+fn lossy_mixed_with_str() {
+    let mut builder = RopeBuilder::new();
+
+    // The incomplete sequence can't be completed by a `&str`.
+    builder
+        .append_bytes_lossy(b"foo\xE2")
+        .append("bar")
+        .append_bytes_lossy(b"\x82\xAC");
+
+    let (r, invalid) = builder.build_lossy();
+
+    assert_eq!(r, "foo\u{FFFD}bar\u{FFFD}\u{FFFD}");
+    assert_eq!(invalid.len(), 3);
+    assert_eq!(invalid.original_bytes(&r), b"foo\xE2bar\x82\xAC");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn lossy_original_bytes_after_edits() {
+    let bytes = b"foo\xFFbar\xFEbaz";
+
+    let (mut r, invalid) = Rope::from_utf8_lossy(bytes);
+
+    // Deleting text after the last replacement character keeps every
+    // sequence in place.
+    r.delete(12..);
+    assert_eq!(invalid.original_bytes(&r), b"foo\xFFbar\xFE");
+
+    // Inserting text before the last one moves it, so it's not restored.
+    r.insert(0, "!");
+    assert_eq!(
+        invalid.original_bytes(&r),
+        "!foo\u{FFFD}bar\u{FFFD}".as_bytes()
+    );
+
+    let mut written = Vec::new();
+    invalid.write_original(&r, &mut written).unwrap();
+    assert_eq!(written, invalid.original_bytes(&r));
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn lossy_random_splits() {
+    let mut rng = rand::thread_rng();
+
+    for s in [SMALL, LARGE, CURSED_LIPSUM] {
+        let bytes = corrupt(s, &mut rng);
+
+        let expected = String::from_utf8_lossy(&bytes);
+
+        for _ in 0..3 {
+            let mut builder = RopeBuilder::new();
+
+            let mut rest = &bytes[..];
+
+            while !rest.is_empty() {
+                let len = rng.gen_range(0..=rest.len().min(8));
+                let (bytes, r) = rest.split_at(len);
+                builder.append_bytes_lossy(bytes);
+                rest = r;
+            }
+
+            let (r, invalid) = builder.build_lossy();
+
+            r.assert_invariants();
+            assert_eq!(r, expected);
+            assert_eq!(invalid.original_bytes(&r), bytes);
+
+            for seq in &invalid {
+                assert_eq!(&bytes[seq.original_range()], seq.bytes());
+                assert_eq!(
+                    r.byte_slice(seq.byte_offset()..seq.byte_offset() + 3),
+                    "\u{FFFD}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+#![cfg(feature = "graphemes")]
+
+use std::borrow::Cow;
+
+use crop::{transforms, Rope};
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, MEDIUM};
+
+/// A text where most grapheme clusters are made of several chars, so that a
+/// lot of them end up being split between two chunks.
+fn clusters_text() -> String {
+    "e\u{301}\u{302}a\u{323}🐻‍❄️\r\n🇮🇹👨‍👩‍👧x".repeat(2000)
+}
+
+#[test]
+fn map_range_pieces_are_grapheme_aligned() {
+    let text = clusters_text();
+    let mut r = Rope::from(&*text);
+
+    let mut pieces = Vec::new();
+
+    let range = r.map_range(3.., |piece| {
+        pieces.push(piece.to_owned());
+        Cow::Borrowed(piece)
+    });
+
+    assert_eq!(range, 3..text.len());
+    assert_eq!(r, text);
+    assert!(pieces.len() > 1);
+    assert_eq!(pieces.concat(), text[3..]);
+
+    let slice = r.byte_slice(3..);
+    let mut offset = 0;
+
+    for piece in &pieces {
+        assert!(!piece.is_empty());
+        assert!(slice.is_grapheme_boundary(offset));
+        offset += piece.len();
+    }
+}
+
+#[test]
+fn map_range_random() {
+    let mut rng = rand::thread_rng();
+
+    for s in [CURSED_LIPSUM, MEDIUM, &clusters_text()] {
+        for _ in 0..20 {
+            let mut r = Rope::from(s);
+
+            let mut start = rng.gen_range(0..=s.len());
+            let mut end = rng.gen_range(start..=s.len());
+
+            while !s.is_char_boundary(start) {
+                start -= 1;
+            }
+
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let new_range =
+                r.map_range(start..end, |s| Cow::Owned(s.replace('e', "ee")));
+
+            let mapped = s[start..end].replace('e', "ee");
+
+            assert_eq!(new_range, start..start + mapped.len());
+            assert_eq!(r.byte_slice(..start), s[..start]);
+            assert_eq!(r.byte_slice(new_range.clone()), mapped);
+            assert_eq!(r.byte_slice(new_range.end..), s[end..]);
+
+            r.assert_invariants();
+        }
+    }
+}
+
+#[test]
+fn map_range_case() {
+    let mut r = Rope::from(MEDIUM);
+
+    r.map_range(1000..50_000, transforms::to_uppercase);
+
+    let expected = format!(
+        "{}{}{}",
+        &MEDIUM[..1000],
+        MEDIUM[1000..50_000].to_uppercase(),
+        &MEDIUM[50_000..]
+    );
+
+    assert_eq!(r, expected);
+
+    r.map_range(.., transforms::to_lowercase);
+    assert_eq!(r, MEDIUM.to_lowercase());
+}
+
+/// Tests that a final sigma is lowercased like `str::to_lowercase()` does
+/// when the chunks end right after it.
+#[test]
+fn map_range_final_sigma_at_chunk_seam() {
+    for (left, right) in [("ΑΣ", "b"), ("ΑΣ", " b"), ("Α'", "Σ b"), ("Α", "Σ")]
+    {
+        let left = format!("{}{left}", "x".repeat(1000));
+        let right = format!("{right}{}", "x".repeat(1000));
+
+        let mut r = Rope::from(&*left);
+        r.append(Rope::from(&*right));
+
+        let text = format!("{left}{right}");
+        assert_eq!(r, text);
+
+        r.map_range(.., transforms::to_lowercase);
+        assert_eq!(r, text.to_lowercase(), "{:?}", &text[995..1010]);
+    }
+}
+
+#[test]
+fn map_range_unchanged_keeps_nodes() {
+    let lowercase = MEDIUM.to_lowercase();
+    let r = Rope::from(&*lowercase);
+    let mut r2 = r.clone();
+
+    let shared = r2.stats().shared_nodes;
+
+    let range = r2.map_range(.., transforms::to_lowercase);
+
+    assert_eq!(range, 0..lowercase.len());
+    assert_eq!(r2.stats().shared_nodes, shared);
+
+    let range = r2.map_range(10..10, |_| Cow::Borrowed("foo"));
+    assert_eq!(range, 10..10);
+    assert_eq!(r2, lowercase);
+}
+
+#[cfg(feature = "normalization")]
+#[test]
+fn map_range_normalization() {
+    let composed = "Th\u{e9} \u{e7}a\u{f1}on \u{1e69}\u{16d}ng\n".repeat(1000);
+
+    let decomposed = composed
+        .replace('\u{e9}', "e\u{301}")
+        .replace('\u{e7}', "c\u{327}")
+        .replace('\u{f1}', "n\u{303}")
+        .replace('\u{1e69}', "s\u{323}\u{307}")
+        .replace('\u{16d}', "u\u{306}");
+
+    let mut r = Rope::from(&*decomposed);
+
+    let range = r.map_range(.., transforms::to_nfc);
+    assert_eq!(range, 0..composed.len());
+    assert_eq!(r, composed);
+
+    let range = r.map_range(.., transforms::to_nfd);
+    assert_eq!(range, 0..decomposed.len());
+    assert_eq!(r, decomposed);
+
+    r.assert_invariants();
+}
@@ -0,0 +1,277 @@
+use crop::ot::{Delta, DeltaError, Operation, Unit};
+use crop::Rope;
+use rand::rngs::ThreadRng;
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, SMALL};
+
+const INSERTIONS: [&str; 5] = ["a", "foo", "🦀", "é\n", "\r\n"];
+
+/// Returns the length of the string in the given unit.
+fn len_in(unit: Unit, s: &str) -> usize {
+    match unit {
+        Unit::Bytes => s.len(),
+        #[cfg(feature = "utf16-metric")]
+        Unit::Utf16 => s.encode_utf16().count(),
+    }
+}
+
+/// Returns a random delta that can be applied to `s`.
+fn random_delta(rng: &mut ThreadRng, s: &str, unit: Unit) -> Delta {
+    let mut delta = Delta::new(unit);
+
+    let chars = s.chars().collect::<Vec<_>>();
+
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let end = (idx + rng.gen_range(1..=8)).min(chars.len());
+
+        let len = chars[idx..end]
+            .iter()
+            .map(|&ch| len_in(unit, ch.encode_utf8(&mut [0; 4])))
+            .sum::<usize>();
+
+        match rng.gen_range(0..4) {
+            0 => delta.delete(len),
+            1 => delta.insert(INSERTIONS[rng.gen_range(0..5)]).delete(len),
+            2 => delta.insert(INSERTIONS[rng.gen_range(0..5)]).retain(len),
+            _ => delta.retain(len),
+        };
+
+        idx = end;
+    }
+
+    if rng.gen_bool(0.5) {
+        delta.insert(INSERTIONS[rng.gen_range(0..5)]);
+    }
+
+    delta
+}
+
+/// Applies a byte delta to a string.
+fn apply_str(s: &str, delta: &Delta) -> String {
+    let mut applied = String::new();
+
+    let mut offset = 0;
+
+    for op in delta.ops() {
+        match op {
+            Operation::Retain(len) => {
+                applied.push_str(&s[offset..offset + len]);
+                offset += len;
+            },
+            Operation::Insert(text) => applied.push_str(text),
+            Operation::Delete(len) => offset += len,
+        }
+    }
+
+    applied
+}
+
+fn units() -> Vec<Unit> {
+    #[cfg(feature = "utf16-metric")]
+    {
+        vec![Unit::Bytes, Unit::Utf16]
+    }
+    #[cfg(not(feature = "utf16-metric"))]
+    {
+        vec![Unit::Bytes]
+    }
+}
+
+#[test]
+fn delta_builder_normalizes() {
+    let mut a = Delta::new(Unit::Bytes);
+    a.retain(1).retain(2).delete(1).insert("a").delete(2).insert("b");
+
+    assert_eq!(
+        a.ops(),
+        [
+            Operation::Retain(3),
+            Operation::Insert("ab".into()),
+            Operation::Delete(3),
+        ]
+    );
+
+    assert_eq!(a.base_len(), 6);
+    assert_eq!(a.target_len(), 5);
+
+    let mut b = Delta::new(Unit::Bytes);
+    b.retain(3).insert("ab").retain(0).delete(3).insert("");
+
+    assert_eq!(a, b);
+    assert!(!a.is_noop());
+    assert!(Delta::new(Unit::Bytes).retain(5).is_noop());
+}
+
+#[test]
+fn apply_delta_errors() {
+    let mut r = Rope::from("aé");
+
+    let mut delta = Delta::new(Unit::Bytes);
+    delta.retain(2);
+
+    assert_eq!(
+        r.apply_delta(&delta),
+        Err(DeltaError::LengthMismatch { expected: 3, found: 2 })
+    );
+
+    let mut delta = Delta::new(Unit::Bytes);
+    delta.retain(1).insert("b").delete(1).retain(1);
+
+    assert_eq!(
+        r.apply_delta(&delta),
+        Err(DeltaError::NotCodePointBoundary { offset: 2 })
+    );
+
+    assert_eq!(r, "aé");
+}
+
+#[test]
+fn compose_errors() {
+    let mut a = Delta::new(Unit::Bytes);
+    a.insert("🦀");
+
+    let mut b = Delta::new(Unit::Bytes);
+    b.retain(3);
+
+    assert_eq!(
+        a.compose(&b),
+        Err(DeltaError::LengthMismatch { expected: 4, found: 3 })
+    );
+
+    let mut b = Delta::new(Unit::Bytes);
+    b.delete(2).retain(2);
+
+    assert_eq!(
+        a.compose(&b),
+        Err(DeltaError::NotCodePointBoundary { offset: 2 })
+    );
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn apply_delta_random() {
+    let mut rng = rand::thread_rng();
+
+    for s in [SMALL, CURSED_LIPSUM] {
+        for _ in 0..20 {
+            let delta = random_delta(&mut rng, s, Unit::Bytes);
+
+            let mut r = Rope::from(s);
+            r.apply_delta(&delta).unwrap();
+            r.assert_invariants();
+
+            assert_eq!(r, apply_str(s, &delta));
+            assert_eq!(r.byte_len(), delta.target_len());
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn compose_random() {
+    let mut rng = rand::thread_rng();
+
+    for unit in units() {
+        for s in [SMALL, CURSED_LIPSUM] {
+            for _ in 0..20 {
+                let a = random_delta(&mut rng, s, unit);
+
+                let mut r = Rope::from(s);
+                r.apply_delta(&a).unwrap();
+
+                let b = random_delta(&mut rng, &r.to_string(), unit);
+                r.apply_delta(&b).unwrap();
+
+                let mut composed = Rope::from(s);
+                composed.apply_delta(&a.compose(&b).unwrap()).unwrap();
+
+                assert_eq!(r, composed);
+            }
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn transform_random() {
+    let mut rng = rand::thread_rng();
+
+    for unit in units() {
+        for s in [SMALL, CURSED_LIPSUM] {
+            for _ in 0..20 {
+                let a = random_delta(&mut rng, s, unit);
+                let b = random_delta(&mut rng, s, unit);
+
+                let (a_prime, b_prime) = a.transform(&b).unwrap();
+
+                let mut r1 = Rope::from(s);
+                r1.apply_delta(&a).unwrap();
+                r1.apply_delta(&b_prime).unwrap();
+
+                let mut r2 = Rope::from(s);
+                r2.apply_delta(&b).unwrap();
+                r2.apply_delta(&a_prime).unwrap();
+
+                assert_eq!(r1, r2);
+
+                assert_eq!(
+                    a.compose(&b_prime).unwrap(),
+                    b.compose(&a_prime).unwrap()
+                );
+            }
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn invert_random() {
+    let mut rng = rand::thread_rng();
+
+    for unit in units() {
+        for s in [SMALL, CURSED_LIPSUM] {
+            for _ in 0..20 {
+                let mut r = Rope::from(s);
+
+                let delta = random_delta(&mut rng, s, unit);
+                let inverse = delta.invert(&r).unwrap();
+
+                r.apply_delta(&delta).unwrap();
+                r.apply_delta(&inverse).unwrap();
+
+                assert_eq!(r, s);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "utf16-metric")]
+#[test]
+fn apply_delta_utf16() {
+    let mut r = Rope::from("a𐐀b");
+
+    let mut delta = Delta::new(Unit::Utf16);
+    delta.retain(3).insert("c").retain(1);
+
+    r.apply_delta(&delta).unwrap();
+    assert_eq!(r, "a𐐀cb");
+
+    // Offset 2 is between the two surrogates of "𐐀".
+    let mut delta = Delta::new(Unit::Utf16);
+    delta.retain(2).delete(3);
+
+    assert_eq!(
+        r.apply_delta(&delta),
+        Err(DeltaError::NotCodePointBoundary { offset: 2 })
+    );
+
+    let mut bytes = Delta::new(Unit::Bytes);
+    bytes.retain(7);
+
+    assert_eq!(bytes.compose(&delta), Err(DeltaError::UnitMismatch));
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "rayon")]
+
+use crop::Rope;
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+#[test]
+fn parallel_same_as_sequential() {
+    for s in ["", "a", "\n", TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from_str_parallel(s);
+
+        r.assert_invariants();
+
+        let sequential = Rope::from(s);
+
+        assert_eq!(r, s);
+        assert_eq!(r.line_len(), sequential.line_len());
+        assert!(r.chunks().eq(sequential.chunks()));
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn parallel_large_input() {
+    let s = CURSED_LIPSUM.repeat(64);
+
+    let r = Rope::from_str_parallel(&s);
+
+    r.assert_invariants();
+
+    let sequential = Rope::from(&*s);
+
+    assert_eq!(r, s);
+    assert_eq!(r.line_len(), sequential.line_len());
+    assert_eq!(r.byte_of_line(100), sequential.byte_of_line(100));
+    assert_eq!(r.line_slice(100..200), sequential.line_slice(100..200));
+}
@@ -51,3 +51,20 @@ fn builder_crlf_1() {
     r.assert_invariants();
     assert_eq!(r, "aaa\r\nbbb");
 }
+
+#[test]
+fn builder_trailing_newline() {
+    let mut b = RopeBuilder::new();
+    b.append("aaa\nbbb").append("\n");
+    let r = b.build();
+    r.assert_invariants();
+    assert_eq!(r, "aaa\nbbb\n");
+    assert_eq!(r.line_len(), 2);
+    assert_eq!(r, Rope::from("aaa\nbbb\n"));
+
+    let mut b = RopeBuilder::new();
+    b.append("aaa\n").append("");
+    let r = b.build();
+    assert_eq!(r.line_len(), 1);
+    assert_eq!(r, Rope::from("aaa\n"));
+}
@@ -0,0 +1,281 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+
+use crop::Rope;
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+fn hash<T: Hash + ?Sized>(t: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A `Hasher` that records the arguments of every `write()` call, used to
+/// check that the hash doesn't depend on how the text is chunked even for
+/// hashers that aren't streaming.
+#[derive(Default)]
+struct RecordingHasher {
+    writes: Vec<Vec<u8>>,
+}
+
+impl Hasher for RecordingHasher {
+    fn finish(&self) -> u64 {
+        0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.writes.push(bytes.to_vec());
+    }
+}
+
+#[test]
+fn hash_same_as_str() {
+    for s in ["", "a", "🐕‍🦺", TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM]
+    {
+        let r = Rope::from(s);
+        assert_eq!(hash(s), hash(&r));
+        assert_eq!(hash(s), hash(&r.byte_slice(..)));
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn hash_slices_random() {
+    let mut rng = rand::thread_rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let start = rng.gen_range(0..=s.len());
+            let end = rng.gen_range(start..=s.len());
+            assert_eq!(hash(&s[start..end]), hash(&r.byte_slice(start..end)));
+        }
+    }
+}
+
+/// Tests that two `Rope`s with the same content but different chunks produce
+/// the same sequence of writes to the hasher.
+#[cfg_attr(miri, ignore)]
+#[test]
+fn hash_independent_of_chunks() {
+    for s in [SMALL, MEDIUM, CURSED_LIPSUM] {
+        let r1 = Rope::from(s);
+
+        let mut r2 = Rope::new();
+        for (idx, ch) in s.char_indices() {
+            r2.insert(idx, &s[idx..idx + ch.len_utf8()]);
+        }
+
+        // Edit the rope a bit to move its chunks around.
+        let mid = r2.floor_char_boundary(r2.byte_len() / 2);
+        r2.insert(mid, "xyz");
+        r2.delete(mid..mid + 3);
+
+        assert_eq!(r1, r2);
+
+        let mut h1 = RecordingHasher::default();
+        r1.hash(&mut h1);
+
+        let mut h2 = RecordingHasher::default();
+        r2.hash(&mut h2);
+
+        let mut h3 = RecordingHasher::default();
+        r2.byte_slice(..).hash(&mut h3);
+
+        assert_eq!(h1.writes, h2.writes);
+        assert_eq!(h1.writes, h3.writes);
+    }
+}
+
+#[test]
+fn cmp_same_as_str() {
+    let strs = ["", "a", "ab", "abc", "b", "ba", "é", "e\u{301}", "🐕‍🦺"];
+
+    for lhs in strs {
+        for rhs in strs {
+            let (l, r) = (Rope::from(lhs), Rope::from(rhs));
+            assert_eq!(lhs.cmp(rhs), l.cmp(&r));
+            assert_eq!(Some(lhs.cmp(rhs)), l.partial_cmp(&r.byte_slice(..)));
+            assert_eq!(Some(lhs.cmp(rhs)), l.byte_slice(..).partial_cmp(&r));
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn cmp_slices_random() {
+    let mut rng = rand::thread_rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+
+        for _ in 0..50 {
+            let start = rng.gen_range(0..=s.len());
+            let end = rng.gen_range(start..=s.len());
+            let lhs = r.byte_slice(start..end);
+
+            // Compare against a slice sharing a prefix with `lhs` to make sure
+            // we're not always decided by the first byte.
+            let end2 = rng.gen_range(start..=s.len());
+            let rhs = r.byte_slice(start..end2);
+
+            assert_eq!(s[start..end].cmp(&s[start..end2]), lhs.cmp(&rhs));
+
+            let start3 = rng.gen_range(0..=s.len());
+            let end3 = rng.gen_range(start3..=s.len());
+            let rhs = r.byte_slice(start3..end3);
+
+            assert_eq!(s[start..end].cmp(&s[start3..end3]), lhs.cmp(&rhs));
+        }
+    }
+}
+
+#[test]
+fn cmp_longer_is_greater() {
+    let r = Rope::from("foo");
+    let s = r.byte_slice(..2);
+    assert_eq!(s.cmp(&r.byte_slice(..)), Ordering::Less);
+    assert!(r > s);
+    assert!(s < r);
+}
+
+#[test]
+fn ropes_as_map_keys() {
+    let lines = ["foo", "bar", "baz", "foo", "qux", "bar"];
+
+    let mut counts = HashMap::new();
+    let mut sorted = BTreeMap::new();
+
+    for line in lines {
+        *counts.entry(Rope::from(line)).or_insert(0) += 1;
+        *sorted.entry(Rope::from(line)).or_insert(0) += 1;
+    }
+
+    assert_eq!(counts[&Rope::from("foo")], 2);
+    assert_eq!(counts[&Rope::from("qux")], 1);
+
+    let keys = sorted.keys().map(ToString::to_string).collect::<Vec<_>>();
+    assert_eq!(keys, ["bar", "baz", "foo", "qux"]);
+
+    let r = Rope::from("foo\nbar\nbaz\nfoo");
+    let mut lines = r.lines().collect::<Vec<_>>();
+    lines.sort();
+    lines.dedup();
+    assert_eq!(lines, ["bar", "baz", "foo"]);
+}
+
+#[test]
+fn eq_ignore_ascii_case() {
+    let r = Rope::from(CURSED_LIPSUM.to_uppercase());
+    assert!(!r.eq_ignore_ascii_case(CURSED_LIPSUM));
+
+    let r = Rope::from(MEDIUM.to_ascii_uppercase());
+    assert!(r.eq_ignore_ascii_case(MEDIUM));
+    assert!(r.byte_slice(10..5000).eq_ignore_ascii_case(&MEDIUM[10..5000]));
+    assert!(!r.byte_slice(10..5000).eq_ignore_ascii_case(&MEDIUM[10..5001]));
+
+    let r = Rope::from("Ḽơᶉëᶆ ȋṕšᶙṁ");
+    assert!(r.eq_ignore_ascii_case("Ḽơᶉëᶆ ȋṕšᶙṁ"));
+    assert!(!r.eq_ignore_ascii_case("ḽơᶉëᶆ ȋṕšᶙṁ"));
+}
+
+/// Tests that `starts_with()` and `ends_with()` agree with the ones on `str`
+/// on random slices, including prefixes and suffixes that don't end on a
+/// char boundary of the slice.
+#[cfg_attr(miri, ignore)]
+#[test]
+fn starts_with_ends_with_random() {
+    let mut rng = rand::thread_rng();
+
+    for s in [CURSED_LIPSUM, SMALL, MEDIUM] {
+        let r = Rope::from(s);
+
+        for _ in 0..100 {
+            let mut start = rng.gen_range(0..=s.len());
+            let mut end = rng.gen_range(start..=s.len());
+
+            while !s.is_char_boundary(start) {
+                start -= 1;
+            }
+
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let slice = r.byte_slice(start..end);
+            let str_slice = &s[start..end];
+
+            let len =
+                rng.gen_range(0..=(end - start + 2).min(s.len() - start));
+            let prefix = &s.as_bytes()[start..start + len];
+            let suffix = &s.as_bytes()[end.saturating_sub(len)..end];
+
+            if let Ok(prefix) = std::str::from_utf8(prefix) {
+                assert_eq!(
+                    slice.starts_with(prefix),
+                    str_slice.starts_with(prefix)
+                );
+            }
+
+            if let Ok(suffix) = std::str::from_utf8(suffix) {
+                assert_eq!(
+                    slice.ends_with(suffix),
+                    str_slice.ends_with(suffix)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn starts_with_ends_with() {
+    let r = Rope::from(MEDIUM);
+
+    assert!(r.starts_with(&MEDIUM[..3000]));
+    assert!(r.ends_with(&MEDIUM[MEDIUM.len() - 3000..]));
+    assert!(!r.starts_with(&MEDIUM[1..3000]));
+    assert!(!r.ends_with(&MEDIUM[MEDIUM.len() - 3000..MEDIUM.len() - 1]));
+
+    let r = Rope::from("abc");
+    assert!(!r.starts_with("abcd"));
+    assert!(!r.ends_with("abcd"));
+    assert!(r.byte_slice(1..1).starts_with(""));
+    assert!(r.byte_slice(1..1).ends_with(""));
+}
+
+#[cfg(feature = "normalization")]
+#[test]
+fn eq_normalized() {
+    let composed = "Th\u{e9} \u{e7}a\u{f1}on \u{1e69}\u{16d}ng\n".repeat(500);
+
+    let decomposed = composed
+        .replace('\u{e9}', "e\u{301}")
+        .replace('\u{e7}', "c\u{327}")
+        .replace('\u{f1}', "n\u{303}")
+        .replace('\u{1e69}', "s\u{323}\u{307}")
+        .replace('\u{16d}', "u\u{306}");
+
+    // Same as `decomposed`, but with the combining marks of the 's' in the
+    // non-canonical order.
+    let reordered = decomposed.replace("\u{323}\u{307}", "\u{307}\u{323}");
+
+    let r = Rope::from(&*decomposed);
+    assert!(r.eq_normalized(&composed));
+    assert!(r.eq_normalized(&reordered));
+    assert!(r.byte_slice(..).eq_normalized(&composed));
+    assert!(!r.eq_normalized(composed.trim_end()));
+    assert!(!r.eq_normalized(&composed.replace('\u{e7}', "c")));
+
+    let r = Rope::from(&*composed);
+    assert!(r.eq_normalized(&decomposed));
+    assert!(r
+        .line(3)
+        .eq_normalized("Th\u{e9} c\u{327}a\u{f1}on \u{1e69}u\u{306}ng"));
+}
@@ -35,6 +35,32 @@ fn rope_is_char_boundary() {
     }
 }
 
+#[cfg_attr(miri, ignore)]
+#[test]
+fn rope_floor_ceil_char_boundary() {
+    for s in ["", "Hi", "🐕‍🦺", TINY, SMALL, CURSED_LIPSUM] {
+        let rope = Rope::from(s);
+        let slice = rope.byte_slice(..);
+
+        for idx in 0..=s.len() + 1 {
+            let mut floor = idx.min(s.len());
+            while !s.is_char_boundary(floor) {
+                floor -= 1;
+            }
+
+            let mut ceil = idx.min(s.len());
+            while !s.is_char_boundary(ceil) {
+                ceil += 1;
+            }
+
+            assert_eq!(floor, rope.floor_char_boundary(idx));
+            assert_eq!(floor, slice.floor_char_boundary(idx));
+            assert_eq!(ceil, rope.ceil_char_boundary(idx));
+            assert_eq!(ceil, slice.ceil_char_boundary(idx));
+        }
+    }
+}
+
 /// ```
 /// Root
 /// ├───┐
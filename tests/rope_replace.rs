@@ -403,3 +403,96 @@ fn fix_crlf_4() {
 
     assert_eq!(r, "\r\n\r\n\r\n\r\n\r\n\r\n\r\n\r\n\r\n\r\n\r\n");
 }
+
+#[test]
+fn insert_rope_0() {
+    let other = Rope::from("Hello ");
+
+    let mut r = Rope::from("world!");
+    r.insert_rope(0, other.byte_slice(..));
+    r.assert_invariants();
+    assert_eq!(r, "Hello world!");
+
+    r.insert_rope(r.byte_len(), other.byte_slice(..5));
+    r.assert_invariants();
+    assert_eq!(r, "Hello world!Hello");
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn insert_rope_shares_nodes() {
+    let large = Rope::from(LARGE);
+
+    let mut r = Rope::from(SMALL);
+    r.insert_rope(SMALL.len() / 2, large.byte_slice(1000..LARGE.len() - 1000));
+    r.assert_invariants();
+
+    assert!(large.stats().shared_nodes > 0);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn replace_with_rope_random() {
+    let mut rng = rand::thread_rng();
+
+    let source = Rope::from(MEDIUM);
+
+    let mut r = Rope::from(SMALL);
+    let mut s = String::from(SMALL);
+
+    for _ in 0..100 {
+        let mut start = rng.gen_range(0..=s.len());
+        while !s.is_char_boundary(start) {
+            start += 1;
+        }
+
+        let mut end = rng.gen_range(start..=s.len().min(start + 1000));
+        while !s.is_char_boundary(end) {
+            end += 1;
+        }
+
+        let mut slice_start = rng.gen_range(0..=MEDIUM.len());
+        while !MEDIUM.is_char_boundary(slice_start) {
+            slice_start += 1;
+        }
+
+        let mut slice_end =
+            rng.gen_range(slice_start..=MEDIUM.len().min(slice_start + 5000));
+        while !MEDIUM.is_char_boundary(slice_end) {
+            slice_end += 1;
+        }
+
+        r.replace_with_rope(
+            start..end,
+            source.byte_slice(slice_start..slice_end),
+        );
+
+        s.replace_range(start..end, &MEDIUM[slice_start..slice_end]);
+
+        r.assert_invariants();
+        assert_eq!(r, s);
+    }
+}
+
+#[test]
+fn replace_with_rope_trailing_newline() {
+    let other = Rope::from("foo\nbar");
+
+    let mut r = Rope::from("baz");
+    r.replace_with_rope(.., other.byte_slice(..4));
+    r.assert_invariants();
+    assert_eq!(r.line_len(), 1);
+
+    r.replace_with_rope(3.., other.byte_slice(4..));
+    r.assert_invariants();
+    assert_eq!(r, "foobar");
+    assert_eq!(r.line_len(), 1);
+}
+
+#[should_panic]
+#[test]
+fn replace_with_rope_out_of_bounds() {
+    let other = Rope::from("foo");
+    let mut r = Rope::from("bar");
+    r.replace_with_rope(2..4, other.byte_slice(..));
+}
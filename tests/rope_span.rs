@@ -0,0 +1,156 @@
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, TEXT_LINES};
+use crop::{Rope, RopeSpan};
+use rand::Rng;
+
+#[test]
+fn span_is_send_sync_static() {
+    fn assert_send_sync_static<T: Send + Sync + 'static>() {}
+    assert_send_sync_static::<RopeSpan>();
+}
+
+#[test]
+fn span_empty() {
+    let span = RopeSpan::default();
+    span.assert_invariants();
+    assert!(span.is_empty());
+    assert_eq!(span, "");
+    assert_eq!(span.line_len(), 0);
+}
+
+#[test]
+fn span_from_rope() {
+    let r = Rope::from(TEXT_LINES);
+    let span = RopeSpan::from(&r);
+    span.assert_invariants();
+    assert_eq!(span, r);
+    assert_eq!(span.line_len(), r.line_len());
+
+    for (line, expected) in span.lines().zip(TEXT_LINES.lines()) {
+        assert_eq!(line, expected);
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn span_random_slices() {
+    let mut rng = rand::thread_rng();
+
+    let r = Rope::from(MEDIUM);
+
+    for _ in 0..100 {
+        let mut start = rng.gen_range(0..=r.byte_len());
+        while !r.is_char_boundary(start) {
+            start += 1;
+        }
+
+        let mut end = rng.gen_range(start..=r.byte_len());
+        while !r.is_char_boundary(end) {
+            end += 1;
+        }
+
+        let slice = r.byte_slice(start..end);
+        let span = RopeSpan::from(slice);
+
+        span.assert_invariants();
+
+        assert_eq!(span, slice);
+        assert_eq!(span, MEDIUM[start..end]);
+        assert_eq!(span.byte_len(), slice.byte_len());
+        assert_eq!(span.line_len(), slice.line_len());
+
+        if !span.is_empty() {
+            let line = rng.gen_range(0..span.line_len());
+            assert_eq!(span.line(line), slice.line(line));
+            assert_eq!(span.byte_of_line(line), slice.byte_of_line(line));
+        }
+
+        // Spans of spans.
+        let sub = RopeSpan::from(span.byte_slice(span.byte_len() / 2..));
+        sub.assert_invariants();
+        assert_eq!(sub, slice.byte_slice(span.byte_len() / 2..));
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn span_outlives_edits() {
+    let mut r = Rope::from(LARGE);
+
+    let span = RopeSpan::from(r.byte_slice(1000..LARGE.len() - 1000));
+
+    r.replace(500..LARGE.len() / 2, CURSED_LIPSUM);
+    r.delete(..100);
+    drop(r);
+
+    span.assert_invariants();
+    assert_eq!(span, LARGE[1000..LARGE.len() - 1000]);
+
+    let r = Rope::from(span);
+    r.assert_invariants();
+    assert_eq!(r, LARGE[1000..LARGE.len() - 1000]);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn span_across_threads() {
+    let r = Rope::from(MEDIUM);
+
+    let handles = (0..4)
+        .map(|i| {
+            let span = RopeSpan::from(r.line_slice(i * 100..(i + 1) * 100));
+            std::thread::spawn(move || span.to_string())
+        })
+        .collect::<Vec<_>>();
+
+    for (i, handle) in handles.into_iter().enumerate() {
+        assert_eq!(
+            handle.join().unwrap(),
+            r.line_slice(i * 100..(i + 1) * 100)
+        );
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn span_forwards_slice_methods() {
+    let r = Rope::from(CURSED_LIPSUM);
+
+    let start = r.ceil_char_boundary(r.byte_len() / 4);
+    let end = r.floor_char_boundary(3 * r.byte_len() / 4);
+
+    let slice = r.byte_slice(start..end);
+    let span = RopeSpan::from(slice);
+
+    for offset in [0, 1, 2, 3, span.byte_len() / 2, span.byte_len()] {
+        assert_eq!(
+            span.ceil_char_boundary(offset),
+            slice.ceil_char_boundary(offset)
+        );
+        assert_eq!(
+            span.floor_char_boundary(offset),
+            slice.floor_char_boundary(offset)
+        );
+        assert_eq!(span.try_byte(offset), slice.try_byte(offset));
+        assert_eq!(
+            span.try_line_of_byte(offset),
+            slice.try_line_of_byte(offset)
+        );
+        assert_eq!(
+            span.try_byte_slice(offset..),
+            slice.try_byte_slice(offset..)
+        );
+    }
+
+    let prefix = slice.byte_slice(..slice.floor_char_boundary(10)).to_string();
+    let suffix = slice.byte_slice(slice.ceil_char_boundary(10)..).to_string();
+
+    assert!(span.starts_with(&prefix));
+    assert!(span.ends_with(&suffix));
+    assert!(span.eq_ignore_ascii_case(&slice.to_string().to_ascii_uppercase()));
+
+    assert!(span < r);
+    assert!(r > span);
+    assert!(span <= slice && slice <= span);
+}
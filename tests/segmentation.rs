@@ -0,0 +1,250 @@
+#![cfg(feature = "graphemes")]
+
+use crop::Rope;
+use rand::Rng;
+use unicode_segmentation::UnicodeSegmentation;
+
+mod common;
+
+use common::{CURSED_LIPSUM, SMALL, TEXT, TEXT_EMOJI, TEXT_LINES};
+
+/// A text with multi-char words, CRLFs split between chunks and sentences
+/// spanning several lines.
+const SEGMENTS_TEXT: &str = concat!(
+    "Hello, world! Can't stop\r\nwon't stop.\r\n\r\n",
+    "e\u{301}\u{302}te\u{301} 🐻‍❄️ 3.14 U.S.A.\n",
+    "Tschüß, 老虎 и медведь? Ja.\r\n",
+);
+
+fn texts() -> [&'static str; 6] {
+    [SEGMENTS_TEXT, TEXT, TEXT_EMOJI, TEXT_LINES, CURSED_LIPSUM, SMALL]
+}
+
+/// Returns the word boundaries of the string, including its start and end.
+fn word_boundaries(s: &str) -> Vec<usize> {
+    s.split_word_bound_indices()
+        .map(|(idx, _)| idx)
+        .chain(std::iter::once(s.len()))
+        .collect()
+}
+
+#[test]
+fn words_match_str() {
+    for s in texts() {
+        let r = Rope::from(s);
+
+        let words = s.unicode_words().collect::<Vec<_>>();
+        assert_eq!(words, r.words().collect::<Vec<_>>());
+        assert_eq!(words, r.byte_slice(..).words().collect::<Vec<_>>());
+
+        let mut rev = r.words().rev().collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(words, rev);
+    }
+}
+
+#[test]
+fn word_bounds_match_str() {
+    for s in texts() {
+        let r = Rope::from(s);
+
+        let bounds = s.split_word_bounds().collect::<Vec<_>>();
+        assert_eq!(bounds, r.word_bounds().collect::<Vec<_>>());
+        assert_eq!(s, r.word_bounds().collect::<String>());
+
+        let mut rev = r.word_bounds().rev().collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(bounds, rev);
+    }
+}
+
+#[test]
+fn sentences_match_str() {
+    for s in texts() {
+        let r = Rope::from(s);
+
+        let sentences = s.split_sentence_bounds().collect::<Vec<_>>();
+        assert_eq!(sentences, r.sentences().collect::<Vec<_>>());
+        assert_eq!(s, r.sentences().collect::<String>());
+
+        let mut rev = r.sentences().rev().collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(sentences, rev);
+    }
+}
+
+#[test]
+fn segments_of_slices() {
+    let s = SEGMENTS_TEXT;
+    let r = Rope::from(s);
+
+    for start in (0..=s.len()).filter(|&i| s.is_char_boundary(i)) {
+        for end in (start..=s.len()).filter(|&i| s.is_char_boundary(i)) {
+            let slice = r.byte_slice(start..end);
+            let s = &s[start..end];
+
+            assert_eq!(
+                s.unicode_words().collect::<Vec<_>>(),
+                slice.words().collect::<Vec<_>>(),
+                "{start}..{end}"
+            );
+
+            assert_eq!(
+                s.split_word_bounds().collect::<Vec<_>>(),
+                slice.word_bounds().collect::<Vec<_>>(),
+                "{start}..{end}"
+            );
+
+            assert_eq!(
+                s.split_sentence_bounds().collect::<Vec<_>>(),
+                slice.sentences().collect::<Vec<_>>(),
+                "{start}..{end}"
+            );
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn word_bounds_from_both_ends() {
+    let mut rng = rand::thread_rng();
+
+    for s in texts() {
+        let r = Rope::from(s);
+
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+        let mut bounds = r.word_bounds();
+
+        loop {
+            if rng.gen() {
+                let Some(segment) = bounds.next() else { break };
+                front.push(segment);
+            } else {
+                let Some(segment) = bounds.next_back() else { break };
+                back.push(segment);
+            }
+        }
+
+        assert_eq!(None, bounds.next());
+        assert_eq!(None, bounds.next_back());
+
+        front.extend(back.into_iter().rev());
+        assert_eq!(s.split_word_bounds().collect::<Vec<_>>(), front);
+    }
+}
+
+#[test]
+fn word_boundaries_prev_next() {
+    for s in texts() {
+        let boundaries = word_boundaries(s);
+
+        let r = Rope::from(s);
+        let slice = r.byte_slice(..);
+
+        for idx in 0..=s.len() {
+            let prev =
+                *boundaries.iter().rev().find(|&&b| b < idx).unwrap_or(&0);
+            let next =
+                *boundaries.iter().find(|&&b| b > idx).unwrap_or(&s.len());
+
+            assert_eq!(prev, r.prev_word_boundary(idx), "{idx}");
+            assert_eq!(next, r.next_word_boundary(idx), "{idx}");
+            assert_eq!(prev, slice.prev_word_boundary(idx), "{idx}");
+            assert_eq!(next, slice.next_word_boundary(idx), "{idx}");
+        }
+
+        // Walking the whole rope one word boundary at a time should yield the
+        // same boundaries both forwards and backwards.
+        let mut forward = vec![0];
+        while *forward.last().unwrap() < r.byte_len() {
+            forward.push(r.next_word_boundary(*forward.last().unwrap()));
+        }
+        assert_eq!(forward, boundaries);
+
+        let mut backward = vec![r.byte_len()];
+        while *backward.last().unwrap() > 0 {
+            backward.push(r.prev_word_boundary(*backward.last().unwrap()));
+        }
+        backward.reverse();
+        assert_eq!(backward, boundaries);
+    }
+}
+
+#[test]
+fn word_boundaries_of_slices() {
+    let s = SEGMENTS_TEXT;
+    let r = Rope::from(s);
+
+    for start in (0..=s.len()).filter(|&i| s.is_char_boundary(i)) {
+        let slice = r.byte_slice(start..);
+        let boundaries = word_boundaries(&s[start..]);
+
+        for idx in 0..=slice.byte_len() {
+            let prev =
+                *boundaries.iter().rev().find(|&&b| b < idx).unwrap_or(&0);
+            let next = *boundaries
+                .iter()
+                .find(|&&b| b > idx)
+                .unwrap_or(&slice.byte_len());
+
+            assert_eq!(prev, slice.prev_word_boundary(idx), "{start} {idx}");
+            assert_eq!(next, slice.next_word_boundary(idx), "{start} {idx}");
+        }
+    }
+}
+
+/// Word and sentence boundaries are found by segmenting a few chunks at a
+/// time, which shouldn't change the result when lines span many chunks.
+#[cfg_attr(miri, ignore)]
+#[test]
+fn segments_of_long_lines() {
+    let s = "Flags 🇫🇷🇩🇪🇮🇹 and 🇫🇷🇩 cost 3.14 U.S.A. dollars, \
+             e\u{301}\u{302}te\u{301}:x isn't it? Yes.   "
+        .repeat(64);
+
+    let r = Rope::from(s.as_str());
+
+    assert_eq!(
+        s.unicode_words().collect::<Vec<_>>(),
+        r.words().collect::<Vec<_>>()
+    );
+
+    let bounds = s.split_word_bounds().collect::<Vec<_>>();
+    assert_eq!(bounds, r.word_bounds().collect::<Vec<_>>());
+
+    let mut rev = r.word_bounds().rev().collect::<Vec<_>>();
+    rev.reverse();
+    assert_eq!(bounds, rev);
+
+    let sentences = s.split_sentence_bounds().collect::<Vec<_>>();
+    assert_eq!(sentences, r.sentences().collect::<Vec<_>>());
+
+    let mut rev = r.sentences().rev().collect::<Vec<_>>();
+    rev.reverse();
+    assert_eq!(sentences, rev);
+
+    let boundaries = word_boundaries(&s);
+
+    for idx in (0..=s.len()).filter(|&i| s.is_char_boundary(i)) {
+        let prev = *boundaries.iter().rev().find(|&&b| b < idx).unwrap_or(&0);
+        let next = *boundaries.iter().find(|&&b| b > idx).unwrap_or(&s.len());
+
+        assert_eq!(prev, r.prev_word_boundary(idx), "{idx}");
+        assert_eq!(next, r.next_word_boundary(idx), "{idx}");
+    }
+}
+
+#[should_panic]
+#[test]
+fn next_word_boundary_out_of_bounds() {
+    let r = Rope::from("foo bar");
+    r.next_word_boundary(8);
+}
+
+#[should_panic]
+#[test]
+fn prev_word_boundary_out_of_bounds() {
+    let r = Rope::from("foo bar");
+    r.byte_slice(4..).prev_word_boundary(4);
+}
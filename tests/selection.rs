@@ -0,0 +1,176 @@
+use crop::{Rope, Selection, SelectionSet};
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, MEDIUM, SMALL};
+
+#[test]
+fn selection_accessors() {
+    let selection = Selection::new(8, 3);
+
+    assert_eq!(selection.start(), 3);
+    assert_eq!(selection.end(), 8);
+    assert_eq!(selection.range(), 3..8);
+    assert!(selection.is_backward());
+    assert!(!selection.is_empty());
+
+    let cursor = Selection::cursor(5);
+
+    assert!(!cursor.is_backward());
+    assert!(cursor.is_empty());
+}
+
+#[test]
+fn selection_transform() {
+    // Replacing a range before the selection shifts it.
+    let mut selection = Selection::new(10, 20);
+    selection.transform(0..5, 2);
+    assert_eq!(selection, Selection::new(7, 17));
+
+    // Replacing a range after the selection doesn't move it.
+    let mut selection = Selection::new(10, 20);
+    selection.transform(20..30, 0);
+    assert_eq!(selection, Selection::new(10, 20));
+
+    // Inserting at a cursor doesn't move it.
+    let mut cursor = Selection::cursor(10);
+    cursor.transform(10..10, 3);
+    assert_eq!(cursor, Selection::cursor(10));
+
+    // Offsets inside the replaced range move to the end of the new text.
+    let mut selection = Selection::new(15, 5);
+    selection.transform(10..20, 4);
+    assert_eq!(selection, Selection::new(14, 5));
+}
+
+#[test]
+fn selection_set_merges_overlapping() {
+    let set = [
+        Selection::new(10, 15),
+        Selection::cursor(0),
+        Selection::new(14, 12),
+        Selection::cursor(0),
+        Selection::new(5, 3),
+        Selection::cursor(15),
+    ]
+    .into_iter()
+    .collect::<SelectionSet>();
+
+    assert_eq!(
+        set.as_slice(),
+        [
+            Selection::cursor(0),
+            Selection::new(5, 3),
+            Selection::new(10, 15),
+            Selection::cursor(15),
+        ]
+    );
+
+    assert_eq!(set.len(), 4);
+    assert!(!SelectionSet::new().iter().any(|_| true));
+}
+
+#[test]
+fn selection_set_insert_empty() {
+    let mut r = Rope::from("foo bar baz");
+
+    let mut set = SelectionSet::new();
+    set.insert(&mut r, "abc");
+
+    assert!(set.is_empty());
+    assert_eq!(r, "foo bar baz");
+}
+
+#[test]
+fn selection_set_delete_merges_cursors() {
+    let mut r = Rope::from("foo bar baz");
+
+    let mut set = [Selection::new(0, 4), Selection::new(4, 8)]
+        .into_iter()
+        .collect::<SelectionSet>();
+
+    set.insert(&mut r, "");
+
+    assert_eq!(r, "baz");
+    assert_eq!(set.as_slice(), [Selection::cursor(0)]);
+}
+
+#[test]
+fn selection_set_transform_merges() {
+    let mut set = [Selection::new(2, 4), Selection::new(6, 8)]
+        .into_iter()
+        .collect::<SelectionSet>();
+
+    // Selections that end up touching aren't merged.
+    set.transform(3..7, 0);
+    assert_eq!(set.as_slice(), [Selection::new(2, 3), Selection::new(3, 4)]);
+
+    let mut set: SelectionSet =
+        [4, 6, 9].into_iter().map(Selection::cursor).collect();
+
+    set.transform(3..7, 1);
+    assert_eq!(set.as_slice(), [Selection::cursor(4), Selection::cursor(6)]);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn selection_set_insert_random() {
+    let mut rng = rand::thread_rng();
+
+    for s in [SMALL, MEDIUM, CURSED_LIPSUM] {
+        let mut r = Rope::from(s);
+
+        let mut set = (0..rng.gen_range(1..20))
+            .map(|_| {
+                let anchor = r.floor_char_boundary(rng.gen_range(0..=s.len()));
+                let head = r.floor_char_boundary(rng.gen_range(0..=s.len()));
+                Selection::new(anchor, head)
+            })
+            .collect::<SelectionSet>();
+
+        let mut expected = s.to_owned();
+
+        for selection in set.iter().rev() {
+            expected.replace_range(selection.range(), "🦀");
+        }
+
+        set.insert(&mut r, "🦀");
+        r.assert_invariants();
+
+        assert_eq!(r, expected);
+
+        for selection in &set {
+            assert!(selection.is_empty());
+            assert_eq!(
+                r.byte_slice(..selection.head).to_string().pop(),
+                Some('🦀')
+            );
+        }
+    }
+}
+
+#[test]
+fn selection_set_insert_checks_offsets_before_editing() {
+    let mut r = Rope::from("aéb");
+
+    let mut set: SelectionSet =
+        [0, 2, 4].into_iter().map(Selection::cursor).collect();
+
+    // The cursor at 2 is inside the "é", so nothing should be inserted at
+    // the cursors before or after it either.
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        set.insert(&mut r, "x")
+    }));
+
+    assert!(res.is_err());
+    assert_eq!(r, "aéb");
+}
+
+#[test]
+#[should_panic]
+fn selection_set_insert_out_of_bounds() {
+    let mut r = Rope::from("foo");
+    let mut set = SelectionSet::from(Selection::new(1, 4));
+    set.insert(&mut r, "a");
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::{LARGE, MEDIUM};
+use crop::Rope;
+
+#[test]
+fn stats_empty() {
+    let r = Rope::new();
+    let stats = r.stats();
+    assert_eq!(stats.depth, 0);
+    assert_eq!(stats.shared_nodes, 0);
+    assert_eq!(stats.fill_ratio, 0.0);
+}
+
+#[test]
+fn stats_gap_and_fill() {
+    let r = Rope::from(MEDIUM);
+    let stats = r.stats();
+
+    let capacity = r.byte_len() + stats.gap_bytes;
+
+    assert!(stats.leaf_count > 1);
+    assert!(stats.depth > 0);
+    assert!(stats.fill_ratio > 0.0 && stats.fill_ratio <= 1.0);
+    assert!(stats.allocated_bytes > capacity);
+    assert_eq!(capacity % stats.leaf_count, 0);
+
+    let expected_ratio = r.byte_len() as f64 / capacity as f64;
+    assert!((stats.fill_ratio - expected_ratio).abs() < f64::EPSILON);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn stats_clone_shares_nodes() {
+    let r = Rope::from(LARGE);
+    assert_eq!(r.stats().shared_nodes, 0);
+
+    let mut r2 = r.clone();
+
+    let all_shared = r2.stats().shared_nodes;
+    assert!(all_shared > 0);
+    assert_eq!(r.stats().shared_nodes, all_shared);
+
+    // Editing the clone un-shares the nodes on the path to the edit.
+    r2.insert(r2.byte_len() / 2, "Hello");
+
+    let stats = r2.stats();
+    assert!(stats.shared_nodes > 0);
+    assert!(stats.shared_nodes < all_shared);
+
+    drop(r2);
+    assert_eq!(r.stats().shared_nodes, 0);
+}
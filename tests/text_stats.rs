@@ -0,0 +1,101 @@
+#![cfg(feature = "text-stats")]
+
+use crop::{Rope, RopeBuilder};
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+/// A naive paragraph counter used as a reference.
+fn paragraphs(s: &str) -> usize {
+    let mut count = 0;
+    let mut in_paragraph = false;
+
+    for line in s.split('\n') {
+        let is_blank = line.trim().is_empty();
+        count += (!is_blank && !in_paragraph) as usize;
+        in_paragraph = !is_blank;
+    }
+
+    count
+}
+
+#[test]
+fn text_stats_empty() {
+    let r = Rope::new();
+    assert_eq!(r.char_count(), 0);
+    assert_eq!(r.word_count(), 0);
+    assert_eq!(r.paragraph_count(), 0);
+
+    let r = Rope::from(" \n\t\r\n ");
+    assert_eq!(r.char_count(), 6);
+    assert_eq!(r.word_count(), 0);
+    assert_eq!(r.paragraph_count(), 0);
+}
+
+/// Tests that the stats only depend on the text and not on how it's split
+/// into chunks.
+#[test]
+fn text_stats_independent_of_chunking() {
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let mut builder = RopeBuilder::new();
+        for word in s.split_inclusive(' ') {
+            builder.append(word);
+        }
+        let r = builder.build();
+
+        assert_eq!(r.char_count(), s.chars().count());
+        assert_eq!(r.word_count(), s.split_whitespace().count());
+        assert_eq!(r.paragraph_count(), paragraphs(s));
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn text_stats_random_slices() {
+    let mut rng = rand::thread_rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        for _ in 0..100 {
+            let start = r.floor_char_boundary(rng.gen_range(0..=s.len()));
+            let end = r.ceil_char_boundary(rng.gen_range(start..=s.len()));
+
+            let slice = r.byte_slice(start..end);
+            let s = &s[start..end];
+
+            assert_eq!(slice.char_count(), s.chars().count());
+            assert_eq!(slice.word_count(), s.split_whitespace().count());
+            assert_eq!(slice.paragraph_count(), paragraphs(s));
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn text_stats_random_edits() {
+    let mut rng = rand::thread_rng();
+
+    let insertions = [" ", "foo", "\n", "\n\n", "bar baz", "🦀 ", "\r\n"];
+
+    for s in [SMALL, MEDIUM, CURSED_LIPSUM] {
+        let mut r = Rope::from(s);
+        let mut expected = s.to_owned();
+
+        for _ in 0..100 {
+            let len = expected.len();
+            let start = r.floor_char_boundary(rng.gen_range(0..=len));
+            let end = r.ceil_char_boundary(rng.gen_range(start..=len));
+            let text = insertions[rng.gen_range(0..insertions.len())];
+
+            r.replace(start..end, text);
+            expected.replace_range(start..end, text);
+
+            assert_eq!(r.char_count(), expected.chars().count());
+            assert_eq!(r.word_count(), expected.split_whitespace().count());
+            assert_eq!(r.paragraph_count(), paragraphs(&expected));
+        }
+    }
+}